@@ -7,12 +7,18 @@ pub enum Action {
   Subprocess,
   Log,
   Modify,
+  Edit,
   HelpPopup,
   ContextMenu,
   ReportMenu,
+  CommandPalette,
   Jump,
   DeletePrompt,
   UndoPrompt,
   DonePrompt,
   Error,
+  PomodoroBreak,
+  ProjectPicker,
+  ProjectPreview,
+  ColumnEditor,
 }
@@ -5,15 +5,17 @@ use std::{
   convert::TryInto,
   fs, io,
   io::{Read, Write},
-  path::Path,
+  path::{Path, PathBuf},
   sync::{Arc, Mutex, mpsc},
   time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::{Context as AnyhowContext, Result, anyhow};
-use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
 use crossterm::{
-  event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+  event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+  },
   execute,
   style::style,
   terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
@@ -41,23 +43,38 @@ use versions::Versioning;
 use crate::{
   action::Action,
   calendar::Calendar,
+  command::{Command, sequence_label},
   completion::{CompletionList, get_start_word_under_cursor},
   config::{Config, TaskInfoLocation},
   datetime,
   event::{Event, KeyCode},
+  group::{GroupBy, ReportRow, TaskGroups},
   help::Help,
   history::HistoryContext,
-  keyconfig::KeyConfig,
+  job::{Job, JobQueue},
+  keyconfig::{KeyConfig, KeyMatch, KeyScope},
+  mouse::{self, ClickTargets},
   pane::{
     Pane,
+    agenda::{AgendaEntry, AgendaKind, AgendaState},
+    board::{self, BoardState},
+    calendar::CalendarState,
+    column_editor::{ColumnEditorMode, ColumnEditorState},
     context::{ContextDetails, ContextsState},
-    project::ProjectsState,
+    dependency::DependencyState,
+    palette::{CommandPaletteState, PaletteTarget},
+    project::{self, ProjectColumn, ProjectsState},
+    project_picker::{ProjectPickerState, ProjectPickerTarget},
     report::ReportsState,
+    timesheet::{self, TimesheetAction, TimesheetState, TimesheetTotal},
   },
+  pomodoro::{self, Phase, Pomodoro},
   scrollbar::Scrollbar,
+  sort,
   table::{Row, Table, TableMode, TaskwarriorTuiTableState},
-  task_report::TaskReportTable,
-  ui, utils,
+  task_editor::{DATE_SYNONYMS, FieldKind, RECURRENCE_SYNONYMS, TaskEditor},
+  task_report::{self, TaskReportTable},
+  timer, ui, utils,
 };
 
 const MAX_LINE: usize = 4096;
@@ -127,12 +144,102 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     .split(popup_layout[1])[1]
 }
 
+/// The taskrc used by `task`: `$TASKRC`, `~/.taskrc` or `$XDG_CONFIG_HOME/task/taskrc`
+fn taskrc_path() -> Option<PathBuf> {
+  if let Ok(path) = std::env::var("TASKRC") {
+    return Some(PathBuf::from(path));
+  }
+  let home = dirs::home_dir().map(|home| home.join(".taskrc"));
+  let xdg = dirs::config_dir().map(|config| config.join("task").join("taskrc"));
+  home.into_iter().chain(xdg).find(|path| path.exists())
+}
+
+/// `taskrc` and the files it pulls in with `include`, recursively. Relative includes are looked up next to the
+/// file that includes them; those that cannot be found there, such as the themes shipped with taskwarrior, are left out.
+fn taskrc_files(taskrc: PathBuf) -> Vec<PathBuf> {
+  let mut files = vec![];
+  let mut pending = vec![taskrc];
+  while let Some(path) = pending.pop() {
+    if files.contains(&path) {
+      continue;
+    }
+    let Ok(contents) = fs::read_to_string(&path) else {
+      continue;
+    };
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    for line in contents.lines() {
+      let Some(include) = line.trim().strip_prefix("include").filter(|rest| rest.starts_with(char::is_whitespace)) else {
+        continue;
+      };
+      let include = include.trim();
+      let include = match include.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+        None => Some(dir.join(include)),
+      };
+      pending.extend(include.filter(|path| path.is_file()));
+    }
+    files.push(path);
+  }
+  files
+}
+
+/// Panes whose data is only refreshed once they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LazyPane {
+  Contexts,
+  Reports,
+  Projects,
+  Timesheet,
+  Dependencies,
+  Board,
+}
+
+impl LazyPane {
+  pub const ALL: [LazyPane; 6] = [
+    LazyPane::Contexts,
+    LazyPane::Reports,
+    LazyPane::Projects,
+    LazyPane::Timesheet,
+    LazyPane::Dependencies,
+    LazyPane::Board,
+  ];
+}
+
+/// Re-export everything at least this often, since urgency changes over time without tasks being modified
+const FULL_EXPORT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Above this many changed tasks an incremental export is no faster than a full one
+const MAX_INCREMENTAL_EXPORT: usize = 100;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Mode {
   Tasks(Action),
   Projects,
   Timesheet,
   Calendar,
+  Agenda,
+  Board,
+  Dependencies,
+}
+
+impl Mode {
+  /// The views in the tab bar, in order
+  pub const TABS: [(&'static str, Mode); 7] = [
+    ("Tasks", Mode::Tasks(Action::Report)),
+    ("Projects", Mode::Projects),
+    ("Timesheet", Mode::Timesheet),
+    ("Calendar", Mode::Calendar),
+    ("Agenda", Mode::Agenda),
+    ("Board", Mode::Board),
+    ("Dependencies", Mode::Dependencies),
+  ];
+
+  fn tab_index(&self) -> usize {
+    match self {
+      Mode::Tasks(_) => 0,
+      mode => Self::TABS.iter().position(|(_, m)| m == mode).unwrap_or(0),
+    }
+  }
 }
 
 pub struct TaskwarriorTui {
@@ -146,36 +253,65 @@ pub struct TaskwarriorTui {
   pub modify: LineBuffer,
   pub tasks: Vec<Task>,
   pub all_tasks: Vec<Task>,
+  /// Every active task, in the report or not, for the timers in the tab bar
+  pub active_tasks: Vec<Task>,
+  /// The focus timer, counted down on every tick
+  pub pomodoro: Option<Pomodoro>,
+  /// Masks descriptions, annotations, projects and tags on screen, see `utils::obfuscate`
+  pub obfuscate: bool,
   pub task_details: HashMap<Uuid, String>,
   pub task_details_modified: HashMap<Uuid, Option<Date>>,
   pub marked: HashSet<Uuid>,
   // stores index of current task that is highlighted
   pub current_selection: usize,
+  /// The groups of the task report, from `uda.taskwarrior-tui.task-report.group-by`
+  pub task_groups: TaskGroups,
   pub current_selection_uuid: Option<Uuid>,
   pub current_selection_id: Option<u64>,
   pub task_report_table: TaskReportTable,
   pub calendar_year: i32,
+  pub calendar: CalendarState,
+  pub agenda: AgendaState,
+  pub timesheet: TimesheetState,
   pub mode: Mode,
   pub previous_mode: Option<Mode>,
   pub config: Config,
   pub task_report_info_show: bool,
   pub task_report_height: u16,
+  /// Lines taken up by each task in the task report as last drawn
+  pub task_row_heights: Vec<usize>,
   pub task_details_scroll: u16,
   pub task_details_defaultwidth: u16,
   pub help_popup: Help,
   pub last_export: Option<SystemTime>,
+  /// When the last full or incremental export started, for `modified.after:`
+  pub export_started: Option<SystemTime>,
+  pub last_full_export: Option<SystemTime>,
+  /// Report, filter and context of the last export; tasks can only be merged while these stay the same
+  pub export_key: String,
+  /// Output of `task show`, re-read only when the taskrc or a file it includes changes
+  pub task_show: String,
+  pub taskrc_mtimes: Vec<SystemTime>,
+  pub stale_panes: HashSet<LazyPane>,
   pub keyconfig: KeyConfig,
   pub terminal_width: u16,
   pub terminal_height: u16,
   pub filter_history: HistoryContext,
   pub command_history: HistoryContext,
   pub history_status: Option<String>,
+  /// Keys typed so far of a multi-key sequence such as `gg`
+  pub pending_keys: Vec<KeyCode>,
   pub completion_list: CompletionList,
   pub show_completion_pane: bool,
   pub report: String,
   pub projects: ProjectsState,
+  pub dependencies: DependencyState,
+  pub board: BoardState,
   pub contexts: ContextsState,
   pub reports: ReportsState,
+  pub command_palette: CommandPaletteState,
+  pub project_picker: ProjectPickerState,
+  pub column_editor: ColumnEditorState,
   pub task_version: Versioning,
   pub error: Option<String>,
   pub event_loop: crate::event::EventLoop,
@@ -184,9 +320,10 @@ pub struct TaskwarriorTui {
   pub task_info_location_override: Option<TaskInfoLocation>,
   pub task_info_location_override_width: Option<u16>,
   pub task_exe: String,
-  pub timesheet_data: String,
-  pub timesheet_scroll: u16,
-  pub timesheet_line_count: u16,
+  pub click_targets: ClickTargets,
+  pub task_editor: Option<TaskEditor>,
+  /// `task` commands that change tasks, run in the background
+  pub jobs: JobQueue,
 }
 
 impl TaskwarriorTui {
@@ -231,7 +368,7 @@ impl TaskwarriorTui {
     } else {
       None
     };
-    let event_loop = crate::event::EventLoop::new(tick_rate, init_event_loop);
+    let event_loop = crate::event::EventLoop::new(tick_rate, Some(&c.data_dir()), init_event_loop);
 
     let mut app = Self {
       should_quit: false,
@@ -239,10 +376,14 @@ impl TaskwarriorTui {
       task_table_state: TaskwarriorTuiTableState::default(),
       tasks: vec![],
       all_tasks: vec![],
+      active_tasks: vec![],
+      pomodoro: None,
+      obfuscate: false,
       task_details: HashMap::new(),
       task_details_modified: HashMap::new(),
       marked: HashSet::new(),
       current_selection: 0,
+      task_groups: TaskGroups::new(c.uda_task_report_group_by.clone()),
       current_selection_uuid: None,
       current_selection_id: None,
       current_context_filter: "".to_string(),
@@ -253,6 +394,7 @@ impl TaskwarriorTui {
       mode: Mode::Tasks(Action::Report),
       previous_mode: None,
       task_report_height: 0,
+      task_row_heights: vec![],
       task_details_scroll: 0,
       task_details_defaultwidth: 0,
       task_report_info_show: c.uda_task_report_info_show,
@@ -261,29 +403,44 @@ impl TaskwarriorTui {
       config: c,
       task_report_table: TaskReportTable::new(&data, report, &task_exe)?,
       calendar_year: Local::now().year(),
+      calendar: CalendarState::new(),
+      agenda: AgendaState::new(),
+      timesheet: TimesheetState::new(),
       help_popup: Help::new(&kc),
       last_export: None,
+      export_started: None,
+      last_full_export: None,
+      export_key: String::new(),
+      task_show: data.to_string(),
+      taskrc_mtimes: vec![],
+      stale_panes: LazyPane::ALL.into_iter().collect(),
       keyconfig: kc,
       terminal_width: w,
       terminal_height: h,
       filter_history: HistoryContext::new("filter.history"),
       command_history: HistoryContext::new("command.history"),
       history_status: None,
+      pending_keys: vec![],
       completion_list: CompletionList::with_items(vec![]),
       show_completion_pane: false,
       report: report.to_string(),
       projects: ProjectsState::new(),
+      dependencies: DependencyState::new(),
+      board: BoardState::new(),
       contexts: ContextsState::new(),
       reports: ReportsState::new(),
+      command_palette: CommandPaletteState::new(),
+      project_picker: ProjectPickerState::new(),
+      column_editor: ColumnEditorState::new(),
       task_version,
       error: None,
       event_loop,
       requires_redraw: false,
       changes: utils::Changeset::default(),
       task_exe,
-      timesheet_data: String::new(),
-      timesheet_scroll: 0,
-      timesheet_line_count: 0,
+      click_targets: ClickTargets::default(),
+      task_editor: None,
+      jobs: JobQueue::new(),
     };
 
     for c in app.config.filter.chars() {
@@ -291,6 +448,7 @@ impl TaskwarriorTui {
     }
 
     app.task_report_table.date_time_vague_precise = app.config.uda_task_report_date_time_vague_more_precise;
+    app.obfuscate = app.config.obfuscate;
 
     app.update(true).await?;
 
@@ -313,7 +471,10 @@ impl TaskwarriorTui {
   pub fn start_tui(&mut self) -> Result<Terminal<CrosstermBackend<std::io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    if self.config.uda_mouse {
+      execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.hide_cursor()?;
@@ -324,7 +485,10 @@ impl TaskwarriorTui {
     self.resume_event_loop().await?;
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
-    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableBracketedPaste)?;
+    if self.config.uda_mouse {
+      execute!(io::stdout(), EnableMouseCapture)?;
+    }
     enable_raw_mode()?;
     self.requires_redraw = true;
     terminal.hide_cursor()?;
@@ -347,7 +511,7 @@ impl TaskwarriorTui {
     } else {
       None
     };
-    self.event_loop = crate::event::EventLoop::new(tick_rate, true);
+    self.event_loop = crate::event::EventLoop::new(tick_rate, Some(&self.config.data_dir()), true);
     Ok(())
   }
 
@@ -362,7 +526,10 @@ impl TaskwarriorTui {
   }
 
   pub async fn next(&mut self) -> Option<Event<KeyCode>> {
-    self.event_loop.rx.recv().await
+    tokio::select! {
+      event = self.event_loop.rx.recv() => event,
+      Some(outcome) = self.jobs.next() => Some(Event::JobFinished(outcome)),
+    }
   }
 
   pub async fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
@@ -371,6 +538,7 @@ impl TaskwarriorTui {
         terminal.clear()?;
         self.requires_redraw = false;
       }
+      self.refresh_visible_panes()?;
       terminal.draw(|f| self.draw(f))?;
       // Handle input
       if let Some(event) = self.next().await {
@@ -379,6 +547,10 @@ impl TaskwarriorTui {
             debug!("Received input = {:?}", input);
             self.handle_input(input).await?;
           }
+          Event::Mouse(mouse) => {
+            debug!("Received mouse event = {:?}", mouse);
+            self.handle_mouse(mouse).await?;
+          }
           Event::Paste(paste) => {
             debug!("Received paste of {} bytes", paste.len());
             self.handle_paste(&paste);
@@ -386,6 +558,29 @@ impl TaskwarriorTui {
           Event::Tick => {
             debug!("Tick event");
             self.update(false).await?;
+            let job = self.pomodoro_tick();
+            self.task_submit(job).await?;
+          }
+          Event::DataChanged => {
+            debug!("Task data changed");
+            // Exporting touches the database too, so ignore changes made before our own last export.
+            // While jobs are queued, wait for them to finish instead of showing every step.
+            if self.jobs.is_empty() && self.tasks_changed_since(self.last_export).unwrap_or(true) {
+              self.update(true).await?;
+            }
+          }
+          Event::JobFinished(outcome) => {
+            debug!("Job `{}` finished: {:?}", outcome.title, outcome.result);
+            if let Err(e) = outcome.result {
+              self.error = Some(e);
+              if self.mode != Mode::Tasks(Action::Error) {
+                self.previous_mode = Some(self.mode.clone());
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+            if self.jobs.is_empty() {
+              self.update(true).await?;
+            }
           }
           Event::Closed => {
             debug!("Event loop closed");
@@ -397,6 +592,14 @@ impl TaskwarriorTui {
         break;
       }
     }
+    // Don't lose changes that are still queued
+    while !self.jobs.is_empty() {
+      if let Some(outcome) = self.jobs.next().await
+        && let Err(e) = outcome.result
+      {
+        error!("{}", e);
+      }
+    }
     Ok(())
   }
 
@@ -434,6 +637,12 @@ impl TaskwarriorTui {
       Mode::Tasks(Action::Subprocess | Action::Jump) => {
         Self::insert_text(&mut self.command, text, &mut self.changes);
       }
+      Mode::Tasks(Action::Edit) => {
+        if let Some(editor) = self.task_editor.as_mut() {
+          Self::insert_text(&mut editor.current_mut().buffer, text, &mut self.changes);
+          self.update_input_for_completion();
+        }
+      }
       Mode::Tasks(Action::ContextMenu) => {
         self.contexts.search.push_str(text);
         self.contexts.table_state.select(Some(0));
@@ -442,8 +651,131 @@ impl TaskwarriorTui {
         self.reports.search.push_str(text);
         self.reports.table_state.select(Some(0));
       }
+      Mode::Tasks(Action::CommandPalette) => {
+        self.command_palette.search.push_str(text);
+        self.command_palette.table_state.select(Some(0));
+      }
+      Mode::Tasks(Action::ColumnEditor) if self.column_editor.mode == ColumnEditorMode::Rename => {
+        self.column_editor.label.push_str(text);
+      }
+      Mode::Tasks(Action::ProjectPicker) => {
+        self.project_picker.search.push_str(text);
+        self.project_picker.table_state.select(Some(0));
+      }
+      _ => {}
+    }
+  }
+
+  async fn handle_mouse(&mut self, event: MouseEvent) -> Result<()> {
+    let position = Position::new(event.column, event.row);
+    match event.kind {
+      MouseEventKind::ScrollUp => self.handle_input(KeyCode::Up).await?,
+      MouseEventKind::ScrollDown => self.handle_input(KeyCode::Down).await?,
+      MouseEventKind::Down(MouseButton::Left) => {
+        let double_click = self.click_targets.register_click(position);
+        self.pending_keys.clear();
+        match self.mode {
+          Mode::Tasks(Action::ContextMenu) => {
+            let Some(area) = self.click_targets.context_menu else {
+              return Ok(());
+            };
+            let len = self.contexts.filtered_indices().len();
+            if let Some(i) = mouse::table_row_at(area, self.contexts.table_state.offset(), len, position) {
+              self.contexts.table_state.select(Some(i));
+              if double_click {
+                self.handle_input(KeyCode::Char('\n')).await?;
+              } else {
+                self.maybe_autoselect_context_menu().await?;
+              }
+            }
+          }
+          Mode::Tasks(Action::ReportMenu) => {
+            let Some(area) = self.click_targets.report_menu else {
+              return Ok(());
+            };
+            let len = self.reports.filtered_indices().len();
+            if let Some(i) = mouse::table_row_at(area, self.reports.table_state.offset(), len, position) {
+              self.reports.table_state.select(Some(i));
+              if double_click {
+                self.handle_input(KeyCode::Char('\n')).await?;
+              } else {
+                self.maybe_autoselect_report_menu().await?;
+              }
+            }
+          }
+          Mode::Tasks(Action::Report) | Mode::Projects | Mode::Timesheet | Mode::Calendar | Mode::Agenda | Mode::Board | Mode::Dependencies => {
+            if let Some(mode) = self.click_targets.tab_at(position) {
+              self.set_tab(mode);
+            } else if self.mode == Mode::Tasks(Action::Report)
+              && let Some(area) = self.click_targets.task_report
+              && let Some(i) = mouse::table_column_at(area, &self.click_targets.task_report_columns, position)
+            {
+              self.task_report_table.focused_column = self.task_report_table.shown_columns.get(i).copied();
+              self.task_report_sort();
+            } else if self.mode == Mode::Tasks(Action::Report)
+              && let Some(area) = self.click_targets.task_report
+              && let rows = self.task_groups.rows(self.tasks.len())
+              && let heights = self.task_report_display_heights(&rows)
+              && let Some(i) = mouse::table_row_at_heights(area, self.task_table_state.offset(), &heights, position)
+            {
+              match rows[i] {
+                ReportRow::Task(i) => {
+                  self
+                    .handle_task_report_click(i, event.modifiers.contains(KeyModifiers::SHIFT), double_click)
+                    .await?;
+                }
+                ReportRow::Group(g) => {
+                  self.current_selection = self.task_groups.groups[g].start;
+                  self.task_report_fold();
+                }
+              }
+            }
+          }
+          _ => {}
+        }
+      }
       _ => {}
     }
+    Ok(())
+  }
+
+  /// Marks the tasks from the selected one to task `index`, leaving out those in folded groups
+  fn task_report_mark_range(&mut self, index: usize) {
+    let (start, end) = if index < self.current_selection {
+      (index, self.current_selection)
+    } else {
+      (self.current_selection, index)
+    };
+    for i in (start..=end).filter(|i| !self.task_groups.is_hidden(*i)) {
+      self.marked.insert(*self.tasks[i].uuid());
+    }
+    self.task_table_state.multiple_selection();
+  }
+
+  async fn handle_task_report_click(&mut self, index: usize, extend: bool, double_click: bool) -> Result<()> {
+    if extend {
+      self.task_report_mark_range(index);
+    }
+    self.current_selection = index;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+    if double_click && !extend {
+      if self.config.uda_mouse_double_click_edit && self.config.uda_task_editor_native {
+        self.task_editor_open();
+      } else if self.config.uda_mouse_double_click_edit {
+        match self.task_edit().await {
+          Ok(_) => self.update(true).await?,
+          Err(e) => {
+            self.error = Some(e);
+            self.mode = Mode::Tasks(Action::Error);
+          }
+        }
+      } else {
+        self.task_report_info_show = true;
+      }
+    }
+    self.update(false).await?;
+    Ok(())
   }
 
   pub fn get_context(&mut self) -> Result<()> {
@@ -484,26 +816,68 @@ impl TaskwarriorTui {
     let tab_layout = chunks[0];
     let main_layout = chunks[1];
 
+    self.click_targets.clear();
     self.draw_tabs(f, tab_layout);
     match self.mode {
+      Mode::Tasks(Action::PomodoroBreak) => self.draw_pomodoro_break(f, main_layout),
+      Mode::Tasks(action @ (Action::ProjectPicker | Action::ProjectPreview)) if self.previous_mode == Some(Mode::Projects) => {
+        self.draw_projects(f, main_layout);
+        self.draw_project_picker(f, action, 80, 60);
+      }
       Mode::Tasks(action) => self.draw_task(f, main_layout, action),
       Mode::Projects => self.draw_projects(f, main_layout),
       Mode::Timesheet => self.draw_timesheet(f, main_layout),
       Mode::Calendar => self.draw_calendar(f, main_layout),
+      Mode::Agenda => self.draw_agenda(f, main_layout),
+      Mode::Board => self.draw_board(f, main_layout),
+      Mode::Dependencies => self.draw_dependencies(f, main_layout),
     }
   }
 
-  fn draw_tabs(&self, f: &mut Frame, layout: Rect) {
-    let titles: Vec<&str> = vec!["Tasks", "Projects", "Timesheet", "Calendar"];
-    let tab_names: Vec<_> = titles.into_iter().map(Line::from).collect();
-    let selected_tab = match self.mode {
-      Mode::Tasks(_) => 0,
-      Mode::Projects => 1,
-      Mode::Timesheet => 2,
-      Mode::Calendar => 3,
-    };
+  /// Switches to the view of a tab
+  fn set_tab(&mut self, mode: Mode) {
+    // These keep their own selection while shown, so they only follow the task report when entered
+    if self.mode != mode {
+      match mode {
+        Mode::Dependencies => self.stale_panes.insert(LazyPane::Dependencies),
+        Mode::Board => self.stale_panes.insert(LazyPane::Board),
+        _ => false,
+      };
+    }
+    self.mode = mode;
+  }
+
+  pub fn next_tab(&mut self) {
+    let i = self.mode.tab_index() + 1;
+    if i < Mode::TABS.len() {
+      self.set_tab(Mode::TABS[i].1.clone());
+    } else if self.config.uda_change_focus_rotate {
+      self.set_tab(Mode::TABS[0].1.clone());
+    }
+  }
+
+  pub fn previous_tab(&mut self) {
+    match self.mode.tab_index() {
+      0 if self.config.uda_change_focus_rotate => self.set_tab(Mode::TABS[Mode::TABS.len() - 1].1.clone()),
+      0 => {}
+      i => self.set_tab(Mode::TABS[i - 1].1.clone()),
+    }
+  }
+
+  fn draw_tabs(&mut self, f: &mut Frame, layout: Rect) {
+    let tab_names: Vec<_> = Mode::TABS.iter().map(|(title, _)| Line::from(*title)).collect();
+    let selected_tab = self.mode.tab_index();
     let navbar_block = Block::default().style(self.config.uda_style_navbar);
+    // The Tasks pane shows pending keys in its command line, the other panes have none
+    let pending = match self.mode {
+      Mode::Tasks(_) => None,
+      _ => self.pending_keys_label(),
+    };
     let context = Line::from(vec![
+      Span::styled(
+        pending.map(|p| format!("{} ", p)).unwrap_or_default(),
+        Style::default().add_modifier(Modifier::BOLD),
+      ),
       Span::from(&self.report),
       Span::from(" "),
       Span::from("["),
@@ -514,6 +888,13 @@ impl TaskwarriorTui {
       }),
       Span::from("]"),
     ]);
+    let mut timers = self.timer_line();
+    if timers.width() > layout.width as usize / 2 {
+      timers = Line::from(vec![
+        Span::styled(format!("▶ {} active", self.active_tasks.len()), self.config.uda_style_timer),
+        Span::raw(" "),
+      ]);
+    }
     let tabs = Tabs::new(tab_names)
       .block(navbar_block.clone())
       .select(selected_tab)
@@ -521,11 +902,69 @@ impl TaskwarriorTui {
       .highlight_style(Style::default().add_modifier(Modifier::BOLD));
     let rects = Layout::default()
       .direction(Direction::Horizontal)
-      .constraints([Constraint::Min(0), Constraint::Length(context.width() as u16)])
+      .constraints([
+        Constraint::Min(0),
+        Constraint::Length(timers.width() as u16),
+        Constraint::Length(context.width() as u16),
+      ])
       .split(layout);
 
     f.render_widget(tabs, rects[0]);
-    f.render_widget(Paragraph::new(Text::from(context)).block(navbar_block), rects[1]);
+    f.render_widget(Paragraph::new(timers).block(navbar_block.clone()), rects[1]);
+    f.render_widget(Paragraph::new(Text::from(context)).block(navbar_block), rects[2]);
+
+    self.click_targets.set_tabs(rects[0], &Mode::TABS);
+  }
+
+  /// How long each active task has been running, and in total if it ran before, e.g. `▶ 12 0:42:10 (3:12:00)`
+  fn timer_line(&self) -> Line<'static> {
+    if !self.config.uda_timer_show {
+      return Line::default();
+    }
+    let now = Utc::now().naive_utc();
+    let mut spans = vec![];
+    if let Some(pomodoro) = &self.pomodoro {
+      let label = match pomodoro.phase {
+        Phase::Work => format!("Pomodoro #{}", pomodoro.number),
+        Phase::Break => "Break".to_string(),
+      };
+      spans.push(Span::styled(
+        format!("{} {}", label, timer::format_duration(pomodoro.remaining(now))),
+        self.config.uda_style_timer,
+      ));
+      spans.push(Span::raw(" "));
+    }
+    for task in &self.active_tasks {
+      let Some(running) = timer::running(task, now) else {
+        continue;
+      };
+      let tracked = timer::tracked(
+        task,
+        now,
+        &self.config.uda_timer_uda,
+        &self.config.journal_time_start_annotation,
+        &self.config.journal_time_stop_annotation,
+      );
+      let id = task
+        .id()
+        .filter(|id| *id > 0)
+        .map_or_else(|| task.uuid().to_string()[..8].to_string(), |id| id.to_string());
+      let mut text = format!("▶ {} {}", id, timer::format_duration(running));
+      if tracked > running {
+        text.push_str(&format!(" ({})", timer::format_duration(tracked)));
+      }
+      spans.push(Span::styled(text, self.config.uda_style_timer));
+      spans.push(Span::raw(" "));
+    }
+    Line::from(spans)
+  }
+
+  fn pending_keys_label(&self) -> Option<String> {
+    if self.pending_keys.is_empty() {
+      None
+    } else {
+      Some(format!("{}-", sequence_label(&self.pending_keys)))
+    }
   }
 
   pub fn draw_debug(&mut self, f: &mut Frame) {
@@ -537,111 +976,320 @@ impl TaskwarriorTui {
   }
 
   pub fn draw_projects(&mut self, f: &mut Frame, rect: Rect) {
-    let data = self.projects.data.clone();
-    let p = Paragraph::new(Text::from(&data[..]));
+    if self.projects.rows.is_empty() {
+      let p = Paragraph::new("No projects have remaining tasks.").style(Style::default().add_modifier(Modifier::DIM));
+      f.render_widget(p, rect);
+      return;
+    }
+    const GAUGE_WIDTH: usize = 10;
+    let style_header = self.config.color.get("color.label").copied().unwrap_or_default();
+    let now = Utc::now().naive_utc();
+
+    // The focused column is underlined, and the sorted one shows its direction
+    let headers: Vec<String> = ProjectColumn::ALL
+      .iter()
+      .map(|column| match self.projects.sort {
+        Some((sorted, ascending)) if sorted == *column => format!("{} {}", column.header(), if ascending { "▲" } else { "▼" }),
+        _ => column.header().to_string(),
+      })
+      .collect();
+    let data: Vec<Vec<String>> = self
+      .projects
+      .rows
+      .iter()
+      .map(|project| {
+        let complete = project.complete();
+        let filled = complete * GAUGE_WIDTH / 100;
+        vec![
+          self.masked(&self.projects.name_cell(project)),
+          project.remaining.to_string(),
+          task_report::vague_format_date_time(now - project.avg_age, now, false),
+          format!("{:>3}% {}{}", complete, "█".repeat(filled), "░".repeat(GAUGE_WIDTH - filled)),
+        ]
+      })
+      .collect();
+    let styles: Vec<Style> = self
+      .projects
+      .rows
+      .iter()
+      .map(|project| {
+        if project.name == project::NO_PROJECT {
+          Style::default()
+        } else {
+          self.style_for_project(std::slice::from_ref(&project.name))
+        }
+      })
+      .collect();
+
+    let maximum_column_width = rect.width;
+    let widths = self.calculate_widths(&data, &headers, maximum_column_width);
+    let constraints: Vec<Constraint> = widths
+      .iter()
+      .map(|w| Constraint::Length((*w).try_into().unwrap_or(maximum_column_width)))
+      .collect();
+    let selected = self.projects.current_selection;
+    let highlight_style = styles
+      .get(selected)
+      .copied()
+      .unwrap_or_default()
+      .patch(self.config.uda_style_report_selection);
+    let focused = ProjectColumn::ALL
+      .iter()
+      .position(|c| *c == self.projects.focused_column)
+      .unwrap_or_default();
+    let rows = data.iter().zip(styles).map(|(cells, style)| Row::StyledData(cells.iter(), style));
+    let t = Table::new(headers.iter(), rows)
+      .header_style(style_header)
+      .focused_header(focused, Style::default().add_modifier(Modifier::UNDERLINED | Modifier::BOLD))
+      .highlight_style(highlight_style)
+      .highlight_symbol(&self.config.uda_selection_indicator)
+      .mark_highlight_symbol(&self.config.uda_mark_highlight_indicator)
+      .unmark_highlight_symbol(&self.config.uda_unmark_highlight_indicator)
+      .mark_symbol(&self.config.uda_mark_indicator)
+      .unmark_symbol(&self.config.uda_unmark_indicator)
+      .widths(&constraints);
+    f.render_stateful_widget(t, rect, &mut self.projects.table_state);
+  }
+
+  pub fn draw_board(&mut self, f: &mut Frame, rect: Rect) {
+    if self.board.columns.is_empty() {
+      return;
+    }
+    let constraints = vec![Constraint::Ratio(1, self.board.columns.len() as u32); self.board.columns.len()];
+    let areas = Layout::default().direction(Direction::Horizontal).constraints(constraints).split(rect);
+    for (i, (column, area)) in self.board.columns.iter().zip(areas.iter()).enumerate() {
+      let focused = i == self.board.focus;
+      let title_style = if focused {
+        Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+      } else {
+        Style::default().add_modifier(Modifier::BOLD)
+      };
+      let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(Span::styled(format!(" {} ({}) ", column.title(), column.tasks.len()), title_style));
+      let height = area.height.saturating_sub(2) as usize;
+      let offset = (column.selection + 1).saturating_sub(height);
+      let lines: Vec<Line> = column
+        .tasks
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .filter_map(|(j, uuid)| {
+          let task = self.tasks.iter().find(|t| t.uuid() == uuid)?;
+          let mark = if self.marked.contains(uuid) {
+            &self.config.uda_mark_indicator
+          } else {
+            &self.config.uda_unmark_indicator
+          };
+          let id = task.id().filter(|id| *id > 0).map(|id| id.to_string()).unwrap_or_default();
+          let mut style = self.style_for_task(task);
+          if focused && j == column.selection {
+            style = style.patch(self.config.uda_style_report_selection);
+          }
+          Some(Line::from(vec![
+            Span::raw(mark.clone()),
+            Span::styled(format!("{} {}", id, self.masked(task.description())), style),
+          ]))
+        })
+        .collect();
+      f.render_widget(Paragraph::new(Text::from(lines)).block(block), *area);
+    }
+  }
+
+  pub fn draw_dependencies(&mut self, f: &mut Frame, rect: Rect) {
+    if self.dependencies.rows.is_empty() {
+      let p = Paragraph::new("No task in this report depends on another task.").style(Style::default().add_modifier(Modifier::DIM));
+      f.render_widget(p, rect);
+      return;
+    }
+    self.dependencies.scroll_to_selection(rect.height);
+    let lines: Vec<Line> = self
+      .dependencies
+      .rows
+      .iter()
+      .enumerate()
+      .map(|(i, row)| {
+        let mut style = self
+          .tasks
+          .iter()
+          .find(|t| *t.uuid() == row.uuid)
+          .map(|t| self.style_for_task(t))
+          .unwrap_or_default();
+        if !row.in_report {
+          style = style.add_modifier(Modifier::DIM);
+        }
+        if row.finished {
+          style = style.add_modifier(Modifier::CROSSED_OUT);
+        }
+        let mark = if self.marked.contains(&row.uuid) {
+          &self.config.uda_mark_indicator
+        } else {
+          &self.config.uda_unmark_indicator
+        };
+        let mut spans = vec![
+          Span::raw(mark.clone()),
+          Span::raw(row.prefix.clone()),
+          Span::styled(row.text(|d| self.masked(d)), style),
+        ];
+        if row.blocked {
+          spans.push(Span::styled(" [blocked]", Style::default().add_modifier(Modifier::DIM)));
+        }
+        if row.repeated {
+          spans.push(Span::styled(" (see above)", Style::default().add_modifier(Modifier::DIM)));
+        }
+        let line = Line::from(spans);
+        if i == self.dependencies.current_selection {
+          line.style(self.config.uda_style_report_selection)
+        } else {
+          line
+        }
+      })
+      .collect();
+    let p = Paragraph::new(Text::from(lines)).scroll((self.dependencies.scroll, 0));
     f.render_widget(p, rect);
   }
 
   pub fn update_timesheet(&mut self) -> Result<()> {
-    let output = std::process::Command::new(&self.task_exe)
-      .arg("rc.color=off")
-      .arg("rc._forcecolor=off")
-      .arg("rc.verbose=nothing")
-      .arg(format!("rc.defaultwidth={}", self.terminal_width))
-      .arg("timesheet")
-      .output()
-      .context("Unable to run `task timesheet`")?;
-    self.timesheet_data = String::from_utf8_lossy(&output.stdout).into_owned();
-    self.timesheet_line_count = self.timesheet_data.lines().count() as u16;
-    // Scroll to end so the most recent week is visible by default.
-    self.timesheet_scroll = self.timesheet_line_count.saturating_sub(self.terminal_height);
+    // Completed tasks are usually not in the report, so the timesheet reads them from `task export all`
+    self.export_all_tasks()?;
+    let current = self.timesheet.selected().map(|r| r.uuid);
+    self.timesheet.update_data(&self.all_tasks, self.config.weekstart, current);
     Ok(())
   }
 
-  fn styled_timesheet_lines(&self) -> Vec<Line<'static>> {
+  pub fn draw_timesheet(&mut self, f: &mut Frame, rect: Rect) {
+    let totals_height = if self.timesheet.totals.is_empty() {
+      0
+    } else {
+      self.timesheet.totals.len() as u16 + 3
+    };
+    let chunks = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(1), Constraint::Min(0), Constraint::Length(totals_height)])
+      .split(rect);
+    let title = self.timesheet.period_label(self.config.weekstart);
+    f.render_widget(
+      Paragraph::new(Line::styled(title, Style::default().add_modifier(Modifier::BOLD))),
+      chunks[0],
+    );
+    if self.timesheet.rows.is_empty() {
+      let p = Paragraph::new("Nothing was added, started or completed in this period.").style(Style::default().add_modifier(Modifier::DIM));
+      f.render_widget(p, chunks[1]);
+      return;
+    }
+
     let style_header = self.config.color.get("color.label").copied().unwrap_or_default();
     let style_completed = self.config.color.get("color.completed").copied().unwrap_or_default();
     let style_active = self.config.color.get("color.active").copied().unwrap_or_default();
     let style_alternate = self.config.color.get("color.alternate").copied().unwrap_or_default();
     let style_footnote = self.config.color.get("color.footnote").copied().unwrap_or_default();
 
-    // Regex to detect the summary footer line: "N completed, N started."
-    let summary_re = regex::Regex::new(r"^\d+ \w+").unwrap();
-
-    let mut lines: Vec<Line> = Vec::new();
-    let mut seen_header = false;
-    let mut week_index: usize = 0;
-    // Track the action style of the last task row so continuation lines match it.
-    let mut last_action_style = Style::default();
-
-    for line in self.timesheet_data.lines() {
-      let trimmed = line.trim();
-
-      // Detect the start of a new week block: lines like "W9 ...", "W10 ...", etc.
-      let is_week_header = trimmed.starts_with('W') && trimmed.len() > 1 && trimmed.chars().nth(1).is_some_and(|c| c.is_ascii_digit());
-
-      let style = if !seen_header && (trimmed.starts_with("Wk") || trimmed.starts_with("---")) {
-        // Column header and separator lines
-        if !trimmed.starts_with("---") {
-          seen_header = true;
-        }
-        style_header
-      } else if is_week_header {
-        week_index += 1;
-        let base = if week_index.is_multiple_of(2) {
-          style_alternate
-        } else {
-          Style::default()
-        };
-        if trimmed.contains("Completed") {
-          let s = base.patch(style_completed);
-          last_action_style = s;
-          s
-        } else if trimmed.contains("Started") {
-          let s = base.patch(style_active);
-          last_action_style = s;
-          s
-        } else {
-          last_action_style = base;
-          base
-        }
-      } else if trimmed.is_empty() {
-        // Blank separator between weeks — no style
-        Style::default()
-      } else if summary_re.is_match(trimmed) {
-        style_footnote
-      } else if trimmed.contains("Completed") {
-        let base = if week_index.is_multiple_of(2) {
-          style_alternate
-        } else {
-          Style::default()
-        };
-        let s = base.patch(style_completed);
-        last_action_style = s;
-        s
-      } else if trimmed.contains("Started") {
-        let base = if week_index.is_multiple_of(2) {
-          style_alternate
-        } else {
-          Style::default()
-        };
-        let s = base.patch(style_active);
-        last_action_style = s;
-        s
+    let headers: Vec<String> = ["Date", "Time", "ID", "Action", "Project", "Description"].map(String::from).to_vec();
+    let mut data = vec![];
+    let mut styles = vec![];
+    let mut day = None;
+    let mut day_index = 0;
+    for row in &self.timesheet.rows {
+      // The date is only written on the first row of each day, and days alternate in color
+      let date = if day == Some(row.time.date()) {
+        String::new()
       } else {
-        // Continuation lines and non-action rows — inherit the style of the parent row.
-        last_action_style
+        day = Some(row.time.date());
+        day_index += 1;
+        row.time.format("%a %Y-%m-%d").to_string()
       };
-
-      lines.push(Line::styled(line.to_string(), style));
+      let id = row.id.map_or_else(|| row.uuid.to_string()[..8].to_string(), |id| id.to_string());
+      data.push(vec![
+        date,
+        row.time.format("%H:%M").to_string(),
+        id,
+        row.action.name().to_string(),
+        self.masked(&row.project),
+        self.masked(&row.description),
+      ]);
+      let base = if day_index % 2 == 0 { style_alternate } else { Style::default() };
+      styles.push(match row.action {
+        TimesheetAction::Added => base,
+        TimesheetAction::Started => base.patch(style_active),
+        TimesheetAction::Completed => base.patch(style_completed),
+      });
     }
 
-    lines
-  }
+    let maximum_column_width = chunks[1].width;
+    let widths = self.calculate_widths(&data, &headers, maximum_column_width);
+    let constraints: Vec<Constraint> = widths
+      .iter()
+      .map(|w| Constraint::Length((*w).try_into().unwrap_or(maximum_column_width)))
+      .collect();
+    let selected = self.timesheet.current_selection;
+    let highlight_style = styles
+      .get(selected)
+      .copied()
+      .unwrap_or_default()
+      .patch(self.config.uda_style_report_selection);
+    let rows = data.iter().zip(styles).map(|(cells, style)| Row::StyledData(cells.iter(), style));
+
+    let table_state = &mut self.timesheet.table_state;
+    table_state.clear();
+    for (i, row) in self.timesheet.rows.iter().enumerate() {
+      if self.marked.contains(&row.uuid) {
+        table_state.mark(Some(i));
+      }
+    }
+    table_state.select(Some(selected));
+    match self.task_table_state.mode() {
+      TableMode::SingleSelection => table_state.single_selection(),
+      TableMode::MultipleSelection => table_state.multiple_selection(),
+    }
+    let t = Table::new(headers.iter(), rows)
+      .header_style(style_header.add_modifier(Modifier::UNDERLINED))
+      .highlight_style(highlight_style)
+      .highlight_symbol(&self.config.uda_selection_indicator)
+      .mark_highlight_symbol(&self.config.uda_mark_highlight_indicator)
+      .unmark_highlight_symbol(&self.config.uda_unmark_highlight_indicator)
+      .mark_symbol(&self.config.uda_mark_indicator)
+      .unmark_symbol(&self.config.uda_unmark_indicator)
+      .widths(&constraints);
+    f.render_stateful_widget(t, chunks[1], table_state);
 
-  pub fn draw_timesheet(&mut self, f: &mut Frame, rect: Rect) {
-    let p = Paragraph::new(Text::from(self.styled_timesheet_lines())).scroll((self.timesheet_scroll, 0));
-    f.render_widget(p, rect);
+    // Totals by project, then for the whole period
+    let count = |n: usize| n.to_string();
+    let mut totals: Vec<(Vec<String>, Style)> = self
+      .timesheet
+      .totals
+      .iter()
+      .map(|t| {
+        let project = if t.project.is_empty() {
+          "(none)".to_string()
+        } else {
+          self.masked(&t.project)
+        };
+        (vec![project, count(t.added), count(t.started), count(t.completed)], Style::default())
+      })
+      .collect();
+    let sum = |field: fn(&TimesheetTotal) -> usize| count(self.timesheet.totals.iter().map(field).sum());
+    totals.push((
+      vec!["Total".to_string(), sum(|t| t.added), sum(|t| t.started), sum(|t| t.completed)],
+      style_footnote,
+    ));
+    let project_width = totals.iter().map(|(cells, _)| cells[0].width()).max().unwrap_or_default().max(7) as u16;
+    let constraints = [
+      Constraint::Length(project_width),
+      Constraint::Length(5),
+      Constraint::Length(7),
+      Constraint::Length(9),
+    ];
+    let headers = ["Project", "Added", "Started", "Completed"];
+    let t = Table::new(headers.iter(), totals.iter().map(|(cells, style)| Row::StyledData(cells.iter(), *style)))
+      .block(Block::default().borders(Borders::TOP).title("Totals"))
+      .header_style(style_header.add_modifier(Modifier::UNDERLINED))
+      .header_gap(0)
+      .widths(&constraints);
+    let mut state = TaskwarriorTuiTableState::default();
+    state.select(None);
+    f.render_stateful_widget(t, chunks[2], &mut state);
   }
 
   fn style_for_project(&self, project: &[String]) -> Style {
@@ -662,32 +1310,206 @@ impl TaskwarriorTui {
   }
 
   pub fn draw_calendar(&mut self, f: &mut Frame, layout: Rect) {
+    let mut calendar_area = layout;
+    if self.calendar.agenda {
+      let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(40)])
+        .split(layout);
+      calendar_area = chunks[0];
+      self.draw_calendar_agenda(f, chunks[1]);
+    }
     let mut c = Calendar::default()
       .today_style(self.config.uda_style_calendar_today)
       .year(self.calendar_year)
-      .date_style(self.get_dates_with_styles())
       .months_per_row(self.config.uda_calendar_months_per_row)
-      .start_on_monday(self.config.weekstart);
+      .start_on_monday(self.config.weekstart)
+      .selected(self.calendar.date)
+      .selected_style(self.config.uda_style_calendar_selection);
+    if self.calendar.heatmap {
+      c = c
+        .heatmap(true)
+        .heatmap_style(self.config.uda_style_calendar_heatmap)
+        .date_counts(self.get_completion_counts());
+    } else {
+      c = c.date_style(self.get_dates_with_styles()).date_counts(self.get_date_counts());
+    }
     c.title_background_color = self.config.uda_style_calendar_title.bg.unwrap_or(Color::Reset);
-    f.render_widget(c, layout);
+    f.render_widget(c, calendar_area);
   }
 
-  fn task_info_location(&mut self, width: u16) -> TaskInfoLocation {
-    if self.config.uda_task_report_info_location == TaskInfoLocation::Auto
-      && self.task_info_location_override_width.is_some()
-      && self.task_info_location_override_width != Some(width)
-    {
-      self.task_info_location_override = None;
-      self.task_info_location_override_width = None;
-    }
-
-    self
-      .task_info_location_override
-      .unwrap_or(self.config.uda_task_report_info_location)
-      .resolve(width)
-  }
+  pub fn draw_agenda(&mut self, f: &mut Frame, rect: Rect) {
+    let days = self.agenda.days(self.config.weekstart);
+    let entries = self.agenda_entries();
+    let grabbed = self.agenda.grabbed.as_ref().and_then(|e| self.tasks.iter().find(|t| *t.uuid() == e.uuid));
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(u16::from(grabbed.is_some())),
+      ])
+      .split(rect);
+    let mut constraints = vec![Constraint::Length(6)];
+    constraints.extend(days.iter().map(|_| Constraint::Ratio(1, days.len() as u32)));
+    let header = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(constraints.clone())
+      .split(rows[0]);
+    let grid = Layout::default().direction(Direction::Horizontal).constraints(constraints).split(rows[1]);
 
-  fn toggle_task_info_location(&mut self) {
+    // An hour takes as many lines as its busiest day needs
+    let heights: Vec<usize> = (0..24)
+      .map(|hour| days.iter().map(|d| AgendaState::slot(&entries, *d, hour).len()).max().unwrap_or(0).max(1))
+      .collect();
+    let cursor_item = if grabbed.is_some() { 0 } else { self.agenda.item };
+    let cursor_line = heights[..self.agenda.hour as usize].iter().sum::<usize>() + cursor_item;
+    let height = rows[1].height as usize;
+    let mut scroll = self.agenda.scroll as usize;
+    if cursor_line < scroll {
+      scroll = cursor_line;
+    } else if height > 0 && cursor_line >= scroll + height {
+      scroll = cursor_line + 1 - height;
+    }
+    self.agenda.scroll = scroll as u16;
+
+    let now = Local::now().naive_local();
+    let labels: Vec<Line> = heights
+      .iter()
+      .enumerate()
+      .flat_map(|(hour, h)| {
+        let style = if now.hour() as usize == hour && days.contains(&now.date()) {
+          self.config.uda_style_calendar_today
+        } else {
+          Style::default().add_modifier(Modifier::DIM)
+        };
+        std::iter::once(Line::styled(format!("{:02}:00", hour), style)).chain(std::iter::repeat_n(Line::default(), h - 1))
+      })
+      .collect();
+    f.render_widget(Paragraph::new(labels).scroll((self.agenda.scroll, 0)), grid[0]);
+
+    for (i, day) in days.iter().enumerate() {
+      let mut style = Style::default().add_modifier(Modifier::BOLD);
+      if *day == now.date() {
+        style = style.patch(self.config.uda_style_calendar_today);
+      }
+      if *day == self.agenda.day {
+        style = style.add_modifier(Modifier::UNDERLINED);
+      }
+      f.render_widget(Paragraph::new(Span::styled(day.format(" %a %Y-%m-%d").to_string(), style)), header[i + 1]);
+
+      let width = grid[i + 1].width.saturating_sub(1) as usize;
+      let mut lines = vec![];
+      for (hour, h) in heights.iter().enumerate() {
+        let slot = AgendaState::slot(&entries, *day, hour as u32);
+        for j in 0..*h {
+          let cursor = *day == self.agenda.day && hour as u32 == self.agenda.hour && j == cursor_item;
+          let (text, mut style) = match (cursor, grabbed, slot.get(j)) {
+            (true, Some(task), _) => (format!("→ {}", self.agenda_task_label(task)), Style::default()),
+            (_, _, Some(entry)) => self.agenda_entry_text(entry),
+            _ => (String::new(), Style::default()),
+          };
+          if cursor {
+            style = style.patch(self.config.uda_style_calendar_selection);
+          }
+          lines.push(Line::styled(format!("{:<width$}", text), style));
+        }
+      }
+      let block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().add_modifier(Modifier::DIM));
+      f.render_widget(Paragraph::new(lines).block(block).scroll((self.agenda.scroll, 0)), grid[i + 1]);
+    }
+
+    if let Some(task) = grabbed {
+      let select = sequence_label(&self.keyconfig.sequence(KeyScope::Agenda, Command::Select));
+      let hint = format!(
+        "Moving {}: go to an hour and press {} to schedule it there, or Esc to leave it where it was",
+        self.agenda_task_label(task),
+        select
+      );
+      f.render_widget(Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)), rows[2]);
+    }
+  }
+
+  fn agenda_task_label(&self, task: &Task) -> String {
+    match task.id().filter(|id| *id > 0) {
+      Some(id) => format!("{} {}", id, self.masked(task.description())),
+      None => self.masked(task.description()),
+    }
+  }
+
+  /// The text of an agenda entry, marked by kind: `•` scheduled, `!` due, and `▶` then `┃` for the hours a task has been active
+  fn agenda_entry_text(&self, entry: &AgendaEntry) -> (String, Style) {
+    let Some(task) = self.tasks.iter().find(|t| *t.uuid() == entry.uuid) else {
+      return (String::new(), Style::default());
+    };
+    let label = self.agenda_task_label(task);
+    let mut style = self.style_for_task(task);
+    let text = match entry.kind {
+      AgendaKind::Scheduled => format!("• {:02}:{:02} {}", entry.hour, entry.minute, label),
+      AgendaKind::Due => format!("! {:02}:{:02} {}", entry.hour, entry.minute, label),
+      AgendaKind::Active { first } => {
+        style = style.add_modifier(Modifier::BOLD);
+        if first {
+          format!("▶ {:02}:{:02} {}", entry.hour, entry.minute, label)
+        } else {
+          format!("┃ {}", label)
+        }
+      }
+    };
+    if self.agenda.grabbed.as_ref().is_some_and(|e| e.uuid == entry.uuid) {
+      style = style.add_modifier(Modifier::DIM);
+    }
+    (text, style)
+  }
+
+  fn draw_calendar_agenda(&self, f: &mut Frame, rect: Rect) {
+    let block = Block::default()
+      .borders(Borders::ALL)
+      .border_type(BorderType::Rounded)
+      .title(Span::styled(
+        format!(" {} ", self.calendar.date.format("%a %Y-%m-%d")),
+        Style::default().add_modifier(Modifier::BOLD),
+      ));
+    let (agenda, empty) = if self.calendar.heatmap {
+      (self.calendar.agenda(&self.all_tasks.iter().collect::<Vec<_>>()), "Nothing completed")
+    } else {
+      (self.calendar.agenda(&self.calendar_tasks()), "Nothing due, scheduled or waiting")
+    };
+    let lines: Vec<Line> = if agenda.is_empty() {
+      vec![Line::styled(empty, Style::default().add_modifier(Modifier::DIM))]
+    } else {
+      agenda
+        .into_iter()
+        .map(|(kind, task)| {
+          let id = task.id().filter(|id| *id > 0).map(|id| id.to_string()).unwrap_or_default();
+          Line::from(vec![
+            Span::styled(format!("{:<10}", kind), Style::default().add_modifier(Modifier::DIM)),
+            Span::styled(format!("{} {}", id, self.masked(task.description())), self.style_for_task(task)),
+          ])
+        })
+        .collect()
+    };
+    f.render_widget(Paragraph::new(Text::from(lines)).block(block), rect);
+  }
+
+  fn task_info_location(&mut self, width: u16) -> TaskInfoLocation {
+    if self.config.uda_task_report_info_location == TaskInfoLocation::Auto
+      && self.task_info_location_override_width.is_some()
+      && self.task_info_location_override_width != Some(width)
+    {
+      self.task_info_location_override = None;
+      self.task_info_location_override_width = None;
+    }
+
+    self
+      .task_info_location_override
+      .unwrap_or(self.config.uda_task_report_info_location)
+      .resolve(width)
+  }
+
+  fn toggle_task_info_location(&mut self) {
     let next = match self.task_info_location(self.terminal_width) {
       TaskInfoLocation::Bottom => TaskInfoLocation::Right,
       TaskInfoLocation::Right => TaskInfoLocation::Bottom,
@@ -738,12 +1560,11 @@ impl TaskwarriorTui {
     }
 
     // calculate selected tasks
-    let selected = self.current_selection;
     let task_ids = if self.tasks.is_empty() {
       vec!["0".to_string()]
     } else {
       match self.task_table_state.mode() {
-        TableMode::SingleSelection => vec![self.tasks[selected].id().unwrap_or_default().to_string()],
+        TableMode::SingleSelection => self.task_current().map(|t| t.id().unwrap_or_default().to_string()).into_iter().collect(),
         TableMode::MultipleSelection => {
           let mut tids = vec![];
           for uuid in &self.marked {
@@ -762,6 +1583,8 @@ impl TaskwarriorTui {
 
   fn handle_task_mode_action(&mut self, f: &mut Frame, rects: &[Rect], task_ids: &[String], action: Action) {
     match action {
+      // Takes the whole screen, see `draw_pomodoro_break`
+      Action::PomodoroBreak => {}
       Action::Error => {
         self.draw_command(
           f,
@@ -795,8 +1618,15 @@ impl TaskwarriorTui {
         self.draw_command(
           f,
           rects[1],
-          self.filter.as_str(),
-          (Span::raw("Filter Tasks"), self.history_status.as_ref().map(Span::raw)),
+          &self.masked_filter(),
+          (
+            Span::raw("Filter Tasks"),
+            self
+              .pending_keys_label()
+              .or_else(|| self.jobs.status())
+              .or_else(|| self.history_status.clone())
+              .map(Span::raw),
+          ),
           Self::get_position(&self.filter),
           false,
           self.error.clone(),
@@ -987,7 +1817,7 @@ impl TaskwarriorTui {
         self.draw_command(
           f,
           rects[1],
-          self.filter.as_str(),
+          &self.masked_filter(),
           ("Filter Tasks".into(), None),
           Self::get_position(&self.filter),
           false,
@@ -1000,7 +1830,7 @@ impl TaskwarriorTui {
         self.draw_command(
           f,
           rects[1],
-          self.filter.as_str(),
+          &self.masked_filter(),
           ("Filter Tasks".into(), None),
           Self::get_position(&self.filter),
           false,
@@ -1009,11 +1839,50 @@ impl TaskwarriorTui {
         );
         self.draw_context_menu(f, 80, 50);
       }
+      Action::ColumnEditor => {
+        self.draw_command(
+          f,
+          rects[1],
+          &self.masked_filter(),
+          ("Filter Tasks".into(), None),
+          Self::get_position(&self.filter),
+          false,
+          self.error.clone(),
+          None,
+        );
+        self.draw_column_editor(f, 60, 50);
+      }
+      Action::ProjectPicker | Action::ProjectPreview => {
+        self.draw_command(
+          f,
+          rects[1],
+          &self.masked_filter(),
+          ("Filter Tasks".into(), None),
+          Self::get_position(&self.filter),
+          false,
+          self.error.clone(),
+          None,
+        );
+        self.draw_project_picker(f, action, 80, 60);
+      }
+      Action::Edit => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Enter to save, Esc to cancel, Up/Down to move between fields, Tab to complete",
+          (Span::styled("Edit Task", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+          None,
+        );
+        self.draw_task_editor(f, 80, 80);
+      }
       Action::ReportMenu => {
         self.draw_command(
           f,
           rects[1],
-          self.filter.as_str(),
+          &self.masked_filter(),
           ("Filter Tasks".into(), None),
           Self::get_position(&self.filter),
           false,
@@ -1022,6 +1891,10 @@ impl TaskwarriorTui {
         );
         self.draw_report_menu(f, 80, 50);
       }
+      Action::CommandPalette => {
+        // The palette has a search line of its own, so the filter is not shown under it
+        self.draw_command_palette(f, 80, 50);
+      }
       Action::DonePrompt => {
         let label = if task_ids.len() > 1 {
           format!("Done Tasks {}?", task_ids.join(","))
@@ -1096,23 +1969,119 @@ impl TaskwarriorTui {
     }
   }
 
+  /// The tasks of the current report placed on the agenda's hour grid
+  pub fn agenda_entries(&self) -> Vec<AgendaEntry> {
+    self.agenda.entries(&self.tasks, self.config.weekstart, Local::now().naive_local())
+  }
+
+  /// The tasks on the Calendar pane: the current report, or with `uda.taskwarrior-tui.calendar.all-tasks` every task that is not deleted
+  pub fn calendar_tasks(&self) -> Vec<&Task> {
+    if self.config.uda_calendar_all_tasks {
+      self.all_tasks.iter().filter(|t| *t.status() != TaskStatus::Deleted).collect()
+    } else {
+      self.tasks.iter().collect()
+    }
+  }
+
+  /// The dates of the configured sources, in the order the sources are listed, so the first one wins on a day
   pub fn get_dates_with_styles(&self) -> Vec<(chrono::NaiveDate, Style)> {
-    if !self.tasks.is_empty() {
-      let tasks = &self.tasks;
-      tasks
+    let tasks = self.calendar_tasks();
+    let mut dates = vec![];
+    for source in &self.config.uda_calendar_date_sources {
+      for task in &tasks {
+        if let Some(date) = source.date(task) {
+          let style = match self.config.uda_style_calendar_sources.get(source) {
+            Some(style) => *style,
+            None => self.style_for_task(task),
+          };
+          dates.push((datetime::local_from_utc(date).date_naive(), style));
+        }
+      }
+    }
+    dates
+  }
+
+  /// How many tasks were completed on each day, from the `end` dates of all tasks
+  pub fn get_completion_counts(&self) -> HashMap<chrono::NaiveDate, usize> {
+    let mut counts = HashMap::new();
+    for task in self.all_tasks.iter().filter(|t| *t.status() == TaskStatus::Completed) {
+      if let Some(end) = task.end() {
+        *counts.entry(datetime::local_from_utc(end).date_naive()).or_default() += 1;
+      }
+    }
+    counts
+  }
+
+  /// How many tasks have a date from one of the configured sources on each day
+  pub fn get_date_counts(&self) -> HashMap<chrono::NaiveDate, usize> {
+    let mut counts = HashMap::new();
+    for task in self.calendar_tasks() {
+      let days: HashSet<chrono::NaiveDate> = self
+        .config
+        .uda_calendar_date_sources
         .iter()
-        .filter_map(|t| t.due().map(|d| (d.clone(), self.style_for_task(t))))
-        .map(|(d, t)| (datetime::local_from_utc(&d).date_naive(), t))
-        .collect()
-    } else {
-      vec![]
+        .filter_map(|source| source.date(task))
+        .map(|date| datetime::local_from_utc(date).date_naive())
+        .collect();
+      for day in days {
+        *counts.entry(day).or_default() += 1;
+      }
     }
+    counts
   }
 
   pub fn get_position(lb: &LineBuffer) -> usize {
     utils::display_width(&lb.as_str()[..lb.pos()])
   }
 
+  fn draw_pomodoro_break(&self, f: &mut Frame, area: Rect) {
+    f.render_widget(Clear, area);
+    let Some(pomodoro) = &self.pomodoro else {
+      return;
+    };
+    let remaining = pomodoro.remaining(Utc::now().naive_utc());
+    let next = match self.keyconfig.pomodoro {
+      KeyCode::Char(c) => c.to_string(),
+      _ => "Enter".to_string(),
+    };
+    let q = match self.keyconfig.quit {
+      KeyCode::Char(c) => c.to_string(),
+      _ => "Esc".to_string(),
+    };
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+      Line::from(Span::styled(format!("Pomodoro #{} is done", pomodoro.number), bold)),
+      Line::from(self.masked(&pomodoro.description)),
+      Line::from(""),
+    ];
+    lines.push(if remaining > chrono::Duration::zero() {
+      Line::from(vec![
+        Span::raw("Take a break: "),
+        Span::styled(timer::format_duration(remaining), self.config.uda_style_timer),
+      ])
+    } else {
+      Line::from(Span::styled("The break is over", bold))
+    });
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!("Press <{}> to start the next pomodoro or <{}> to stop.", next, q)));
+    let height = lines.len() as u16;
+    let rects = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Min(0), Constraint::Length(height), Constraint::Min(0)])
+      .split(area.inner(Margin { vertical: 1, horizontal: 1 }));
+    f.render_widget(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(Span::styled("Pomodoro", bold)),
+      area,
+    );
+    f.render_widget(
+      Paragraph::new(Text::from(lines)).alignment(Alignment::Center).wrap(Wrap { trim: true }),
+      rects[1],
+    );
+  }
+
   fn draw_help_popup(&mut self, f: &mut Frame, percent_x: u16, percent_y: u16) {
     let area = centered_rect(percent_x, percent_y, f.area());
     f.render_widget(Clear, area);
@@ -1224,6 +2193,7 @@ impl TaskwarriorTui {
       .widths(&constraints);
 
     f.render_stateful_widget(t, chunks[1], &mut self.contexts.table_state);
+    self.click_targets.context_menu = Some(chunks[1].inner(Margin { vertical: 1, horizontal: 1 }));
   }
 
   fn draw_report_menu(&mut self, f: &mut Frame, percent_x: u16, percent_y: u16) {
@@ -1312,128 +2282,521 @@ impl TaskwarriorTui {
       .widths(&constraints);
 
     f.render_stateful_widget(t, chunks[1], &mut self.reports.table_state);
+    self.click_targets.report_menu = Some(chunks[1].inner(Margin { vertical: 1, horizontal: 1 }));
   }
 
-  fn draw_completion_pop_up(&mut self, f: &mut Frame, rect: Rect, cursor_position: usize) {
-    if self.completion_list.candidates().is_empty() {
-      self.show_completion_pane = false;
+  fn draw_task_editor(&mut self, f: &mut Frame, percent_x: u16, percent_y: u16) {
+    let Some(editor) = &self.task_editor else {
       return;
-    }
-    // Iterate through all elements in the `items` app and append some debug text to it.
-    let items: Vec<ListItem> = self
-      .completion_list
-      .candidates()
+    };
+    let area = centered_rect(percent_x, percent_y, f.area());
+    f.render_widget(Clear, area);
+
+    let title = match editor.id {
+      Some(id) => format!("Edit Task {}", id),
+      None => format!("Edit Task {}", editor.uuid),
+    };
+    let block = Block::default()
+      .borders(Borders::ALL)
+      .border_type(BorderType::Rounded)
+      .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD)));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let label_width = editor.fields.iter().map(|f| f.label.width()).max().unwrap_or(0) as u16 + 3;
+    let height = inner.height as usize;
+    let offset = (editor.selected + 1).saturating_sub(height);
+    let label_style = self.config.color.get("color.label").copied().unwrap_or_default();
+
+    let lines: Vec<Line> = editor
+      .fields
       .iter()
-      .map(|p| {
-        let lines = vec![Line::from(vec![
-          Span::styled(p.3.clone(), Style::default().add_modifier(Modifier::BOLD)),
-          Span::from(p.4.clone()),
-        ])];
-        ListItem::new(lines)
+      .enumerate()
+      .skip(offset)
+      .take(height)
+      .map(|(i, field)| {
+        let marker = if field.is_changed() { "*" } else { " " };
+        let mut style = label_style;
+        if i == editor.selected {
+          style = style.add_modifier(Modifier::BOLD);
+        }
+        Line::from(vec![
+          Span::styled(
+            format!("{:<width$}", format!("{}{}", field.label, marker), width = label_width as usize),
+            style,
+          ),
+          Span::raw(utils::display_control_chars(field.buffer.as_str())),
+        ])
       })
       .collect();
+    f.render_widget(Paragraph::new(Text::from(lines)), inner);
 
-    // Create a List from all list items and highlight the currently selected one
-    let items = List::new(items)
-      .block(Block::default().borders(Borders::NONE).title(""))
-      .style(self.config.uda_style_report_completion_pane)
-      .highlight_style(self.config.uda_style_report_completion_pane_highlight)
-      .highlight_symbol(self.config.uda_selection_indicator.as_str());
-
-    let area = f.area();
+    let field = editor.current();
+    let row = inner.y + (editor.selected - offset) as u16;
+    let cursor_x = inner.x + label_width + utils::display_width(&field.buffer.as_str()[..field.buffer.pos()]) as u16;
+    let cursor_x = cursor_x.min(inner.right().saturating_sub(1));
+    f.set_cursor_position(Position::new(cursor_x, row));
 
-    let mut rect = rect;
-    rect.height = std::cmp::min(area.height / 2, self.completion_list.len() as u16 + 2);
-    rect.width = std::cmp::min(
-      area.width / 2,
-      self.completion_list.max_width().unwrap_or(40).try_into().unwrap_or(area.width / 2),
-    );
-    rect.y = rect.y.saturating_sub(rect.height);
-    if cursor_position as u16 + rect.width >= area.width {
-      rect.x = area.width - rect.width;
-    } else {
-      rect.x = cursor_position as u16;
+    if self.show_completion_pane {
+      self.draw_completion_pop_up(f, Rect::new(inner.x, row, inner.width, 1), cursor_x as usize);
     }
-
-    // We can now render the item list
-    f.render_widget(Clear, rect);
-    f.render_stateful_widget(items, rect, &mut self.completion_list.state);
   }
 
-  fn draw_command(
-    &self,
-    f: &mut Frame,
-    rect: Rect,
-    text: &str,
-    title: (Span, Option<Span>),
-    position: usize,
-    cursor: bool,
-    error: Option<String>,
-    ghost_text: Option<&str>,
-  ) {
-    let rendered_text = utils::display_control_chars(text);
-    let rendered_ghost_text = ghost_text.map(utils::display_control_chars);
+  fn draw_command_palette(&mut self, f: &mut Frame, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.area());
+    f.render_widget(Clear, area.inner(Margin { vertical: 0, horizontal: 0 }));
 
-    // f.render_widget(Clear, rect);
-    if cursor {
-      let position = Position::new(std::cmp::min(rect.x + position as u16, rect.x + rect.width.saturating_sub(2)), rect.y + 1);
-      f.set_cursor_position(position);
-    }
-    let rects = Layout::default()
+    // Split the popup: 1 row for search bar, remainder for the table.
+    let chunks = Layout::default()
       .direction(Direction::Vertical)
-      .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
-      .split(rect);
-
-    // render command title
-    let mut style = self.config.uda_style_command;
-    if error.is_some() {
-      style = self.config.uda_style_command_error;
-    };
-    let title_spans = if let Some(subtitle) = title.1 {
-      Line::from(vec![title.0, Span::from(" ["), subtitle, Span::from("]")])
-    } else {
-      Line::from(vec![title.0])
-    };
-    let title = Paragraph::new(Text::from(title_spans)).style(style);
-    f.render_widget(title, rects[0]);
-
-    // render command with optional ghost text suffix
-    let scroll = (0, ((position + 2) as u16).saturating_sub(rects[1].width));
-    let p = if let Some(ghost) = rendered_ghost_text {
-      let line = Line::from(vec![Span::raw(rendered_text), Span::styled(ghost, Style::default().fg(Color::DarkGray))]);
-      Paragraph::new(Text::from(line)).scroll(scroll)
-    } else {
-      Paragraph::new(Text::from(rendered_text)).scroll(scroll)
-    };
-    f.render_widget(p, rects[1]);
-  }
-
-  fn draw_task_details(&mut self, f: &mut Frame, rect: Rect) {
-    if self.tasks.is_empty() {
-      let p = Paragraph::new(Text::from("Task not found")).block(Block::default().borders(Borders::TOP));
-      f.render_widget(p, rect);
-      return;
-    }
-    let selected = self.current_selection;
-    let task_id = self.tasks[selected].id().unwrap_or_default();
-    let task_uuid = *self.tasks[selected].uuid();
+      .constraints([Constraint::Length(3), Constraint::Min(0)])
+      .split(area);
 
-    let data = match self.task_details.get(&task_uuid) {
-      Some(s) => s.clone(),
-      None => "Loading task details ...".to_string(),
-    };
-    self.task_details_scroll = std::cmp::min(
-      (data.lines().count() as u16).saturating_sub(rect.height).saturating_add(2),
-      self.task_details_scroll,
+    // --- Search bar ---
+    let search_text = format!(" {}", self.command_palette.search);
+    let cursor_x = chunks[0].x + 2 + self.command_palette.search.len() as u16;
+    let cursor_y = chunks[0].y + 1;
+    f.render_widget(
+      Paragraph::new(search_text).block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(BorderType::Rounded)
+          .title(Span::styled(
+            "Command Palette  (type to search)",
+            Style::default().add_modifier(Modifier::BOLD),
+          )),
+      ),
+      chunks[0],
     );
-    let p = Paragraph::new(Text::from(&data[..]))
-      .block(Block::default().borders(Borders::TOP))
-      .scroll((self.task_details_scroll, 0));
-    f.render_widget(p, rect);
-  }
+    f.set_cursor_position(Position {
+      x: cursor_x.min(chunks[0].x + chunks[0].width.saturating_sub(2)),
+      y: cursor_y,
+    });
 
-  fn task_details_scroll_up(&mut self) {
-    self.task_details_scroll = self.task_details_scroll.saturating_sub(1);
+    // --- Filtered table ---
+    let headers = ["Action", "Key"];
+    let filtered_rows: Vec<Vec<String>> = self
+      .command_palette
+      .filtered_indices()
+      .iter()
+      .map(|&ri| {
+        let r = &self.command_palette.rows[ri];
+        vec![r.title.clone(), r.key.clone()]
+      })
+      .collect();
+
+    let maximum_column_width = chunks[1].width;
+    let widths = self.calculate_widths(
+      &filtered_rows,
+      &headers.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+      maximum_column_width,
+    );
+    let constraints: Vec<Constraint> = widths
+      .iter()
+      .map(|w| Constraint::Length((*w).try_into().unwrap_or(maximum_column_width)))
+      .collect();
+
+    let rows: Vec<Row<std::slice::Iter<String>>> = filtered_rows.iter().map(|row| Row::Data(row.iter())).collect();
+    let t = Table::new(headers.iter(), rows.into_iter())
+      .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded))
+      .header_style(
+        self
+          .config
+          .color
+          .get("color.label")
+          .copied()
+          .unwrap_or_default()
+          .add_modifier(Modifier::UNDERLINED),
+      )
+      .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator)
+      .widths(&constraints);
+
+    f.render_stateful_widget(t, chunks[1], &mut self.command_palette.table_state);
+  }
+
+  fn draw_project_picker(&mut self, f: &mut Frame, action: Action, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.area());
+    f.render_widget(Clear, area);
+    let style_header = self
+      .config
+      .color
+      .get("color.label")
+      .copied()
+      .unwrap_or_default()
+      .add_modifier(Modifier::UNDERLINED);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+
+    if action == Action::ProjectPreview {
+      let count = self.project_picker.preview.len();
+      let title = format!(
+        "{} {} will change. Press <Enter> to apply or <Esc> to go back.",
+        count,
+        if count == 1 { "task" } else { "tasks" }
+      );
+      let headers: Vec<String> = ["ID", "Description", "Project", "New project"].map(String::from).to_vec();
+      let data: Vec<Vec<String>> = self
+        .project_picker
+        .preview
+        .iter()
+        .map(|c| {
+          vec![
+            c.id.map_or_else(|| c.uuid.to_string()[..8].to_string(), |id| id.to_string()),
+            self.masked(&c.description),
+            self.masked(&c.from),
+            self.masked(&c.to),
+          ]
+        })
+        .collect();
+      let inner = area.inner(Margin { vertical: 1, horizontal: 1 });
+      let widths = self.calculate_widths(&data, &headers, inner.width);
+      let constraints: Vec<Constraint> = widths
+        .iter()
+        .map(|w| Constraint::Length((*w).try_into().unwrap_or(inner.width)))
+        .collect();
+      let t = Table::new(headers.iter(), data.iter().map(|row| Row::Data(row.iter())))
+        .block(
+          Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(Span::styled(title, bold)),
+        )
+        .header_style(style_header)
+        .widths(&constraints);
+      let mut state = TaskwarriorTuiTableState::default();
+      state.select(None);
+      f.render_stateful_widget(t, area, &mut state);
+      return;
+    }
+
+    let chunks = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(3), Constraint::Min(0)])
+      .split(area);
+    let title = match &self.project_picker.target {
+      ProjectPickerTarget::Rename(from) => format!("Rename {} to", self.masked(from)),
+      ProjectPickerTarget::Merge(from) => format!("Merge {} into", self.masked(from)),
+      ProjectPickerTarget::Move(uuids) if uuids.len() == 1 => "Move task to project".to_string(),
+      ProjectPickerTarget::Move(uuids) => format!("Move {} tasks to project", uuids.len()),
+    };
+    let search = &self.project_picker.search;
+    f.render_widget(
+      Paragraph::new(format!(" {}", search)).block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(BorderType::Rounded)
+          .title(Span::styled(title, bold)),
+      ),
+      chunks[0],
+    );
+    f.set_cursor_position(Position {
+      x: (chunks[0].x + 2 + search.width() as u16).min(chunks[0].x + chunks[0].width.saturating_sub(2)),
+      y: chunks[0].y + 1,
+    });
+
+    let headers = ["Project", ""];
+    let choices: Vec<Vec<String>> = self
+      .project_picker
+      .choices()
+      .into_iter()
+      .map(|(name, new)| vec![self.masked(&name), if new { "new".to_string() } else { String::new() }])
+      .collect();
+    let maximum_column_width = chunks[1].width;
+    let widths = self.calculate_widths(&choices, &headers.map(String::from), maximum_column_width);
+    let constraints: Vec<Constraint> = widths
+      .iter()
+      .map(|w| Constraint::Length((*w).try_into().unwrap_or(maximum_column_width)))
+      .collect();
+    let t = Table::new(headers.iter(), choices.iter().map(|row| Row::Data(row.iter())))
+      .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded))
+      .header_style(style_header)
+      .highlight_style(bold)
+      .highlight_symbol(&self.config.uda_selection_indicator)
+      .widths(&constraints);
+    f.render_stateful_widget(t, chunks[1], &mut self.project_picker.table_state);
+  }
+
+  fn draw_column_editor(&mut self, f: &mut Frame, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.area());
+    f.render_widget(Clear, area);
+    let style_header = self
+      .config
+      .color
+      .get("color.label")
+      .copied()
+      .unwrap_or_default()
+      .add_modifier(Modifier::UNDERLINED);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let editor = &self.column_editor;
+    let renaming = editor.mode == ColumnEditorMode::Rename;
+    let chunks = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Min(0), Constraint::Length(if renaming { 3 } else { 0 })])
+      .split(area);
+
+    let (title, headers, data) = if editor.mode == ColumnEditorMode::Add {
+      let data: Vec<Vec<String>> = editor.available.iter().map(|c| vec![c.name.clone(), c.formats.join(", ")]).collect();
+      (
+        "Add column: <Enter> to add, <Esc> to go back".to_string(),
+        ["Column", "Formats"].to_vec(),
+        data,
+      )
+    } else {
+      let data: Vec<Vec<String>> = editor
+        .columns
+        .iter()
+        .zip(&editor.labels)
+        .map(|(column, label)| {
+          let formats = editor
+            .formats(column)
+            .map(|(formats, current)| {
+              formats
+                .iter()
+                .enumerate()
+                .map(|(i, format)| if i == current { format!("[{}]", format) } else { format.clone() })
+                .collect::<Vec<_>>()
+                .join(" ")
+            })
+            .unwrap_or_default();
+          vec![column.clone(), label.clone(), formats]
+        })
+        .collect();
+      let key = |command| sequence_label(&self.keyconfig.sequence(KeyScope::ColumnEditor, command));
+      (
+        format!(
+          "Columns: {} add, {} remove, {}/{} move, {}/{} format, {} relabel, <Enter> keep, <{}> save, <Esc> cancel",
+          key(Command::AddColumn),
+          key(Command::RemoveColumn),
+          key(Command::MoveColumnDown),
+          key(Command::MoveColumnUp),
+          key(Command::PreviousFormat),
+          key(Command::NextFormat),
+          key(Command::RelabelColumn),
+          key(Command::SaveColumns),
+        ),
+        ["Column", "Label", "Formats"].to_vec(),
+        data,
+      )
+    };
+    let headers: Vec<String> = headers.into_iter().map(String::from).collect();
+    let inner = chunks[0].inner(Margin { vertical: 1, horizontal: 1 });
+    let widths = self.calculate_widths(&data, &headers, inner.width);
+    let constraints: Vec<Constraint> = widths
+      .iter()
+      .map(|w| Constraint::Length((*w).try_into().unwrap_or(inner.width)))
+      .collect();
+    let t = Table::new(headers.iter(), data.iter().map(|row| Row::Data(row.iter())))
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(BorderType::Rounded)
+          .title(Span::styled(title, bold)),
+      )
+      .header_style(style_header)
+      .highlight_style(bold)
+      .highlight_symbol(&self.config.uda_selection_indicator)
+      .widths(&constraints);
+    let state = if self.column_editor.mode == ColumnEditorMode::Add {
+      &mut self.column_editor.available_state
+    } else {
+      &mut self.column_editor.table_state
+    };
+    f.render_stateful_widget(t, chunks[0], state);
+
+    if renaming {
+      let label = &self.column_editor.label;
+      f.render_widget(
+        Paragraph::new(format!(" {}", label)).block(
+          Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(Span::styled("Label", bold)),
+        ),
+        chunks[1],
+      );
+      f.set_cursor_position(Position {
+        x: (chunks[1].x + 2 + label.width() as u16).min(chunks[1].x + chunks[1].width.saturating_sub(2)),
+        y: chunks[1].y + 1,
+      });
+    }
+  }
+
+  fn draw_completion_pop_up(&mut self, f: &mut Frame, rect: Rect, cursor_position: usize) {
+    if self.completion_list.candidates().is_empty() {
+      self.show_completion_pane = false;
+      return;
+    }
+    // Iterate through all elements in the `items` app and append some debug text to it.
+    let items: Vec<ListItem> = self
+      .completion_list
+      .candidates()
+      .iter()
+      .map(|p| {
+        let lines = vec![Line::from(vec![
+          Span::styled(p.3.clone(), Style::default().add_modifier(Modifier::BOLD)),
+          Span::from(p.4.clone()),
+        ])];
+        ListItem::new(lines)
+      })
+      .collect();
+
+    // Create a List from all list items and highlight the currently selected one
+    let items = List::new(items)
+      .block(Block::default().borders(Borders::NONE).title(""))
+      .style(self.config.uda_style_report_completion_pane)
+      .highlight_style(self.config.uda_style_report_completion_pane_highlight)
+      .highlight_symbol(self.config.uda_selection_indicator.as_str());
+
+    let area = f.area();
+
+    let mut rect = rect;
+    rect.height = std::cmp::min(area.height / 2, self.completion_list.len() as u16 + 2);
+    rect.width = std::cmp::min(
+      area.width / 2,
+      self.completion_list.max_width().unwrap_or(40).try_into().unwrap_or(area.width / 2),
+    );
+    rect.y = rect.y.saturating_sub(rect.height);
+    if cursor_position as u16 + rect.width >= area.width {
+      rect.x = area.width - rect.width;
+    } else {
+      rect.x = cursor_position as u16;
+    }
+
+    // We can now render the item list
+    f.render_widget(Clear, rect);
+    f.render_stateful_widget(items, rect, &mut self.completion_list.state);
+  }
+
+  fn draw_command(
+    &self,
+    f: &mut Frame,
+    rect: Rect,
+    text: &str,
+    title: (Span, Option<Span>),
+    position: usize,
+    cursor: bool,
+    error: Option<String>,
+    ghost_text: Option<&str>,
+  ) {
+    let rendered_text = utils::display_control_chars(text);
+    let rendered_ghost_text = ghost_text.map(utils::display_control_chars);
+
+    // f.render_widget(Clear, rect);
+    if cursor {
+      let position = Position::new(std::cmp::min(rect.x + position as u16, rect.x + rect.width.saturating_sub(2)), rect.y + 1);
+      f.set_cursor_position(position);
+    }
+    let rects = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+      .split(rect);
+
+    // render command title
+    let mut style = self.config.uda_style_command;
+    if error.is_some() {
+      style = self.config.uda_style_command_error;
+    };
+    let title_spans = if let Some(subtitle) = title.1 {
+      Line::from(vec![title.0, Span::from(" ["), subtitle, Span::from("]")])
+    } else {
+      Line::from(vec![title.0])
+    };
+    let title = Paragraph::new(Text::from(title_spans)).style(style);
+    f.render_widget(title, rects[0]);
+
+    // render command with optional ghost text suffix
+    let scroll = (0, ((position + 2) as u16).saturating_sub(rects[1].width));
+    let p = if let Some(ghost) = rendered_ghost_text {
+      let line = Line::from(vec![Span::raw(rendered_text), Span::styled(ghost, Style::default().fg(Color::DarkGray))]);
+      Paragraph::new(Text::from(line)).scroll(scroll)
+    } else {
+      Paragraph::new(Text::from(rendered_text)).scroll(scroll)
+    };
+    f.render_widget(p, rects[1]);
+  }
+
+  fn draw_task_details(&mut self, f: &mut Frame, rect: Rect) {
+    if self.tasks.is_empty() {
+      let p = Paragraph::new(Text::from("Task not found")).block(Block::default().borders(Borders::TOP));
+      f.render_widget(p, rect);
+      return;
+    }
+    let selected = self.current_selection;
+    if let Some(group) = self.task_groups.group_of(selected).filter(|g| self.task_groups.is_collapsed(g)) {
+      let text = format!(
+        "{}: {} {} folded",
+        self.group_label(&group.label),
+        group.len,
+        if group.len == 1 { "task" } else { "tasks" }
+      );
+      let p = Paragraph::new(text)
+        .block(Block::default().borders(Borders::TOP))
+        .style(Style::default().add_modifier(Modifier::DIM));
+      f.render_widget(p, rect);
+      return;
+    }
+    let task_id = self.tasks[selected].id().unwrap_or_default();
+    let task_uuid = *self.tasks[selected].uuid();
+
+    let data = match self.task_details.get(&task_uuid) {
+      Some(s) if self.obfuscate => self.masked_details(&self.tasks[selected], s),
+      Some(s) => s.clone(),
+      None => "Loading task details ...".to_string(),
+    };
+    self.task_details_scroll = std::cmp::min(
+      (data.lines().count() as u16).saturating_sub(rect.height).saturating_add(2),
+      self.task_details_scroll,
+    );
+    let tracked = timer::tracked(
+      &self.tasks[selected],
+      Utc::now().naive_utc(),
+      &self.config.uda_timer_uda,
+      &self.config.journal_time_start_annotation,
+      &self.config.journal_time_stop_annotation,
+    );
+    let mut block = Block::default().borders(Borders::TOP);
+    if tracked.num_seconds() > 0 {
+      block = block.title(format!("Tracked {}", timer::format_duration(tracked)));
+    }
+    let p = Paragraph::new(Text::from(&data[..])).block(block).scroll((self.task_details_scroll, 0));
+    f.render_widget(p, rect);
+  }
+
+  /// The words of the task's description, annotations, project and tags masked in its `task info` output.
+  /// Whole words are matched because the output wraps long text.
+  fn masked_details(&self, task: &Task, details: &str) -> String {
+    let mut texts = vec![task.description().as_str()];
+    texts.extend(task.annotations().into_iter().flatten().map(|a| a.description().as_str()));
+    texts.extend(task.project().map(String::as_str));
+    texts.extend(task.tags().into_iter().flatten().map(String::as_str));
+    let words = texts
+      .into_iter()
+      .flat_map(|text| text.split_word_bounds())
+      .filter(|word| word.chars().any(char::is_alphabetic))
+      .collect();
+    utils::obfuscate_words(details, &words)
+  }
+
+  /// `text` as it is shown, which is masked while obfuscation is on
+  fn masked(&self, text: &str) -> String {
+    if self.obfuscate { utils::obfuscate(text) } else { text.to_string() }
+  }
+
+  /// The filter as it is shown outside the prompt, with project names and tags masked while obfuscation is on
+  fn masked_filter(&self) -> String {
+    if self.obfuscate {
+      utils::obfuscate_filter(self.filter.as_str())
+    } else {
+      self.filter.as_str().to_string()
+    }
+  }
+
+  pub fn toggle_obfuscate(&mut self) {
+    self.obfuscate = !self.obfuscate;
+  }
+
+  fn task_details_scroll_up(&mut self) {
+    self.task_details_scroll = self.task_details_scroll.saturating_sub(1);
   }
 
   fn task_details_scroll_down(&mut self) {
@@ -1534,21 +2897,31 @@ impl TaskwarriorTui {
   }
 
   pub fn calculate_widths(&self, tasks: &[Vec<String>], headers: &[String], maximum_column_width: u16) -> Vec<usize> {
+    let description = headers.iter().position(|h| h == "Description" || h == "Definition");
+    self.calculate_widths_with_description(tasks, headers, description, maximum_column_width)
+  }
+
+  /// Like `calculate_widths`, with the index of the description column given rather than found by its header
+  pub fn calculate_widths_with_description(
+    &self,
+    tasks: &[Vec<String>],
+    headers: &[String],
+    description: Option<usize>,
+    maximum_column_width: u16,
+  ) -> Vec<usize> {
     // naive implementation of calculate widths
     let mut widths = headers.iter().map(String::len).collect::<Vec<usize>>();
 
     for row in tasks.iter() {
       for (i, cell) in row.iter().enumerate() {
-        widths[i] = std::cmp::max(cell.len(), widths[i]);
+        let width = cell.split('\n').map(str::len).max().unwrap_or_default();
+        widths[i] = std::cmp::max(width, widths[i]);
       }
     }
 
-    for (i, header) in headers.iter().enumerate() {
-      if header == "Description" || header == "Definition" {
-        // always give description or definition the most room to breath
-        widths[i] = maximum_column_width as usize;
-        break;
-      }
+    if let Some(i) = description {
+      // always give description or definition the most room to breath
+      widths[i] = maximum_column_width as usize;
     }
     for (i, header) in headers.iter().enumerate() {
       if i == 0 {
@@ -1573,31 +2946,59 @@ impl TaskwarriorTui {
   }
 
   fn draw_task_report(&mut self, f: &mut Frame, rect: Rect) {
-    let (tasks, headers) = self.get_task_report();
+    let (mut tasks, mut headers) = self.get_task_report();
 
     if tasks.is_empty() {
       f.render_widget(Block::default(), rect);
       return;
     }
 
+    let description = headers.iter().position(|h| h == "Description" || h == "Definition");
+    for (header, column) in headers.iter_mut().zip(&self.task_report_table.shown_columns) {
+      if let Some(indicator) = self.task_report_table.sort_indicator(*column) {
+        header.push(' ');
+        header.push_str(indicator);
+      }
+    }
+    let focused = self
+      .task_report_table
+      .focused_column
+      .and_then(|c| self.task_report_table.shown_columns.iter().position(|s| *s == c));
+
     let maximum_column_width = rect.width;
-    let widths = self.calculate_widths(&tasks, &headers, maximum_column_width);
+    let widths = self.calculate_widths_with_description(&tasks, &headers, description, maximum_column_width);
 
-    for (i, header) in headers.iter().enumerate() {
-      if header == "Description" || header == "Definition" {
-        self.task_report_table.description_width = widths[i] - 1;
-        break;
+    if let Some(i) = description {
+      self.task_report_table.description_width = widths[i] - 1;
+      if self.config.uda_task_report_wrap_description {
+        for row in &mut tasks {
+          row[i] = utils::wrap(&row[i], widths[i] - 1).join("\n");
+        }
       }
     }
-    let selected = self.current_selection;
+    self.task_row_heights = task_report::row_heights(&tasks);
+    let selected = self.task_groups.row_of(self.current_selection);
     let header = headers.iter();
     let mut rows = vec![];
     let mut highlight_style = Style::default();
-    let mut pos = 0;
-    for (i, task) in tasks.iter().enumerate() {
-      let style = self.task_report_row_style(i, &self.tasks[i]);
-      if i == selected {
-        pos = i;
+    let report_rows = self.task_groups.rows(tasks.len());
+    let heights = self.task_report_display_heights(&report_rows);
+    for (row, report_row) in report_rows.iter().enumerate() {
+      let style = match *report_row {
+        ReportRow::Group(g) => {
+          let group = &self.task_groups.groups[g];
+          let fold = if self.task_groups.is_collapsed(group) { "▸" } else { "▾" };
+          let text = format!("{} {} ({})", fold, self.group_label(&group.label), group.len);
+          rows.push(Row::Header(text, self.config.uda_style_report_group));
+          self.config.uda_style_report_group
+        }
+        ReportRow::Task(i) => {
+          let style = self.task_report_row_style(i, &self.tasks[i]);
+          rows.push(Row::StyledData(tasks[i].iter(), style));
+          style
+        }
+      };
+      if row == selected {
         highlight_style = style.patch(self.config.uda_style_report_selection);
         if self.config.uda_selection_bold {
           highlight_style = highlight_style.add_modifier(Modifier::BOLD);
@@ -1615,7 +3016,6 @@ impl TaskwarriorTui {
           highlight_style = highlight_style.add_modifier(Modifier::REVERSED);
         }
       }
-      rows.push(Row::StyledData(task.iter(), style));
     }
 
     let constraints: Vec<Constraint> = widths
@@ -1639,11 +3039,18 @@ impl TaskwarriorTui {
       .unmark_highlight_symbol(&self.config.uda_unmark_highlight_indicator)
       .mark_symbol(&self.config.uda_mark_indicator)
       .unmark_symbol(&self.config.uda_unmark_indicator)
-      .widths(&constraints);
+      .widths(&constraints)
+      .heights(&heights);
+    let t = match focused {
+      Some(i) => t.focused_header(i, Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)),
+      None => t,
+    };
 
     f.render_stateful_widget(t, rect, &mut self.task_table_state);
-    if tasks.iter().len() as u16 > rect.height.saturating_sub(4) {
-      let mut widget = Scrollbar::new(pos, tasks.iter().len());
+    self.click_targets.task_report = Some(rect);
+    self.click_targets.task_report_columns = widths;
+    if heights.iter().sum::<usize>() > rect.height.saturating_sub(4) as usize {
+      let mut widget = Scrollbar::new(selected, report_rows.len());
       widget.pos_style = self.config.uda_style_report_scrollbar;
       widget.pos_symbol.clone_from(&self.config.uda_scrollbar_indicator);
       widget.area_style = self.config.uda_style_report_scrollbar_area;
@@ -1653,6 +3060,7 @@ impl TaskwarriorTui {
   }
 
   fn get_task_report(&mut self) -> (Vec<Vec<String>>, Vec<String>) {
+    self.task_report_table.obfuscate = self.obfuscate;
     self.task_report_table.generate_table(&self.tasks);
     let (tasks, headers) = self.task_report_table.simplify_table();
     (tasks, headers)
@@ -1660,8 +3068,10 @@ impl TaskwarriorTui {
 
   pub async fn update(&mut self, force: bool) -> Result<()> {
     trace!("self.update({:?});", force);
-    if force || self.dirty || self.tasks_changed_since(self.last_export).unwrap_or(true) {
-      self.get_context()?;
+    // Without a watcher on the data directory, fall back to polling the database on every tick
+    let poll = self.event_loop.watcher.is_none();
+    if force || self.dirty || (poll && self.tasks_changed_since(self.last_export).unwrap_or(true)) {
+      self.refresh_config()?;
       let task_uuids = self.selected_task_uuids();
       if self.current_selection_uuid.is_none()
         && self.current_selection_id.is_none()
@@ -1670,15 +3080,19 @@ impl TaskwarriorTui {
         self.current_selection_uuid = Some(*uuid);
       }
 
-      self.task_report_table.export_headers(None, &self.report, &self.task_exe)?;
-      self.export_tasks()?;
-      if self.config.uda_task_report_use_all_tasks_for_completion {
-        self.export_all_tasks()?;
+      self
+        .task_report_table
+        .export_headers(Some(&self.task_show), &self.report, &self.task_exe)?;
+      if !self.export_tasks_incremental()? {
+        self.export_tasks()?;
+        if self.needs_all_tasks() {
+          self.export_all_tasks()?;
+        }
       }
-      self.contexts.update_data(&self.task_exe)?;
-      self.reports.update_data(&self.report, &Self::task_show_output(&self.task_exe)?);
-      self.projects.update_data(&self.task_exe)?;
-      self.update_timesheet()?;
+      self.export_active_tasks()?;
+      self.group_tasks();
+      self.stale_panes.extend(LazyPane::ALL);
+      self.refresh_visible_panes()?;
       self.update_tags();
       self.task_details.clear();
       self.task_details_modified.clear();
@@ -1699,6 +3113,61 @@ impl TaskwarriorTui {
     Ok(())
   }
 
+  /// Re-reads `task show` and the current context if the taskrc or a file it includes changed since they were last read
+  fn refresh_config(&mut self) -> Result<()> {
+    let mtimes: Vec<SystemTime> = taskrc_path()
+      .map(taskrc_files)
+      .unwrap_or_default()
+      .iter()
+      .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+      .collect();
+    if !mtimes.is_empty() && mtimes == self.taskrc_mtimes {
+      return Ok(());
+    }
+    self.task_show = Self::task_show_output(&self.task_exe)?;
+    self.get_context()?;
+    self.taskrc_mtimes = mtimes;
+    Ok(())
+  }
+
+  /// Brings a pane up to date if the task data changed since it was last refreshed
+  pub fn refresh_pane(&mut self, pane: LazyPane) -> Result<()> {
+    if !self.stale_panes.remove(&pane) {
+      return Ok(());
+    }
+    match pane {
+      LazyPane::Contexts => self.contexts.update_data(&self.task_exe)?,
+      LazyPane::Reports => self.reports.update_data(&self.report, &self.task_show),
+      LazyPane::Projects => {
+        // Completed tasks count towards the progress of a project, so they are read from `task export all`
+        self.export_all_tasks()?;
+        self.projects.update_data(&self.all_tasks, Utc::now().naive_utc());
+      }
+      LazyPane::Timesheet => self.update_timesheet()?,
+      LazyPane::Dependencies => {
+        let current = self.task_current().map(|t| *t.uuid());
+        self.dependencies.update_data(&self.tasks, &self.all_tasks, current);
+      }
+      LazyPane::Board => {
+        let current = self.task_current().map(|t| *t.uuid());
+        self
+          .board
+          .update_data(&self.tasks, &self.config.uda_board_group_by, &self.config.uda_board_columns, current);
+      }
+    }
+    Ok(())
+  }
+
+  pub fn refresh_visible_panes(&mut self) -> Result<()> {
+    match self.mode {
+      Mode::Projects => self.refresh_pane(LazyPane::Projects),
+      Mode::Timesheet => self.refresh_pane(LazyPane::Timesheet),
+      Mode::Dependencies => self.refresh_pane(LazyPane::Dependencies),
+      Mode::Board => self.refresh_pane(LazyPane::Board),
+      _ => Ok(()),
+    }
+  }
+
   pub fn selection_fix(&mut self) {
     if let (Some(t), Some(id)) = (self.task_current(), self.current_selection_id)
       && t.id() != Some(id)
@@ -1724,9 +3193,104 @@ impl TaskwarriorTui {
   }
 
   pub fn cursor_fix(&mut self) {
-    while !self.tasks.is_empty() && self.current_selection >= self.tasks.len() {
-      self.task_report_previous();
+    if let Some(last) = self.tasks.len().checked_sub(1)
+      && self.current_selection > last
+    {
+      self.current_selection = last;
+      self.current_selection_id = None;
+      self.current_selection_uuid = None;
+    }
+    // A task in a folded group is reached through the group's header
+    let (selectable, pos) = self.task_report_cursor();
+    if let Some(i) = selectable.get(pos) {
+      self.current_selection = *i;
+    }
+  }
+
+  /// The tasks the cursor can be on, and the position of the cursor among them
+  fn task_report_cursor(&self) -> (Vec<usize>, usize) {
+    let selectable = self.task_groups.selectable(self.tasks.len());
+    let pos = selectable.iter().rposition(|i| *i <= self.current_selection).unwrap_or_default();
+    (selectable, pos)
+  }
+
+  /// Orders the report by group, if it is grouped
+  fn group_tasks(&mut self) {
+    self.task_groups.update(
+      &mut self.tasks,
+      Utc::now().naive_utc(),
+      self.config.weekstart,
+      &self.config.uda_priority_values,
+    );
+  }
+
+  /// The label of a group as it is shown, which is masked while obfuscation is on unless it is a due date or priority
+  fn group_label(&self, label: &str) -> String {
+    match self.task_groups.by {
+      Some(GroupBy::Due | GroupBy::Priority) => label.to_string(),
+      _ => self.masked(label),
+    }
+  }
+
+  /// Moves the cursor to task `i`, unfolding its group if it is folded
+  pub fn task_report_select(&mut self, i: usize) {
+    if self.task_groups.is_hidden(i) {
+      self.task_groups.toggle(i);
+    }
+    self.current_selection = i;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  /// Folds or unfolds the group of the selected task
+  pub fn task_report_fold(&mut self) {
+    if let Some(i) = self.task_groups.toggle(self.current_selection) {
+      self.current_selection = i;
+      self.current_selection_id = None;
+      self.current_selection_uuid = None;
+    }
+  }
+
+  /// Cycles the sort of the focused column and re-orders the report without exporting it again
+  pub fn task_report_sort(&mut self) {
+    let udas: Vec<String> = self.config.uda.iter().map(|u| u.name.clone()).collect();
+    if let Err(e) = self.task_report_table.cycle_sort(&udas) {
+      self.error = Some(e);
+      self.mode = Mode::Tasks(Action::Error);
+      return;
+    }
+    let selected = self.tasks.get(self.current_selection).map(|t| *t.uuid());
+    sort::sort_tasks(&mut self.tasks, self.task_report_table.sort_keys(), &self.config.uda_priority_values);
+    self.group_tasks();
+    if let Some(i) = selected.and_then(|uuid| self.tasks.iter().position(|t| *t.uuid() == uuid)) {
+      self.current_selection = i;
     }
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  /// `task config report.<name>.sort` with the order picked from inside the report
+  pub fn task_report_save_sort(&mut self) -> Result<Option<Job>, String> {
+    let Some(keys) = &self.task_report_table.custom_sort else {
+      return Err(format!("The sort order of report {} has not been changed", self.report));
+    };
+    let spec = sort::format_sort(keys);
+    let mut cmd = std::process::Command::new(&self.task_exe);
+    cmd
+      .arg("rc.confirmation=off")
+      .arg("config")
+      .arg(format!("report.{}.sort", self.report))
+      .arg(&spec);
+    let job = Job::new(
+      "save sort",
+      format!(
+        "Cannot run `task config report.{}.sort {}`. Check documentation for more information",
+        self.report, spec
+      ),
+    )
+    .command(cmd)
+    .fails_with("Saving the sort order failed.");
+    Ok(Some(job))
   }
 
   pub async fn update_task_details(&mut self) -> Result<()> {
@@ -1809,7 +3373,7 @@ impl TaskwarriorTui {
 
   pub fn update_task_table_state(&mut self) {
     trace!("self.update_task_table_state()");
-    self.task_table_state.select(Some(self.current_selection));
+    self.task_table_state.select(Some(self.task_groups.row_of(self.current_selection)));
 
     for uuid in self.marked.clone() {
       if self.task_by_uuid(uuid).is_none() {
@@ -1824,7 +3388,11 @@ impl TaskwarriorTui {
     self.task_table_state.clear();
 
     for uuid in &self.marked {
-      self.task_table_state.mark(self.task_index_by_uuid(*uuid));
+      let row = self
+        .task_index_by_uuid(*uuid)
+        .filter(|i| !self.task_groups.is_hidden(*i))
+        .map(|i| self.task_groups.row_of(i));
+      self.task_table_state.mark(row);
     }
   }
 
@@ -1973,6 +3541,9 @@ impl TaskwarriorTui {
       self.filter.insert(c, 1, &mut self.changes);
     }
 
+    self.task_report_table.custom_sort = None;
+    self.task_report_table.custom_columns = None;
+    self.task_report_table.focused_column = None;
     self.task_report_table.export_headers(Some(data), &self.report, &self.task_exe)?;
     Ok(true)
   }
@@ -2037,7 +3608,8 @@ impl TaskwarriorTui {
     if self.tasks.is_empty() {
       return;
     }
-    self.current_selection = self.tasks.len() - 1;
+    let (selectable, _) = self.task_report_cursor();
+    self.current_selection = selectable[selectable.len() - 1];
     self.current_selection_id = None;
     self.current_selection_uuid = None;
   }
@@ -2046,18 +3618,15 @@ impl TaskwarriorTui {
     if self.tasks.is_empty() {
       return;
     }
+    let (selectable, pos) = self.task_report_cursor();
     let i = {
-      if self.current_selection >= self.tasks.len() - 1 {
-        if self.config.uda_task_report_looping {
-          0
-        } else {
-          self.current_selection
-        }
+      if pos >= selectable.len() - 1 {
+        if self.config.uda_task_report_looping { 0 } else { pos }
       } else {
-        self.current_selection + 1
+        pos + 1
       }
     };
-    self.current_selection = i;
+    self.current_selection = selectable[i];
     self.current_selection_id = None;
     self.current_selection_uuid = None;
   }
@@ -2066,36 +3635,67 @@ impl TaskwarriorTui {
     if self.tasks.is_empty() {
       return;
     }
+    let (selectable, pos) = self.task_report_cursor();
     let i = {
-      if self.current_selection == 0 {
-        if self.config.uda_task_report_looping { self.tasks.len() - 1 } else { 0 }
+      if pos == 0 {
+        if self.config.uda_task_report_looping { selectable.len() - 1 } else { 0 }
       } else {
-        self.current_selection - 1
+        pos - 1
       }
     };
-    self.current_selection = i;
+    self.current_selection = selectable[i];
     self.current_selection_id = None;
     self.current_selection_uuid = None;
   }
 
+  /// The position among `selectable` a page away from `pos`, counting the lines each task takes up
+  fn task_report_page(&self, selectable: &[usize], pos: usize, forward: bool) -> usize {
+    let height = |p: usize| {
+      let i = selectable[p];
+      if self.task_groups.is_hidden(i) {
+        1
+      } else {
+        self.task_row_heights.get(i).copied().unwrap_or(1)
+      }
+    };
+    let mut lines = 0;
+    let mut p = pos;
+    while lines < self.task_report_height as usize {
+      let next = if forward { p + 1 } else { p.wrapping_sub(1) };
+      if next >= selectable.len() {
+        break;
+      }
+      lines += height(next);
+      p = next;
+    }
+    p
+  }
+
+  /// Lines taken up by each of `rows` in the task report as last drawn
+  fn task_report_display_heights(&self, rows: &[ReportRow]) -> Vec<usize> {
+    rows
+      .iter()
+      .map(|row| match *row {
+        ReportRow::Group(_) => 1,
+        ReportRow::Task(i) => self.task_row_heights.get(i).copied().unwrap_or(1),
+      })
+      .collect()
+  }
+
   pub fn task_report_next_page(&mut self) {
     if self.tasks.is_empty() {
       return;
     }
+    let (selectable, pos) = self.task_report_cursor();
+    let last = selectable.len() - 1;
     let i = {
-      if self.current_selection == self.tasks.len() - 1 {
-        if self.config.uda_task_report_looping { 0 } else { self.tasks.len() - 1 }
+      if pos == last {
+        if self.config.uda_task_report_looping { 0 } else { last }
       } else {
-        std::cmp::min(
-          self
-            .current_selection
-            .checked_add(self.task_report_height as usize)
-            .unwrap_or(self.tasks.len() - 1),
-          self.tasks.len() - 1,
-        )
+        self.task_report_page(&selectable, pos, true)
       }
     };
-    self.current_selection = i;
+    self.current_selection = selectable[i];
     self.current_selection_id = None;
     self.current_selection_uuid = None;
   }
@@ -2104,14 +3704,15 @@ impl TaskwarriorTui {
     if self.tasks.is_empty() {
       return;
     }
+    let (selectable, pos) = self.task_report_cursor();
     let i = {
-      if self.current_selection == 0 {
-        if self.config.uda_task_report_looping { self.tasks.len() - 1 } else { 0 }
+      if pos == 0 {
+        if self.config.uda_task_report_looping { selectable.len() - 1 } else { 0 }
       } else {
-        self.current_selection.saturating_sub(self.task_report_height as usize)
+        self.task_report_page(&selectable, pos, false)
       }
     };
-    self.current_selection = i;
+    self.current_selection = selectable[i];
     self.current_selection_id = None;
     self.current_selection_uuid = None;
   }
@@ -2123,9 +3724,7 @@ impl TaskwarriorTui {
     let i = self.command.as_str().parse::<usize>()?;
     if let Some(task) = self.task_by_id(i as u64) {
       let j = self.task_index_by_uuid(*task.uuid()).unwrap_or_default();
-      self.current_selection = j;
-      self.current_selection_id = None;
-      self.current_selection_uuid = None;
+      self.task_report_select(j);
       Ok(())
     } else {
       Err(anyhow!("Cannot locate task id {} in report", i))
@@ -2133,8 +3732,7 @@ impl TaskwarriorTui {
   }
 
   fn get_task_database_mtime(&self) -> Result<SystemTime> {
-    let data_dir = shellexpand::tilde(&self.config.data_location);
-    let database_path = Path::new(data_dir.as_ref()).join("taskchampion.sqlite3");
+    let database_path = self.config.data_dir().join("taskchampion.sqlite3");
 
     let metadata = fs::metadata(database_path).context("Fetching the metadate of the task database failed")?;
     let mtime = metadata
@@ -2162,6 +3760,11 @@ impl TaskwarriorTui {
     }
   }
 
+  /// Whether a feature reads `all_tasks`, so it has to be exported along with the report
+  fn needs_all_tasks(&self) -> bool {
+    self.config.uda_task_report_use_all_tasks_for_completion || self.config.uda_calendar_all_tasks || self.calendar.heatmap
+  }
+
   pub fn export_all_tasks(&mut self) -> Result<()> {
     let mut task = std::process::Command::new(&self.task_exe);
 
@@ -2204,42 +3807,177 @@ impl TaskwarriorTui {
     } else {
       self.error = Some(format!("Cannot run `{:?}` - ({}) error:\n{}", &task, output.status, error));
     }
-
-    Ok(())
+
+    Ok(())
+  }
+
+  /// The key under which exported tasks can be merged: report, filter and context
+  fn current_export_key(&self) -> String {
+    format!("{}\n{}\n{}", self.report, self.filter.as_str().trim(), self.current_context_filter.trim())
+  }
+
+  /// `task ... export <report>` for the current report, filter and context, with `extra` added to the filter
+  fn report_export_command(&self, extra: &[String]) -> std::process::Command {
+    let mut task = std::process::Command::new(&self.task_exe);
+
+    task
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off");
+    // .arg("rc.verbose:override=false");
+
+    if let Some(args) = shlex::split(format!(r#"rc.report.{}.filter='{}'"#, self.report, self.filter.trim()).trim()) {
+      for arg in args {
+        task.arg(arg);
+      }
+    }
+
+    if !self.current_context_filter.trim().is_empty() && self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
+      if let Some(args) = shlex::split(&self.current_context_filter) {
+        for arg in args {
+          task.arg(arg);
+        }
+      }
+    } else if !self.current_context_filter.trim().is_empty() {
+      task.arg(format!("'\\({}\\)'", self.current_context_filter));
+    }
+
+    if let Some(keys) = &self.task_report_table.custom_sort {
+      task.arg(format!("rc.report.{}.sort={}", self.report, sort::format_sort(keys)));
+    }
+
+    task.args(extra);
+    task.arg("export");
+
+    if self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
+      task.arg(&self.report);
+    }
+    task
+  }
+
+  fn run_export(mut task: std::process::Command) -> Result<Vec<Task>> {
+    info!("Running `{:?}`", task);
+    let output = task.output()?;
+    if !output.status.success() {
+      return Err(anyhow!(
+        "Cannot run `{:?}` - ({}) error:\n{}",
+        &task,
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+      ));
+    }
+    import(output.stdout.as_slice()).map_err(|e| anyhow!("Unable to parse output of `{:?}`: {:?}", task, e))
+  }
+
+  /// Re-exports only the tasks modified since the last export and merges them into `tasks` (and
+  /// `all_tasks`) by UUID, keeping the report's sort order.
+  ///
+  /// Returns `false` when a full export is needed instead: the report, filter or context changed,
+  /// a task was completed or deleted (which renumbers IDs), the report sorts by a column that cannot
+  /// be sorted locally, or the last full export is too old.
+  fn export_tasks_incremental(&mut self) -> Result<bool> {
+    let full_export_due = self
+      .last_full_export
+      .and_then(|t| t.elapsed().ok())
+      .is_none_or(|elapsed| elapsed > FULL_EXPORT_INTERVAL);
+    let udas: Vec<String> = self.config.uda.iter().map(|u| u.name.clone()).collect();
+    let sortable = self
+      .task_report_table
+      .sort_keys()
+      .iter()
+      .all(|key| sort::is_supported(&key.column, &udas));
+    let Some(since) = self.export_started else {
+      return Ok(false);
+    };
+    if !self.config.uda_task_report_incremental_export || full_export_due || !sortable || self.export_key != self.current_export_key() {
+      return Ok(false);
+    }
+
+    let started = SystemTime::now();
+    // `modified` has a resolution of one second
+    let since: DateTime<Utc> = (since - Duration::from_secs(1)).into();
+    let mut task = std::process::Command::new(&self.task_exe);
+    task
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg("rc.context=")
+      .arg(format!("modified.after:{}", since.format("%Y%m%dT%H%M%SZ")))
+      .arg("export");
+    let Ok(changed) = Self::run_export(task) else {
+      return Ok(false);
+    };
+    if changed
+      .iter()
+      .any(|t| !matches!(t.status(), TaskStatus::Pending | TaskStatus::Waiting | TaskStatus::Recurring))
+    {
+      return Ok(false);
+    }
+
+    if !changed.is_empty() {
+      // Tasks that depend on a changed task may be blocked or unblocked now, changing their urgency
+      let changed_uuids: HashSet<Uuid> = changed.iter().map(|t| *t.uuid()).collect();
+      let mut uuids = changed_uuids.clone();
+      uuids.extend(
+        self
+          .tasks
+          .iter()
+          .filter(|t| t.depends().is_some_and(|d| d.iter().any(|u| changed_uuids.contains(u))))
+          .map(|t| *t.uuid()),
+      );
+      if uuids.len() > MAX_INCREMENTAL_EXPORT {
+        return Ok(false);
+      }
+      let extra: Vec<String> = uuids.iter().map(ToString::to_string).collect();
+      let Ok(matching) = Self::run_export(self.report_export_command(&extra)) else {
+        return Ok(false);
+      };
+
+      self.tasks.retain(|t| !uuids.contains(t.uuid()));
+      self.tasks.extend(matching);
+      sort::sort_tasks(&mut self.tasks, self.task_report_table.sort_keys(), &self.config.uda_priority_values);
+      info!("Merged {} changed tasks", changed.len());
+
+      if self.needs_all_tasks() {
+        self.all_tasks.retain(|t| !changed_uuids.contains(t.uuid()));
+        self.all_tasks.extend(changed);
+      }
+    }
+    self.export_started = Some(started);
+    Ok(true)
   }
 
-  pub fn export_tasks(&mut self) -> Result<()> {
+  pub fn export_active_tasks(&mut self) -> Result<()> {
     let mut task = std::process::Command::new(&self.task_exe);
-
     task
       .arg("rc.json.array=on")
       .arg("rc.confirmation=off")
       .arg("rc.json.depends.array=on")
       .arg("rc.color=off")
-      .arg("rc._forcecolor=off");
-    // .arg("rc.verbose:override=false");
-
-    if let Some(args) = shlex::split(format!(r#"rc.report.{}.filter='{}'"#, self.report, self.filter.trim()).trim()) {
-      for arg in args {
-        task.arg(arg);
-      }
-    }
+      .arg("rc._forcecolor=off")
+      .arg("+ACTIVE")
+      .arg("export");
 
-    if !self.current_context_filter.trim().is_empty() && self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
-      if let Some(args) = shlex::split(&self.current_context_filter) {
-        for arg in args {
-          task.arg(arg);
-        }
+    info!("Running `{:?}`", task);
+    let output = task.output()?;
+    if output.status.success() {
+      match import(output.stdout.as_slice()) {
+        Ok(imported) => self.active_tasks = imported,
+        Err(err) => debug!("Unable to parse active tasks: {:?}", err),
       }
-    } else if !self.current_context_filter.trim().is_empty() {
-      task.arg(format!("'\\({}\\)'", self.current_context_filter));
+    } else {
+      debug!("Cannot run `{:?}` - ({})", &task, output.status);
     }
+    Ok(())
+  }
 
-    task.arg("export");
-
-    if self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
-      task.arg(&self.report);
-    }
+  pub fn export_tasks(&mut self) -> Result<()> {
+    let started = SystemTime::now();
+    let mut task = self.report_export_command(&[]);
 
     info!("Running `{:#?}`", task);
     let output = task.output()?;
@@ -2252,6 +3990,9 @@ impl TaskwarriorTui {
         Ok(imported) => {
           self.tasks = imported;
           info!("Imported {} tasks", self.tasks.len());
+          self.export_started = Some(started);
+          self.last_full_export = Some(started);
+          self.export_key = self.current_export_key();
           self.error = None;
           if self.mode == Mode::Tasks(Action::Error) {
             self.mode = self.previous_mode.clone().unwrap_or(Mode::Tasks(Action::Report));
@@ -2274,8 +4015,9 @@ impl TaskwarriorTui {
 
   pub fn selected_task_uuids(&self) -> Vec<Uuid> {
     let selected = match self.task_table_state.mode() {
+      TableMode::SingleSelection if self.task_groups.is_hidden(self.current_selection) => vec![],
       TableMode::SingleSelection => vec![self.current_selection],
-      TableMode::MultipleSelection => self.task_table_state.marked().copied().collect::<Vec<usize>>(),
+      TableMode::MultipleSelection => (0..self.tasks.len()).filter(|i| self.marked.contains(self.tasks[*i].uuid())).collect(),
     };
 
     let mut task_uuids = vec![];
@@ -2450,12 +4192,15 @@ impl TaskwarriorTui {
     r
   }
 
-  pub fn task_modify(&mut self) -> Result<(), String> {
+  pub fn task_modify(&mut self) -> Result<Option<Job>, String> {
     if self.tasks.is_empty() {
-      return Ok(());
+      return Ok(None);
     }
 
     let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return Ok(None);
+    }
 
     let mut command = std::process::Command::new(&self.task_exe);
     command.arg("rc.bulk=0");
@@ -2467,44 +4212,35 @@ impl TaskwarriorTui {
     }
     command.arg("modify");
 
-    let shell = self.modify.as_str();
-
-    let r = match shlex::split(shell) {
-      Some(cmd) => {
-        for s in cmd {
-          command.arg(&s);
-        }
-        let output = command.output();
-        match output {
-          Ok(o) => {
-            if o.status.success() {
-              Ok(())
-            } else {
-              Err(format!("Modify failed. {}", String::from_utf8_lossy(&o.stdout)))
-            }
-          }
-          Err(_) => Err(format!(
-            "Cannot run `task {:?} modify {}`. Check documentation for more information",
-            task_uuids, shell,
-          )),
-        }
-      }
-      None => Err(format!("Cannot shlex split `{}`", shell)),
-    };
-
     if let [uuid] = task_uuids.as_slice() {
       self.current_selection_uuid = Some(*uuid);
     }
 
-    r
+    let shell = self.modify.as_str();
+    command.args(shlex::split(shell).ok_or_else(|| format!("Cannot shlex split `{}`", shell))?);
+
+    Ok(Some(
+      Job::new(
+        "modify",
+        format!(
+          "Cannot run `task {:?} modify {}`. Check documentation for more information",
+          task_uuids, shell,
+        ),
+      )
+      .command(command)
+      .fails_with("Modify failed."),
+    ))
   }
 
-  pub fn task_annotate(&mut self) -> Result<(), String> {
+  pub fn task_annotate(&mut self) -> Result<Option<Job>, String> {
     if self.tasks.is_empty() {
-      return Ok(());
+      return Ok(None);
     }
 
     let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return Ok(None);
+    }
 
     let mut command = std::process::Command::new(&self.task_exe);
     command.arg("rc.bulk=0");
@@ -2516,36 +4252,25 @@ impl TaskwarriorTui {
     }
     command.arg("annotate");
 
-    let shell = self.command.as_str();
-
-    let r = match shlex::split(shell) {
-      Some(cmd) => {
-        for s in cmd {
-          command.arg(&s);
-        }
-        let output = command.output();
-        match output {
-          Ok(o) => {
-            if o.status.success() {
-              Ok(())
-            } else {
-              Err(format!("Annotate failed. {}", String::from_utf8_lossy(&o.stdout)))
-            }
-          }
-          Err(_) => Err(format!(
-            "Cannot run `task {} annotate {}`. Check documentation for more information",
-            task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" "),
-            shell
-          )),
-        }
-      }
-      None => Err(format!("Cannot shlex split `{}`", shell)),
-    };
-
     if let [uuid] = task_uuids.as_slice() {
       self.current_selection_uuid = Some(*uuid);
     }
-    r
+
+    let shell = self.command.as_str();
+    command.args(shlex::split(shell).ok_or_else(|| format!("Cannot shlex split `{}`", shell))?);
+
+    Ok(Some(
+      Job::new(
+        "annotate",
+        format!(
+          "Cannot run `task {} annotate {}`. Check documentation for more information",
+          task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" "),
+          shell
+        ),
+      )
+      .command(command)
+      .fails_with("Annotate failed."),
+    ))
   }
 
   pub fn task_add(&mut self) -> Result<(), String> {
@@ -2582,92 +4307,198 @@ impl TaskwarriorTui {
     }
   }
 
-  pub fn task_virtual_tags(task_uuid: Uuid, task_exe: &str) -> Result<String, String> {
-    let output = std::process::Command::new(task_exe).arg(format!("{}", task_uuid)).output();
+  pub fn task_start_stop(&mut self) -> Option<Job> {
+    if self.tasks.is_empty() {
+      return None;
+    }
 
-    match output {
-      Ok(output) => {
-        let data = String::from_utf8_lossy(&output.stdout);
-        for line in data.split('\n') {
-          for prefix in &["Virtual tags", "Virtual"] {
-            if line.starts_with(prefix) {
-              let line = line.to_string();
-              let line = line.replace(prefix, "");
-              return Ok(line);
-            }
-          }
-        }
-        Err(format!(
-          "Cannot find any tags for `task {}`. Check documentation for more information",
-          task_uuid
-        ))
+    let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return None;
+    }
+
+    let mut job = Job::new(
+      "start/stop",
+      format!(
+        "Error running `task start` or `task stop` for tasks `{}`.",
+        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+      ),
+    );
+    for task_uuid in &task_uuids {
+      let task = self.task_by_uuid(*task_uuid);
+      // A task is ACTIVE exactly when it has a start date
+      let active = task.as_ref().is_some_and(|t| t.start().is_some());
+      let mut command = std::process::Command::new(&self.task_exe);
+      command.arg(task_uuid.to_string()).arg(if active { "stop" } else { "start" });
+      job = job.command(command);
+      if active && let Some(command) = task.as_ref().and_then(|t| self.timer_uda_command(t)) {
+        job = job.command(command);
       }
-      Err(_) => Err(format!("Cannot run `task {}`. Check documentation for more information", task_uuid)),
     }
-  }
 
-  pub fn task_start_stop(&mut self) -> Result<(), String> {
-    if self.tasks.is_empty() {
-      return Ok(());
+    if let [uuid] = task_uuids.as_slice() {
+      self.current_selection_uuid = Some(*uuid);
     }
 
-    let task_uuids = self.selected_task_uuids();
+    Some(job)
+  }
 
-    for task_uuid in &task_uuids {
-      let mut command = "start";
-      for tag in TaskwarriorTui::task_virtual_tags(*task_uuid, &self.task_exe)
-        .unwrap_or_default()
-        .split(' ')
-      {
-        if tag == "ACTIVE" {
-          command = "stop";
-        }
+  /// Stops every active task, including the ones that are not in the report
+  pub fn task_stop_all(&mut self) -> Option<Job> {
+    if self.active_tasks.is_empty() {
+      return None;
+    }
+    let mut job = Job::new(
+      "stop all",
+      format!(
+        "Error running `task stop` for tasks `{}`.",
+        self.active_tasks.iter().map(|t| t.uuid().to_string()).collect::<Vec<String>>().join(" ")
+      ),
+    );
+    for task in &self.active_tasks {
+      let mut command = std::process::Command::new(&self.task_exe);
+      command.arg(task.uuid().to_string()).arg("stop");
+      job = job.command(command);
+      if let Some(command) = self.timer_uda_command(task) {
+        job = job.command(command);
       }
+    }
+    Some(job)
+  }
 
-      let output = std::process::Command::new(&self.task_exe)
-        .arg(task_uuid.to_string())
-        .arg(command)
-        .output();
-      if output.is_err() {
-        return Err(format!("Error running `task {}` for task `{}`.", command, task_uuid));
+  /// Starts a pomodoro on the selected task, or cancels the one that is running
+  pub fn task_pomodoro(&mut self) -> Option<Job> {
+    if let Some(pomodoro) = self.pomodoro.take() {
+      let active = self.active_tasks.iter().find(|t| *t.uuid() == pomodoro.uuid);
+      if pomodoro.phase == Phase::Break || active.is_none() {
+        return None;
       }
+      let mut command = std::process::Command::new(&self.task_exe);
+      command.arg(pomodoro.uuid.to_string()).arg("stop");
+      let mut job = Job::new("pomodoro", format!("Error running `task stop` for task `{}`.", pomodoro.uuid)).command(command);
+      if let Some(command) = active.and_then(|t| self.timer_uda_command(t)) {
+        job = job.command(command);
+      }
+      return Some(job);
     }
+    let task = self.task_current()?;
+    self.current_selection_uuid = Some(*task.uuid());
+    self.pomodoro_start(&task)
+  }
 
-    if let [uuid] = task_uuids.as_slice() {
-      self.current_selection_uuid = Some(*uuid);
+  fn pomodoro_start(&mut self, task: &Task) -> Option<Job> {
+    let now = Utc::now().naive_utc();
+    let length = chrono::Duration::minutes(self.config.uda_pomodoro_work as i64);
+    self.pomodoro = Some(Pomodoro::start(task, now, length, &self.config.uda_pomodoro_uda));
+    if self.active_tasks.iter().any(|t| t.uuid() == task.uuid()) {
+      return None;
+    }
+    let mut command = std::process::Command::new(&self.task_exe);
+    command.arg(task.uuid().to_string()).arg("start");
+    Some(Job::new("pomodoro", format!("Error running `task start` for task `{}`.", task.uuid())).command(command))
+  }
+
+  /// Ends the pomodoro whose interval is over: stops its task, records it and shows the break prompt
+  fn pomodoro_tick(&mut self) -> Option<Job> {
+    let now = Utc::now().naive_utc();
+    let pomodoro = self.pomodoro.as_mut().filter(|p| p.is_over(now))?;
+    if pomodoro.phase == Phase::Break {
+      // A break that is not shown in the prompt ends on its own
+      if self.mode != Mode::Tasks(Action::PomodoroBreak) {
+        self.pomodoro = None;
+      }
+      return None;
     }
+    pomodoro.take_break(now, chrono::Duration::minutes(self.config.uda_pomodoro_break as i64));
+    let pomodoro = pomodoro.clone();
 
-    Ok(())
+    let mut job = Job::new(
+      "pomodoro",
+      format!("Error recording pomodoro #{} on task `{}`.", pomodoro.number, pomodoro.uuid),
+    );
+    if let Some(task) = self.active_tasks.iter().find(|t| *t.uuid() == pomodoro.uuid) {
+      let mut command = std::process::Command::new(&self.task_exe);
+      command.arg(pomodoro.uuid.to_string()).arg("stop");
+      job = job.command(command);
+      if let Some(command) = self.timer_uda_command(task) {
+        job = job.command(command);
+      }
+    }
+    if self.config.uda_pomodoro_annotate {
+      let mut command = std::process::Command::new(&self.task_exe);
+      command
+        .arg(pomodoro.uuid.to_string())
+        .arg("annotate")
+        .arg(format!("{}{}", pomodoro::ANNOTATION, pomodoro.number));
+      job = job.command(command);
+    }
+    if !self.config.uda_pomodoro_uda.is_empty() {
+      let mut command = std::process::Command::new(&self.task_exe);
+      command
+        .arg("rc.bulk=0")
+        .arg("rc.confirmation=off")
+        .arg(pomodoro.uuid.to_string())
+        .arg("modify")
+        .arg(format!("{}:{}", self.config.uda_pomodoro_uda, pomodoro.number));
+      job = job.command(command);
+    }
+
+    // Don't take over a prompt the user is typing in; the break still counts down in the tab bar
+    if matches!(self.mode, Mode::Tasks(Action::Report)) || !matches!(self.mode, Mode::Tasks(_)) {
+      self.previous_mode = Some(self.mode.clone());
+      self.mode = Mode::Tasks(Action::PomodoroBreak);
+    }
+    Some(job)
+  }
+
+  /// Adds the run of `task` that is being stopped to the duration UDA `uda.taskwarrior-tui.timer.uda`, if one is set
+  fn timer_uda_command(&self, task: &Task) -> Option<std::process::Command> {
+    let uda = &self.config.uda_timer_uda;
+    if uda.is_empty() {
+      return None;
+    }
+    let spent = timer::tracked(task, Utc::now().naive_utc(), uda, "", "");
+    let mut command = std::process::Command::new(&self.task_exe);
+    command
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg(task.uuid().to_string())
+      .arg("modify")
+      .arg(format!("{}:{}", uda, timer::format_iso_duration(spent)));
+    Some(command)
   }
 
-  pub fn task_quick_tag(&mut self) -> Result<(), String> {
+  pub fn task_quick_tag(&mut self) -> Option<Job> {
     let tag_name = &self.config.uda_quick_tag_name;
     let ptag_name = format!("+{}", tag_name);
     let ntag_name = format!("-{}", tag_name);
     if self.tasks.is_empty() {
-      return Ok(());
+      return None;
     }
 
     let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return None;
+    }
 
+    let mut job = Job::new(
+      "tag",
+      format!(
+        "Error running `task modify {}` for tasks `{}`.",
+        ptag_name,
+        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+      ),
+    );
     for task_uuid in &task_uuids {
       if let Some(task) = self.task_by_uuid(*task_uuid) {
-        let mut tag_to_set = &ptag_name;
-        for tag in task.tags().unwrap() {
-          if tag == tag_name {
-            tag_to_set = &ntag_name;
-          }
-        }
-
-        let output = std::process::Command::new(&self.task_exe)
-          .arg(task_uuid.to_string())
-          .arg("modify")
-          .arg(tag_to_set)
-          .output();
-
-        if output.is_err() {
-          return Err(format!("Error running `task modify {}` for task `{}`.", tag_to_set, task_uuid,));
-        }
+        let tag_to_set = if task.tags().is_some_and(|tags| tags.contains(tag_name)) {
+          &ntag_name
+        } else {
+          &ptag_name
+        };
+        let mut command = std::process::Command::new(&self.task_exe);
+        command.arg(task_uuid.to_string()).arg("modify").arg(tag_to_set);
+        job = job.command(command);
       }
     }
 
@@ -2675,15 +4506,18 @@ impl TaskwarriorTui {
       self.current_selection_uuid = Some(*uuid);
     }
 
-    Ok(())
+    Some(job)
   }
 
-  pub fn task_delete(&mut self) -> Result<(), String> {
+  pub fn task_delete(&mut self) -> Option<Job> {
     if self.tasks.is_empty() {
-      return Ok(());
+      return None;
     }
 
     let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return None;
+    }
 
     let mut cmd = std::process::Command::new(&self.task_exe);
     cmd
@@ -2695,24 +4529,28 @@ impl TaskwarriorTui {
       cmd.arg(task_uuid.to_string());
     }
     cmd.arg("delete");
-    let output = cmd.output();
-    let r = match output {
-      Ok(_) => Ok(()),
-      Err(_) => Err(format!(
+    let job = Job::new(
+      "delete",
+      format!(
         "Cannot run `task delete` for tasks `{}`. Check documentation for more information",
         task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
-      )),
-    };
+      ),
+    )
+    .command(cmd)
+    .removes(&task_uuids);
     self.current_selection_uuid = None;
     self.current_selection_id = None;
-    r
+    Some(job)
   }
 
-  pub fn task_done(&mut self) -> Result<(), String> {
+  pub fn task_done(&mut self) -> Option<Job> {
     if self.tasks.is_empty() {
-      return Ok(());
+      return None;
     }
     let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return None;
+    }
     let mut cmd = std::process::Command::new(&self.task_exe);
     cmd
       .arg("rc.bulk=0")
@@ -2723,26 +4561,30 @@ impl TaskwarriorTui {
       cmd.arg(task_uuid.to_string());
     }
     cmd.arg("done");
-    let output = cmd.output();
-    let r = match output {
-      Ok(_) => Ok(()),
-      Err(_) => Err(format!(
+    let job = Job::new(
+      "done",
+      format!(
         "Cannot run `task done` for task `{}`. Check documentation for more information",
         task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
-      )),
-    };
+      ),
+    )
+    .command(cmd)
+    .removes(&task_uuids);
     self.current_selection_uuid = None;
     self.current_selection_id = None;
-    r
+    Some(job)
   }
 
-  pub fn task_priority(&mut self, priority: &str) -> Result<(), String> {
+  pub fn task_priority(&mut self, priority: &str) -> Option<Job> {
     if self.tasks.is_empty() {
-      return Ok(());
+      return None;
     }
     let mut priority_arg = String::from("priority:");
     priority_arg.push_str(priority);
     let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return None;
+    }
     let mut cmd = std::process::Command::new(&self.task_exe);
     cmd
       .arg("rc.bulk=0")
@@ -2754,17 +4596,203 @@ impl TaskwarriorTui {
     for task_uuid in &task_uuids {
       cmd.arg(task_uuid.to_string());
     }
-    let output = cmd.output();
-    let r = match output {
-      Ok(_) => Ok(()),
-      Err(_) => Err(format!(
-        "Cannot run `task modify priority` for task `{}`. Check documentation for more information",
+    let job = Job::new(
+      "priority",
+      format!(
+        "Cannot run `task modify priority` for task `{}`. Check documentation for more information",
+        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+      ),
+    )
+    .command(cmd);
+    self.current_selection_uuid = None;
+    self.current_selection_id = None;
+    Some(job)
+  }
+
+  /// Sets the day under the calendar cursor as the `field` date of the tasks marked in the report.
+  /// The report's cursor is not shown in the calendar, so nothing is changed when no task is marked.
+  pub fn task_set_date(&mut self, field: &str) -> Option<Job> {
+    let task_uuids: Vec<Uuid> = self.tasks.iter().map(|t| *t.uuid()).filter(|uuid| self.marked.contains(uuid)).collect();
+    if task_uuids.is_empty() {
+      self.error = Some(format!("Mark the tasks to set the {} date of in the Tasks pane first.", field));
+      self.previous_mode = Some(self.mode.clone());
+      self.mode = Mode::Tasks(Action::Error);
+      return None;
+    }
+    let date_arg = format!("{}:{}", field, self.calendar.date.format("%Y-%m-%d"));
+    let mut cmd = std::process::Command::new(&self.task_exe);
+    cmd
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg("rc.dependency.confirmation=off")
+      .arg("rc.recurrence.confirmation=off");
+    for task_uuid in &task_uuids {
+      cmd.arg(task_uuid.to_string());
+    }
+    cmd.arg("modify").arg(&date_arg);
+    let job = Job::new(
+      format!("{} {}", field, self.calendar.date.format("%Y-%m-%d")),
+      format!(
+        "Cannot run `task modify {}` for task `{}`. Check documentation for more information",
+        date_arg,
+        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+      ),
+    )
+    .command(cmd)
+    .fails_with(format!("Setting {} failed.", field));
+    self.current_selection_uuid = None;
+    self.current_selection_id = None;
+    Some(job)
+  }
+
+  /// Queues a job, hiding the tasks it removes from the report right away.
+  /// With `uda.taskwarrior-tui.background-jobs` off, runs it and refreshes instead.
+  pub async fn task_submit(&mut self, job: Option<Job>) -> Result<()> {
+    let Some(job) = job else {
+      return Ok(());
+    };
+    if !self.config.uda_background_jobs {
+      match job.run() {
+        Ok(()) => self.update(true).await?,
+        Err(e) => {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      return Ok(());
+    }
+    if !job.removes.is_empty() {
+      self.tasks.retain(|t| !job.removes.contains(t.uuid()));
+      self.group_tasks();
+      self.cursor_fix();
+      self.update_task_table_state();
+    }
+    self.jobs.push(job);
+    Ok(())
+  }
+
+  /// Makes the selected task depend on the other marked task, or removes that dependency.
+  /// Exactly two tasks have to be marked, one of them being the selected task.
+  pub fn task_dependency(&mut self, add: bool) -> Result<Option<Job>, String> {
+    let marked: Vec<Uuid> = self.marked.iter().copied().collect();
+    let current = self.task_current().map(|t| *t.uuid());
+    let (Some(task_uuid), [a, b]) = (current, marked.as_slice()) else {
+      return Err("Mark two tasks and select the one that depends on the other".to_string());
+    };
+    let other = if task_uuid == *a {
+      *b
+    } else if task_uuid == *b {
+      *a
+    } else {
+      return Err("Select the marked task that depends on the other".to_string());
+    };
+
+    let depends = if add {
+      format!("depends:{}", other)
+    } else {
+      format!("depends:-{}", other)
+    };
+    let mut cmd = std::process::Command::new(&self.task_exe);
+    cmd
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg("rc.dependency.confirmation=off")
+      .arg("rc.recurrence.confirmation=off")
+      .arg(task_uuid.to_string())
+      .arg("modify")
+      .arg(&depends);
+    let job = Job::new(
+      if add { "add dependency" } else { "remove dependency" },
+      format!(
+        "Cannot run `task {} modify {}`. Check documentation for more information",
+        task_uuid, depends
+      ),
+    )
+    .command(cmd)
+    .fails_with("Changing the dependency failed.");
+    self.marked.clear();
+    self.update_task_table_state();
+    self.current_selection_uuid = Some(task_uuid);
+    Ok(Some(job))
+  }
+
+  /// Picks up the task under the cursor, or schedules the picked up task in the hour under the cursor at the
+  /// same minutes past the hour. Dropping it back in the hour it was picked up from leaves it unchanged.
+  pub fn task_agenda_select(&mut self) -> Option<Job> {
+    let Some(grabbed) = self.agenda.grabbed.take() else {
+      let entries = self.agenda_entries();
+      self.agenda.grabbed = self.agenda.selected(&entries).cloned();
+      return None;
+    };
+    if (grabbed.day, grabbed.hour) == (self.agenda.day, self.agenda.hour) {
+      return None;
+    }
+    let task_uuid = grabbed.uuid;
+    let scheduled = format!(
+      "scheduled:{}",
+      datetime::format_taskwarrior_datetime_literal(&self.agenda.slot_time(grabbed.minute)?)
+    );
+    let mut cmd = std::process::Command::new(&self.task_exe);
+    cmd
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg("rc.dependency.confirmation=off")
+      .arg("rc.recurrence.confirmation=off")
+      .arg(task_uuid.to_string())
+      .arg("modify")
+      .arg(&scheduled);
+    let job = Job::new(
+      "reschedule",
+      format!(
+        "Cannot run `task {} modify {}`. Check documentation for more information",
+        task_uuid, scheduled
+      ),
+    )
+    .command(cmd)
+    .fails_with("Rescheduling the task failed.");
+    Some(job)
+  }
+
+  /// Moves the selected card, or all marked cards, to the column next to the focused one
+  pub fn task_board_move(&mut self, right: bool) -> Option<Job> {
+    let to = if right {
+      Some(self.board.focus + 1).filter(|to| *to < self.board.columns.len())
+    } else {
+      self.board.focus.checked_sub(1)
+    }?;
+    if !board::select_in_report(self) && self.marked.is_empty() {
+      return None;
+    }
+    let task_uuids = self.selected_task_uuids();
+    let mut job = Job::new(
+      format!("move to {}", self.board.columns[to].title()),
+      format!(
+        "Cannot move tasks `{}`. Check documentation for more information",
         task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
-      )),
-    };
-    self.current_selection_uuid = None;
-    self.current_selection_id = None;
-    r
+      ),
+    )
+    .fails_with("Moving the card failed.");
+    for task_uuid in &task_uuids {
+      let Some(task) = self.task_by_uuid(*task_uuid) else {
+        continue;
+      };
+      for args in self.board.move_args(&task, to) {
+        let mut cmd = std::process::Command::new(&self.task_exe);
+        cmd
+          .arg("rc.bulk=0")
+          .arg("rc.confirmation=off")
+          .arg("rc.dependency.confirmation=off")
+          .arg("rc.recurrence.confirmation=off")
+          .arg(task_uuid.to_string())
+          .args(args);
+        job = job.command(cmd);
+      }
+    }
+    self.board.focus = to;
+    if let Some(task) = self.task_current() {
+      self.current_selection_uuid = Some(*task.uuid());
+    }
+    Some(job).filter(|job| !job.is_empty())
   }
 
   pub fn task_undo(&mut self) -> Result<(), String> {
@@ -2825,15 +4853,13 @@ impl TaskwarriorTui {
   }
 
   pub async fn task_edit(&mut self) -> Result<(), String> {
-    if self.tasks.is_empty() {
+    let Some(task) = self.task_current() else {
       return Ok(());
-    }
+    };
 
     self.pause_tui().await.unwrap();
 
-    let selected = self.current_selection;
-    let task_id = self.tasks[selected].id().unwrap_or_default();
-    let task_uuid = *self.tasks[selected].uuid();
+    let task_uuid = *task.uuid();
 
     let r = std::process::Command::new(&self.task_exe)
       .arg(format!("{}", task_uuid))
@@ -2872,8 +4898,102 @@ impl TaskwarriorTui {
     r
   }
 
+  pub fn task_editor_open(&mut self) {
+    let Some(task) = self.task_current() else {
+      return;
+    };
+    let mut editor = TaskEditor::new(&task, &self.config.uda, &self.config.uda_priority_values, |uuid| {
+      self
+        .all_tasks
+        .iter()
+        .chain(self.tasks.iter())
+        .find(|t| t.uuid() == uuid)
+        .and_then(|t| t.id())
+        .map_or_else(|| uuid.to_string(), |id| id.to_string())
+    });
+    editor.date_format.clone_from(&self.config.dateformat);
+
+    self.completion_list.clear();
+    let tasks = if self.config.uda_task_report_use_all_tasks_for_completion {
+      &self.all_tasks
+    } else {
+      &self.tasks
+    };
+    let virtual_tags = &self.task_report_table.virtual_tags;
+    for task in tasks {
+      if let Some(project) = task.project() {
+        self.completion_list.insert(("project".to_string(), project.to_string()));
+      }
+      for tag in task.tags().into_iter().flatten() {
+        if !virtual_tags.contains(tag) {
+          self.completion_list.insert(("tags".to_string(), tag.to_string()));
+        }
+      }
+      if let Some(id) = task.id() {
+        self.completion_list.insert(("depends".to_string(), id.to_string()));
+      }
+    }
+    for field in &editor.fields {
+      let candidates: Vec<String> = match &field.kind {
+        FieldKind::Choice(values) => values.clone(),
+        FieldKind::Date => DATE_SYNONYMS.iter().map(ToString::to_string).collect(),
+        FieldKind::Duration => RECURRENCE_SYNONYMS.iter().map(ToString::to_string).collect(),
+        _ => vec![],
+      };
+      for candidate in candidates {
+        self.completion_list.insert((field.attribute.clone(), candidate));
+      }
+    }
+
+    self.task_editor = Some(editor);
+    self.show_completion_pane = false;
+    self.mode = Mode::Tasks(Action::Edit);
+    self.update_input_for_completion();
+  }
+
+  /// Sends every changed field of the task editor in one `task modify`.
+  /// Returns `Ok(false)` when nothing was changed.
+  pub fn task_editor_save(&mut self) -> Result<bool, String> {
+    let Some(editor) = &self.task_editor else {
+      return Ok(false);
+    };
+    editor.validate()?;
+    let modifications = editor.modifications();
+    if modifications.is_empty() {
+      return Ok(false);
+    }
+
+    let task_uuid = editor.uuid;
+    let output = std::process::Command::new(&self.task_exe)
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg("rc.dependency.confirmation=off")
+      .arg("rc.recurrence.confirmation=off")
+      .arg(task_uuid.to_string())
+      .arg("modify")
+      .args(&modifications)
+      .output();
+
+    self.current_selection_uuid = Some(task_uuid);
+
+    match output {
+      Ok(o) if o.status.success() => Ok(true),
+      Ok(o) => Err(format!(
+        "Modify failed. {}{}",
+        String::from_utf8_lossy(&o.stdout),
+        String::from_utf8_lossy(&o.stderr)
+      )),
+      Err(_) => Err(format!(
+        "Cannot run `task {} modify {}`. Check documentation for more information",
+        task_uuid,
+        modifications.join(" "),
+      )),
+    }
+  }
+
+  /// The task under the cursor, which is none on a folded group
   pub fn task_current(&self) -> Option<Task> {
-    if self.tasks.is_empty() {
+    if self.tasks.is_empty() || self.task_groups.is_hidden(self.current_selection) {
       return None;
     }
     let selected = self.current_selection;
@@ -3000,10 +5120,8 @@ impl TaskwarriorTui {
   }
 
   pub fn toggle_mark(&mut self) {
-    if !self.tasks.is_empty() {
-      let selected = self.current_selection;
-      let task_id = self.tasks[selected].id().unwrap_or_default();
-      let task_uuid = *self.tasks[selected].uuid();
+    if let Some(task) = self.task_current() {
+      let task_uuid = *task.uuid();
 
       if !self.marked.insert(task_uuid) {
         self.marked.remove(&task_uuid);
@@ -3036,438 +5154,652 @@ impl TaskwarriorTui {
   }
 
   pub async fn handle_input(&mut self, input: KeyCode) -> Result<()> {
+    let mode = self.mode.clone();
     match self.mode {
       Mode::Tasks(_) => {
         self.handle_input_by_task_mode(input).await?;
       }
       Mode::Projects => {
-        ProjectsState::handle_input(self, input)?;
+        if input == KeyCode::Char('\n') && self.pending_keys.is_empty() {
+          project::filter_by_selected_project(self);
+          self.set_tab(Mode::Tasks(Action::Report));
+        } else {
+          match self.resolve_keys(KeyScope::Projects, input) {
+            Some(command @ (Command::RenameProject | Command::MergeProject)) => {
+              if let Some(from) = self.projects.selected().map(|p| p.name.clone()) {
+                let (target, search) = if command == Command::RenameProject {
+                  let search = if from == project::NO_PROJECT { String::new() } else { from.clone() };
+                  (ProjectPickerTarget::Rename(from), search)
+                } else {
+                  (ProjectPickerTarget::Merge(from), String::new())
+                };
+                self.project_picker_open(target, search)?;
+              }
+            }
+            Some(command) => ProjectsState::handle_command(self, command)?,
+            None => {}
+          }
+        }
         self.update(false).await?;
       }
-      Mode::Timesheet => {
-        if input == self.keyconfig.quit || input == KeyCode::Ctrl('c') {
-          self.should_quit = true;
-        } else if input == self.keyconfig.next_tab {
-          self.mode = Mode::Calendar;
-        } else if input == self.keyconfig.previous_tab {
-          self.mode = Mode::Projects;
-        } else if input == KeyCode::Up || input == self.keyconfig.up {
-          self.timesheet_scroll = self.timesheet_scroll.saturating_sub(1);
-        } else if input == KeyCode::Down || input == self.keyconfig.down {
-          self.timesheet_scroll = self.timesheet_scroll.saturating_add(1);
-        } else if input == KeyCode::PageUp || input == self.keyconfig.page_up {
-          self.timesheet_scroll = self.timesheet_scroll.saturating_sub(self.terminal_height);
-        } else if input == KeyCode::PageDown || input == self.keyconfig.page_down {
-          self.timesheet_scroll = self.timesheet_scroll.saturating_add(self.terminal_height);
-        }
-        // Clamp scroll: viewport is terminal height minus the tab bar row.
-        let viewport = self.terminal_height.saturating_sub(1);
-        let max_scroll = self.timesheet_line_count.saturating_sub(viewport);
-        self.timesheet_scroll = self.timesheet_scroll.min(max_scroll);
-      }
-      Mode::Calendar => {
-        if input == self.keyconfig.quit || input == KeyCode::Ctrl('c') {
-          self.should_quit = true;
-        } else if input == self.keyconfig.next_tab {
-          if self.config.uda_change_focus_rotate {
-            self.mode = Mode::Tasks(Action::Report);
+      Mode::Timesheet => match self.resolve_keys(KeyScope::Timesheet, input) {
+        Some(command @ (Command::Undo | Command::Refresh | Command::StopAll)) => self.run_command(command).await?,
+        // The usual task actions, on the task under the cursor or the marked tasks
+        Some(command @ (Command::Done | Command::Delete | Command::StartStop | Command::Edit | Command::Modify | Command::Annotate)) => {
+          if timesheet::select_in_report(self) {
+            self.run_command(command).await?;
+          } else if let Some(row) = self.timesheet.selected() {
+            self.error = Some(format!("`{}` is not in the {} report", row.description, self.report));
+            self.previous_mode = Some(self.mode.clone());
+            self.mode = Mode::Tasks(Action::Error);
           }
-        } else if input == self.keyconfig.previous_tab {
-          self.mode = Mode::Timesheet;
-        } else if input == KeyCode::Up || input == self.keyconfig.up {
-          if self.calendar_year > 0 {
-            self.calendar_year -= 1;
-          }
-        } else if input == KeyCode::Down || input == self.keyconfig.down {
-          self.calendar_year += 1;
-        } else if input == KeyCode::PageUp || input == self.keyconfig.page_up {
-          self.task_report_previous_page();
-        } else if input == KeyCode::PageDown || input == self.keyconfig.page_down {
-          self.calendar_year += 10;
-        } else if input == KeyCode::Ctrl('e') {
-          self.task_details_scroll_down();
-        } else if input == KeyCode::Ctrl('y') {
-          self.task_details_scroll_up();
-        } else if input == self.keyconfig.done {
+        }
+        Some(command) => TimesheetState::handle_command(self, command)?,
+        None => {}
+      },
+      Mode::Calendar => match self.resolve_keys(KeyScope::Calendar, input) {
+        Some(Command::Quit) => self.should_quit = true,
+        Some(Command::NextTab) => self.next_tab(),
+        Some(Command::PreviousTab) => self.previous_tab(),
+        Some(command @ (Command::SetDue | Command::SetScheduled)) => {
+          let job = self.task_set_date(if command == Command::SetDue { "due" } else { "scheduled" });
+          self.task_submit(job).await?;
+        }
+        Some(Command::ScrollDetailsDown) => self.task_details_scroll_down(),
+        Some(Command::ScrollDetailsUp) => self.task_details_scroll_up(),
+        Some(Command::Done) => {
           if self.config.uda_task_report_prompt_on_done {
             self.mode = Mode::Tasks(Action::DonePrompt);
             if self.task_current().is_none() {
               self.mode = Mode::Tasks(Action::Report);
             }
           } else {
-            match self.task_done() {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-            if self.calendar_year > 0 {
-              self.calendar_year -= 10;
-            }
+            let job = self.task_done();
+            self.task_submit(job).await?;
           }
         }
+        Some(command) => CalendarState::handle_command(self, command)?,
+        None => {}
+      },
+      Mode::Dependencies => match self.resolve_keys(KeyScope::Dependencies, input) {
+        Some(command @ (Command::AddDependency | Command::RemoveDependency)) => match self.task_dependency(command == Command::AddDependency) {
+          Ok(job) => self.task_submit(job).await?,
+          Err(e) => {
+            self.error = Some(e);
+            self.previous_mode = Some(self.mode.clone());
+            self.mode = Mode::Tasks(Action::Error);
+          }
+        },
+        Some(command) => DependencyState::handle_command(self, command)?,
+        None => {}
+      },
+      Mode::Agenda if input == KeyCode::Esc && self.pending_keys.is_empty() && self.agenda.grabbed.is_some() => {
+        self.agenda.grabbed = None;
       }
+      Mode::Agenda => match self.resolve_keys(KeyScope::Agenda, input) {
+        Some(Command::Select) => {
+          let job = self.task_agenda_select();
+          self.task_submit(job).await?;
+        }
+        Some(command) => AgendaState::handle_command(self, command)?,
+        None => {}
+      },
+      Mode::Board => match self.resolve_keys(KeyScope::Board, input) {
+        Some(command @ (Command::MoveLeft | Command::MoveRight)) => {
+          let job = self.task_board_move(command == Command::MoveRight);
+          self.task_submit(job).await?;
+        }
+        Some(command) => BoardState::handle_command(self, command)?,
+        None => {}
+      },
+    }
+    // Each prompt starts with a fresh undo history, whether it was submitted or cancelled
+    if self.mode != mode {
+      self.changes.reset();
     }
     self.update_task_table_state();
     Ok(())
   }
 
-  async fn handle_input_by_task_mode(&mut self, input: KeyCode) -> Result<()> {
-    if let Mode::Tasks(task_mode) = &self.mode {
-      match task_mode {
-        Action::Report => {
-          if input == KeyCode::Esc {
-            self.marked.clear();
-          } else if input == self.keyconfig.quit || input == KeyCode::Ctrl('c') {
-            self.should_quit = true;
-          } else if input == self.keyconfig.select {
-            self.task_table_state.multiple_selection();
-            self.toggle_mark();
-          } else if input == self.keyconfig.select_all {
-            self.task_table_state.multiple_selection();
-            self.toggle_mark_all();
-          } else if input == self.keyconfig.refresh {
-            self.update(true).await?;
-          } else if input == self.keyconfig.go_to_bottom || input == KeyCode::End {
-            self.task_report_bottom();
-          } else if input == self.keyconfig.go_to_top || input == KeyCode::Home {
-            self.task_report_top();
-          } else if input == KeyCode::Down || input == self.keyconfig.down {
-            self.task_report_next();
-          } else if input == KeyCode::Up || input == self.keyconfig.up {
-            self.task_report_previous();
-          } else if input == KeyCode::PageDown || input == self.keyconfig.page_down {
-            self.task_report_next_page();
-          } else if input == KeyCode::PageUp || input == self.keyconfig.page_up {
-            self.task_report_previous_page();
-          } else if input == KeyCode::Ctrl('e') {
-            self.task_details_scroll_down();
-          } else if input == KeyCode::Ctrl('y') {
-            self.task_details_scroll_up();
-          } else if input == self.keyconfig.done {
-            if self.config.uda_task_report_prompt_on_done {
-              self.mode = Mode::Tasks(Action::DonePrompt);
-              if self.task_current().is_none() {
-                self.mode = Mode::Tasks(Action::Report);
-              }
-            } else {
-              match self.task_done() {
-                Ok(_) => self.update(true).await?,
-                Err(e) => {
-                  self.error = Some(e);
-                  self.mode = Mode::Tasks(Action::Error);
-                }
-              }
-            }
-          } else if input == self.keyconfig.delete {
-            if self.config.uda_task_report_prompt_on_delete {
-              self.mode = Mode::Tasks(Action::DeletePrompt);
-              if self.task_current().is_none() {
-                self.mode = Mode::Tasks(Action::Report);
-              }
-            } else {
-              match self.task_delete() {
-                Ok(_) => self.update(true).await?,
-                Err(e) => {
-                  self.error = Some(e);
-                  self.mode = Mode::Tasks(Action::Error);
-                }
-              }
-            }
-          } else if input == self.keyconfig.start_stop {
-            match self.task_start_stop() {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.quick_tag {
-            match self.task_quick_tag() {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.edit {
-            match self.task_edit().await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.duplicate {
-            match self.task_duplicate() {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.undo {
-            if self.config.uda_task_report_prompt_on_undo {
-              self.mode = Mode::Tasks(Action::UndoPrompt);
-              if self.task_current().is_none() {
-                self.mode = Mode::Tasks(Action::Report);
-              }
-            } else {
-              match self.task_undo() {
-                Ok(_) => self.update(true).await?,
-                Err(e) => {
-                  self.error = Some(e);
-                  self.mode = Mode::Tasks(Action::Error);
-                }
-              }
-            }
-          } else if input == self.keyconfig.modify {
-            self.mode = Mode::Tasks(Action::Modify);
-            self.command_history.reset();
-            self.history_status = Some(format!(
-              "{} / {}",
-              self
-                .command_history
-                .history_index()
-                .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
-                .saturating_add(1),
-              self.command_history.history_len()
-            ));
-            self.update_completion_list();
-            match self.task_table_state.mode() {
-              TableMode::SingleSelection => match self.task_current() {
-                Some(t) => {
-                  let mut s = format!("{} ", Self::escape(t.description()));
-                  if self.config.uda_prefill_task_metadata {
-                    if t.tags().is_some() {
-                      let virtual_tags = self.task_report_table.virtual_tags.clone();
-                      for tag in t.tags().unwrap() {
-                        if !virtual_tags.contains(tag) {
-                          s = format!("{}+{} ", s, tag);
-                        }
-                      }
-                    }
-                    if t.project().is_some() {
-                      s = format!("{}project:{} ", s, t.project().unwrap());
-                    }
-                    if t.priority().is_some() {
-                      s = format!("{}priority:{} ", s, t.priority().unwrap());
-                    }
-                    if t.due().is_some() {
-                      let date = t.due().unwrap();
-                      s = format!("{}due:{} ", s, get_formatted_datetime(date));
-                    }
-                  }
-                  self.modify.update(&s, s.as_str().len(), &mut self.changes);
-                }
-                None => self.modify.update("", 0, &mut self.changes),
-              },
-              TableMode::MultipleSelection => self.modify.update("", 0, &mut self.changes),
-            }
-          } else if input == self.keyconfig.shell {
-            self.mode = Mode::Tasks(Action::Subprocess);
-          } else if input == self.keyconfig.log {
-            self.mode = Mode::Tasks(Action::Log);
-            self.command_history.reset();
-            self.history_status = Some(format!(
-              "{} / {}",
-              self
-                .command_history
-                .history_index()
-                .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
-                .saturating_add(1),
-              self.command_history.history_len()
-            ));
-            self.update_completion_list();
-          } else if input == self.keyconfig.add {
-            self.mode = Mode::Tasks(Action::Add);
-            self.command_history.reset();
-            self.history_status = Some(format!(
-              "{} / {}",
-              self
-                .command_history
-                .history_index()
-                .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
-                .saturating_add(1),
-              self.command_history.history_len()
-            ));
-            self.update_completion_list();
-          } else if input == self.keyconfig.annotate {
-            self.mode = Mode::Tasks(Action::Annotate);
-            self.command_history.reset();
-            self.history_status = Some(format!(
-              "{} / {}",
-              self
-                .command_history
-                .history_index()
-                .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
-                .saturating_add(1),
-              self.command_history.history_len()
-            ));
-            self.update_completion_list();
-          } else if input == self.keyconfig.help {
-            self.mode = Mode::Tasks(Action::HelpPopup);
-          } else if input == self.keyconfig.filter {
-            self.mode = Mode::Tasks(Action::Filter);
-            self.filter_history.reset();
-            self.history_status = Some(format!(
-              "{} / {}",
-              self
-                .filter_history
-                .history_index()
-                .unwrap_or_else(|| self.filter_history.history_len().saturating_sub(1))
-                .saturating_add(1),
-              self.filter_history.history_len()
-            ));
-            self.update_completion_list();
-          } else if input == KeyCode::Char(':') {
-            self.mode = Mode::Tasks(Action::Jump);
-          } else if input == self.keyconfig.shortcut1 {
-            match self.task_shortcut(1).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.priority_h {
-            match self.task_priority("H") {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.priority_m {
-            match self.task_priority("M") {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.priority_l {
-            match self.task_priority("L") {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.priority_n {
-            match self.task_priority("") {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.shortcut2 {
-            match self.task_shortcut(2).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.shortcut3 {
-            match self.task_shortcut(3).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.shortcut4 {
-            match self.task_shortcut(4).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.shortcut5 {
-            match self.task_shortcut(5).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.shortcut6 {
-            match self.task_shortcut(6).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
-            }
-          } else if input == self.keyconfig.shortcut7 {
-            match self.task_shortcut(7).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
+  /// Adds `input` to the pending key sequence and returns the command it completes, if any.
+  ///
+  /// Esc drops a pending sequence. A key that does not continue the pending sequence
+  /// starts a new one, so a mistyped chord does not swallow the next key.
+  fn resolve_keys(&mut self, scope: KeyScope, input: KeyCode) -> Option<Command> {
+    if input == KeyCode::Esc && !self.pending_keys.is_empty() {
+      self.pending_keys.clear();
+      return None;
+    }
+    self.pending_keys.push(input);
+    match self.keyconfig.resolve(scope, &self.pending_keys) {
+      KeyMatch::Command(command) => {
+        self.pending_keys.clear();
+        Some(command)
+      }
+      KeyMatch::Pending => None,
+      KeyMatch::Unbound => {
+        let restart = self.pending_keys.len() > 1;
+        self.pending_keys.clear();
+        if restart { self.resolve_keys(scope, input) } else { None }
+      }
+    }
+  }
+
+  /// Opens the project picker; the projects to choose from include those of completed tasks
+  pub fn project_picker_open(&mut self, target: ProjectPickerTarget, search: String) -> Result<()> {
+    self.export_all_tasks()?;
+    self.project_picker.open(target, &self.all_tasks, search);
+    self.previous_mode = Some(self.mode.clone());
+    self.mode = Mode::Tasks(Action::ProjectPicker);
+    Ok(())
+  }
+
+  /// Opens the column editor on the columns of the current report
+  pub fn column_editor_open(&mut self) {
+    self
+      .column_editor
+      .open(&self.task_report_table.columns, &self.task_report_table.labels, &self.config.uda);
+    self.column_editor.original.clone_from(&self.task_report_table.custom_columns);
+    self.previous_mode = Some(self.mode.clone());
+    self.mode = Mode::Tasks(Action::ColumnEditor);
+  }
+
+  /// Shows the columns being edited in the report, for this session only
+  fn column_editor_preview(&mut self) {
+    let columns = (self.column_editor.columns.clone(), self.column_editor.labels.clone());
+    self.task_report_table.columns.clone_from(&columns.0);
+    self.task_report_table.labels.clone_from(&columns.1);
+    self.task_report_table.custom_columns = Some(columns);
+    self.task_report_table.focused_column = None;
+  }
+
+  /// Closes the column editor, bringing back the columns it was opened with if `cancel` is set
+  fn column_editor_close(&mut self, cancel: bool) -> Result<()> {
+    let original = self.column_editor.original.take();
+    if cancel {
+      self.task_report_table.custom_columns = original;
+      self.task_report_table.focused_column = None;
+      self
+        .task_report_table
+        .export_headers(Some(&self.task_show), &self.report, &self.task_exe)?;
+    }
+    self.mode = self.previous_mode.take().unwrap_or(Mode::Tasks(Action::Report));
+    Ok(())
+  }
+
+  /// `task config report.<name>.columns` and `labels` with the columns picked in the column editor
+  pub fn task_report_save_columns(&self) -> Option<Job> {
+    let columns = self.column_editor.columns.join(",");
+    let labels = self.column_editor.labels.join(",");
+    let mut job = Job::new(
+      "save columns",
+      format!("Cannot run `task config report.{}.columns {}`.", self.report, columns),
+    );
+    for (setting, value) in [("columns", columns), ("labels", labels)] {
+      let mut command = std::process::Command::new(&self.task_exe);
+      command
+        .arg("rc.confirmation=off")
+        .arg("config")
+        .arg(format!("report.{}.{}", self.report, setting))
+        .arg(value);
+      job = job.command(command);
+    }
+    Some(job.fails_with("Saving the columns failed."))
+  }
+
+  /// Changes the project of the previewed tasks, with one `task modify` for each new project
+  pub fn task_project_change(&mut self) -> Option<Job> {
+    let changes = self.project_picker.changes_by_project();
+    if changes.is_empty() {
+      return None;
+    }
+    let mut job = Job::new(
+      "project",
+      format!(
+        "Cannot run `task modify project:{}`.",
+        changes.keys().copied().collect::<Vec<&str>>().join(" ")
+      ),
+    );
+    for (to, uuids) in changes {
+      let mut command = std::process::Command::new(&self.task_exe);
+      command.arg("rc.bulk=0");
+      command.arg("rc.confirmation=off");
+      command.arg("rc.dependency.confirmation=off");
+      command.arg("rc.recurrence.confirmation=off");
+      for uuid in uuids {
+        command.arg(uuid.to_string());
+      }
+      command.arg("modify").arg(format!("project:{}", to));
+      job = job.command(command);
+    }
+    Some(job.fails_with("Modify failed."))
+  }
+
+  pub fn command_palette_open(&mut self) -> Result<()> {
+    self.refresh_pane(LazyPane::Contexts)?;
+    self.refresh_pane(LazyPane::Reports)?;
+    let reports: Vec<String> = self.reports.rows.iter().map(|r| r.name.clone()).collect();
+    let contexts: Vec<String> = self.contexts.rows.iter().map(|r| r.name.clone()).collect();
+    self.command_palette.update_data(&self.keyconfig, &reports, &contexts);
+    self.command_palette.search.clear();
+    self.command_palette.table_state.select(Some(0));
+    self.mode = Mode::Tasks(Action::CommandPalette);
+    Ok(())
+  }
+
+  async fn command_palette_run(&mut self, target: PaletteTarget) -> Result<()> {
+    self.mode = Mode::Tasks(Action::Report);
+    let selected = match target {
+      PaletteTarget::Command(command) => return self.run_command(command).await,
+      PaletteTarget::Report(name) => {
+        self.reports.search.clear();
+        let i = self.reports.rows.iter().position(|r| r.name == name);
+        self.reports.table_state.select(i);
+        let data = Self::task_show_output(&self.task_exe)?;
+        self.report_select(&data)
+      }
+      PaletteTarget::Context(name) => {
+        self.contexts.search.clear();
+        let i = self.contexts.rows.iter().position(|r| r.name == name);
+        self.contexts.table_state.select(i);
+        self.context_select()
+      }
+    };
+    match selected {
+      Ok(true) => self.update(true).await?,
+      Ok(false) => {}
+      Err(e) => {
+        self.error = Some(e.to_string());
+        self.mode = Mode::Tasks(Action::Error);
+      }
+    }
+    Ok(())
+  }
+
+  /// Runs a command from the registry, as bound to a key or picked in the command palette.
+  pub async fn run_command(&mut self, command: Command) -> Result<()> {
+    // A folded group has no task under the cursor to act on, unless tasks are marked
+    let on_folded_group = matches!(self.task_table_state.mode(), TableMode::SingleSelection)
+      && !self.tasks.is_empty()
+      && self.task_groups.is_hidden(self.current_selection);
+    if on_folded_group
+      && matches!(
+        command,
+        Command::Done
+          | Command::Delete
+          | Command::StartStop
+          | Command::Pomodoro
+          | Command::QuickTag
+          | Command::Edit
+          | Command::Duplicate
+          | Command::Modify
+          | Command::MoveToProject
+          | Command::Annotate
+          | Command::PriorityH
+          | Command::PriorityM
+          | Command::PriorityL
+          | Command::PriorityN
+          | Command::AddDependency
+          | Command::RemoveDependency
+      )
+    {
+      return Ok(());
+    }
+    match command {
+      Command::Quit => {
+        self.should_quit = true;
+      }
+      Command::Select => {
+        self.task_table_state.multiple_selection();
+        self.toggle_mark();
+      }
+      Command::SelectAll => {
+        self.task_table_state.multiple_selection();
+        self.toggle_mark_all();
+      }
+      Command::Refresh => {
+        self.update(true).await?;
+      }
+      Command::GoToBottom => {
+        self.task_report_bottom();
+      }
+      Command::GoToTop => {
+        self.task_report_top();
+      }
+      Command::Down => {
+        self.task_report_next();
+      }
+      Command::Up => {
+        self.task_report_previous();
+      }
+      Command::PageDown => {
+        self.task_report_next_page();
+      }
+      Command::PageUp => {
+        self.task_report_previous_page();
+      }
+      Command::ScrollDetailsDown => {
+        self.task_details_scroll_down();
+      }
+      Command::ScrollDetailsUp => {
+        self.task_details_scroll_up();
+      }
+      Command::Done => {
+        if self.config.uda_task_report_prompt_on_done {
+          self.mode = Mode::Tasks(Action::DonePrompt);
+          if self.task_current().is_none() {
+            self.mode = Mode::Tasks(Action::Report);
+          }
+        } else {
+          let job = self.task_done();
+          self.task_submit(job).await?;
+        }
+      }
+      Command::Delete => {
+        if self.config.uda_task_report_prompt_on_delete {
+          self.mode = Mode::Tasks(Action::DeletePrompt);
+          if self.task_current().is_none() {
+            self.mode = Mode::Tasks(Action::Report);
+          }
+        } else {
+          let job = self.task_delete();
+          self.task_submit(job).await?;
+        }
+      }
+      Command::StartStop => {
+        let job = self.task_start_stop();
+        self.task_submit(job).await?;
+      }
+      Command::StopAll => {
+        let job = self.task_stop_all();
+        self.task_submit(job).await?;
+      }
+      Command::Pomodoro => {
+        let job = self.task_pomodoro();
+        self.task_submit(job).await?;
+      }
+      Command::QuickTag => {
+        let job = self.task_quick_tag();
+        self.task_submit(job).await?;
+      }
+      Command::Edit => {
+        if self.config.uda_task_editor_native {
+          self.task_editor_open();
+        } else {
+          match self.task_edit().await {
+            Ok(_) => self.update(true).await?,
+            Err(e) => {
+              self.error = Some(e);
+              self.mode = Mode::Tasks(Action::Error);
             }
-          } else if input == self.keyconfig.shortcut8 {
-            match self.task_shortcut(8).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
+          }
+        }
+      }
+      Command::Duplicate => match self.task_duplicate() {
+        Ok(_) => self.update(true).await?,
+        Err(e) => {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      },
+      Command::Undo => {
+        if self.config.uda_task_report_prompt_on_undo {
+          self.mode = Mode::Tasks(Action::UndoPrompt);
+          if self.task_current().is_none() {
+            self.mode = Mode::Tasks(Action::Report);
+          }
+        } else {
+          match self.task_undo() {
+            Ok(_) => self.update(true).await?,
+            Err(e) => {
+              self.error = Some(e);
+              self.mode = Mode::Tasks(Action::Error);
             }
-          } else if input == self.keyconfig.shortcut9 {
-            match self.task_shortcut(9).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
+          }
+        }
+      }
+      Command::MoveToProject => {
+        let uuids = self.selected_task_uuids();
+        if !uuids.is_empty() {
+          self.project_picker_open(ProjectPickerTarget::Move(uuids), String::new())?;
+        }
+      }
+      Command::Modify => {
+        self.mode = Mode::Tasks(Action::Modify);
+        self.command_history.reset();
+        self.history_status = Some(format!(
+          "{} / {}",
+          self
+            .command_history
+            .history_index()
+            .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
+            .saturating_add(1),
+          self.command_history.history_len()
+        ));
+        self.update_completion_list();
+        match self.task_table_state.mode() {
+          TableMode::SingleSelection => match self.task_current() {
+            Some(t) => {
+              let mut s = format!("{} ", Self::escape(t.description()));
+              if self.config.uda_prefill_task_metadata {
+                if t.tags().is_some() {
+                  let virtual_tags = self.task_report_table.virtual_tags.clone();
+                  for tag in t.tags().unwrap() {
+                    if !virtual_tags.contains(tag) {
+                      s = format!("{}+{} ", s, tag);
+                    }
+                  }
+                }
+                if t.project().is_some() {
+                  s = format!("{}project:{} ", s, t.project().unwrap());
+                }
+                if t.priority().is_some() {
+                  s = format!("{}priority:{} ", s, t.priority().unwrap());
+                }
+                if t.due().is_some() {
+                  let date = t.due().unwrap();
+                  s = format!("{}due:{} ", s, get_formatted_datetime(date));
+                }
               }
+              self.modify.update(&s, s.as_str().len(), &mut self.changes);
             }
-          } else if input == self.keyconfig.zoom {
-            self.task_report_info_show = !self.task_report_info_show;
-          } else if input == self.keyconfig.transpose {
-            self.toggle_task_info_location();
-          } else if input == self.keyconfig.context_menu {
-            self.contexts.search.clear();
-            // Pre-select the active context.
-            let active_pos = self
-              .contexts
-              .filtered_indices()
-              .iter()
-              .position(|&ri| self.contexts.rows[ri].active == "yes")
-              .unwrap_or(0);
-            self.contexts.table_state.select(Some(active_pos));
-            self.mode = Mode::Tasks(Action::ContextMenu);
-          } else if input == self.keyconfig.report_menu {
-            self.reports.search.clear();
-            // Pre-select the active report.
-            let active_pos = self
-              .reports
-              .filtered_indices()
-              .iter()
-              .position(|&ri| self.reports.rows[ri].active == "yes")
-              .unwrap_or(0);
-            self.reports.table_state.select(Some(active_pos));
-            self.mode = Mode::Tasks(Action::ReportMenu);
-          } else if input == self.keyconfig.previous_tab {
-            if self.config.uda_change_focus_rotate {
-              self.mode = Mode::Calendar;
-            }
-          } else if input == self.keyconfig.next_tab {
-            self.mode = Mode::Projects;
+            None => self.modify.update("", 0, &mut self.changes),
+          },
+          TableMode::MultipleSelection => self.modify.update("", 0, &mut self.changes),
+        }
+      }
+      Command::Shell => {
+        self.mode = Mode::Tasks(Action::Subprocess);
+      }
+      Command::Log => {
+        self.mode = Mode::Tasks(Action::Log);
+        self.command_history.reset();
+        self.history_status = Some(format!(
+          "{} / {}",
+          self
+            .command_history
+            .history_index()
+            .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
+            .saturating_add(1),
+          self.command_history.history_len()
+        ));
+        self.update_completion_list();
+      }
+      Command::Add => {
+        self.mode = Mode::Tasks(Action::Add);
+        self.command_history.reset();
+        self.history_status = Some(format!(
+          "{} / {}",
+          self
+            .command_history
+            .history_index()
+            .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
+            .saturating_add(1),
+          self.command_history.history_len()
+        ));
+        self.update_completion_list();
+      }
+      Command::Annotate => {
+        self.mode = Mode::Tasks(Action::Annotate);
+        self.command_history.reset();
+        self.history_status = Some(format!(
+          "{} / {}",
+          self
+            .command_history
+            .history_index()
+            .unwrap_or_else(|| self.command_history.history_len().saturating_sub(1))
+            .saturating_add(1),
+          self.command_history.history_len()
+        ));
+        self.update_completion_list();
+      }
+      Command::Help => {
+        self.mode = Mode::Tasks(Action::HelpPopup);
+      }
+      Command::Filter => {
+        self.mode = Mode::Tasks(Action::Filter);
+        self.filter_history.reset();
+        self.history_status = Some(format!(
+          "{} / {}",
+          self
+            .filter_history
+            .history_index()
+            .unwrap_or_else(|| self.filter_history.history_len().saturating_sub(1))
+            .saturating_add(1),
+          self.filter_history.history_len()
+        ));
+        self.update_completion_list();
+      }
+      Command::Jump => {
+        self.mode = Mode::Tasks(Action::Jump);
+      }
+      Command::Shortcut0
+      | Command::Shortcut1
+      | Command::Shortcut2
+      | Command::Shortcut3
+      | Command::Shortcut4
+      | Command::Shortcut5
+      | Command::Shortcut6
+      | Command::Shortcut7
+      | Command::Shortcut8
+      | Command::Shortcut9 => {
+        let shortcut = command.shortcut().unwrap_or_default();
+        match self.task_shortcut(shortcut).await {
+          Ok(_) => self.update(true).await?,
+          Err(e) => {
+            self.update(true).await?;
+            self.error = Some(e);
+            self.mode = Mode::Tasks(Action::Error);
+          }
+        }
+      }
+      Command::PriorityH => {
+        let job = self.task_priority("H");
+        self.task_submit(job).await?;
+      }
+      Command::PriorityM => {
+        let job = self.task_priority("M");
+        self.task_submit(job).await?;
+      }
+      Command::PriorityL => {
+        let job = self.task_priority("L");
+        self.task_submit(job).await?;
+      }
+      Command::PriorityN => {
+        let job = self.task_priority("");
+        self.task_submit(job).await?;
+      }
+      Command::Zoom => {
+        self.task_report_info_show = !self.task_report_info_show;
+      }
+      Command::Transpose => {
+        self.toggle_task_info_location();
+      }
+      Command::Obfuscate => self.toggle_obfuscate(),
+      Command::Fold => self.task_report_fold(),
+      Command::EditColumns => self.column_editor_open(),
+      Command::ContextMenu => {
+        self.refresh_pane(LazyPane::Contexts)?;
+        self.contexts.search.clear();
+        // Pre-select the active context.
+        let active_pos = self
+          .contexts
+          .filtered_indices()
+          .iter()
+          .position(|&ri| self.contexts.rows[ri].active == "yes")
+          .unwrap_or(0);
+        self.contexts.table_state.select(Some(active_pos));
+        self.mode = Mode::Tasks(Action::ContextMenu);
+      }
+      Command::ReportMenu => {
+        self.refresh_pane(LazyPane::Reports)?;
+        self.reports.search.clear();
+        // Pre-select the active report.
+        let active_pos = self
+          .reports
+          .filtered_indices()
+          .iter()
+          .position(|&ri| self.reports.rows[ri].active == "yes")
+          .unwrap_or(0);
+        self.reports.table_state.select(Some(active_pos));
+        self.mode = Mode::Tasks(Action::ReportMenu);
+      }
+      Command::PreviousTab => self.previous_tab(),
+      Command::NextTab => self.next_tab(),
+      Command::ColumnLeft | Command::ColumnRight => self.task_report_table.move_focus(command == Command::ColumnRight),
+      Command::Sort => self.task_report_sort(),
+      Command::SaveSort => match self.task_report_save_sort() {
+        Ok(job) => self.task_submit(job).await?,
+        Err(e) => {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      },
+      // Only bound in the Projects, Board, Calendar, Agenda and Timesheet panes and the column editor
+      Command::MoveLeft
+      | Command::MoveRight
+      | Command::PreviousDay
+      | Command::NextDay
+      | Command::PreviousYear
+      | Command::NextYear
+      | Command::SetDue
+      | Command::SetScheduled
+      | Command::Heatmap
+      | Command::WeekView
+      | Command::PreviousPeriod
+      | Command::NextPeriod
+      | Command::RenameProject
+      | Command::MergeProject
+      | Command::AddColumn
+      | Command::RemoveColumn
+      | Command::MoveColumnDown
+      | Command::MoveColumnUp
+      | Command::PreviousFormat
+      | Command::NextFormat
+      | Command::RelabelColumn
+      | Command::SaveColumns => {}
+      Command::AddDependency | Command::RemoveDependency => match self.task_dependency(command == Command::AddDependency) {
+        Ok(job) => self.task_submit(job).await?,
+        Err(e) => {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      },
+      Command::Palette => {
+        self.command_palette_open()?;
+      }
+    }
+    Ok(())
+  }
+
+  async fn handle_input_by_task_mode(&mut self, input: KeyCode) -> Result<()> {
+    if let Mode::Tasks(task_mode) = &self.mode {
+      match task_mode {
+        Action::Report => {
+          if input == KeyCode::Esc && self.pending_keys.is_empty() {
+            self.marked.clear();
+          } else if let Some(command) = self.resolve_keys(KeyScope::Tasks, input) {
+            self.run_command(command).await?;
           }
         }
         Action::ContextMenu => {
@@ -3595,6 +5927,237 @@ impl TaskwarriorTui {
             _ => {}
           }
         }
+        Action::Edit => match input {
+          KeyCode::Esc => {
+            if self.show_completion_pane {
+              self.show_completion_pane = false;
+              self.completion_list.unselect();
+            } else {
+              self.task_editor = None;
+              self.mode = Mode::Tasks(Action::Report);
+            }
+          }
+          KeyCode::Char('\n') => {
+            if self.show_completion_pane {
+              self.show_completion_pane = false;
+              if let Some((_, (r, _, o, _, _))) = self.completion_list.selected()
+                && let Some(editor) = self.task_editor.as_mut()
+              {
+                Self::apply_completion_to_buffer(&mut editor.current_mut().buffer, &r, &o, &mut self.changes);
+              }
+              self.completion_list.unselect();
+            } else {
+              match self.task_editor_save() {
+                Ok(changed) => {
+                  self.task_editor = None;
+                  self.mode = Mode::Tasks(Action::Report);
+                  if changed {
+                    self.update(true).await?;
+                  }
+                }
+                Err(e) => {
+                  self.error = Some(e);
+                  self.previous_mode = Some(self.mode.clone());
+                  self.mode = Mode::Tasks(Action::Error);
+                }
+              }
+            }
+          }
+          KeyCode::Tab | KeyCode::Ctrl('n') => {
+            self.update_input_for_completion();
+            if !self.completion_list.is_empty() {
+              let candidates = self.completion_list.candidates();
+              if candidates.len() == 1 {
+                let (r, _, o, _, _) = candidates.into_iter().next().unwrap();
+                if let Some(editor) = self.task_editor.as_mut() {
+                  Self::apply_completion_to_buffer(&mut editor.current_mut().buffer, &r, &o, &mut self.changes);
+                }
+                self.show_completion_pane = false;
+                self.completion_list.unselect();
+                self.update_input_for_completion();
+              } else {
+                self.show_completion_pane = true;
+                self.completion_list.next();
+              }
+            }
+          }
+          KeyCode::BackTab | KeyCode::Ctrl('p') => {
+            if self.show_completion_pane && !self.completion_list.is_empty() {
+              self.completion_list.previous();
+            }
+          }
+          KeyCode::Up | KeyCode::Down if self.show_completion_pane && !self.completion_list.is_empty() => {
+            if input == KeyCode::Up {
+              self.completion_list.previous();
+            } else {
+              self.completion_list.next();
+            }
+          }
+          KeyCode::Up | KeyCode::Down => {
+            if let Some(editor) = self.task_editor.as_mut() {
+              if input == KeyCode::Up {
+                editor.previous();
+              } else {
+                editor.next();
+              }
+            }
+            self.completion_list.unselect();
+            self.update_input_for_completion();
+          }
+          _ => {
+            if let Some(editor) = self.task_editor.as_mut() {
+              handle_movement(&mut editor.current_mut().buffer, input, &mut self.changes);
+            }
+            self.update_input_for_completion();
+          }
+        },
+        Action::CommandPalette => match input {
+          // Esc: clear search first; if already empty, close the palette.
+          KeyCode::Esc => {
+            if self.command_palette.search.is_empty() {
+              self.mode = Mode::Tasks(Action::Report);
+            } else {
+              self.command_palette.search.clear();
+              self.command_palette.table_state.select(Some(0));
+            }
+          }
+          KeyCode::Backspace | KeyCode::Ctrl('h') => {
+            self.command_palette.search.pop();
+            self.command_palette.table_state.select(Some(0));
+          }
+          KeyCode::Char('\n') => {
+            if let Some(entry) = self.command_palette.selected() {
+              let target = entry.target.clone();
+              self.command_palette_run(target).await?;
+            }
+          }
+          KeyCode::Char(c) => {
+            self.command_palette.search.push(c);
+            self.command_palette.table_state.select(Some(0));
+          }
+          KeyCode::Down | KeyCode::Ctrl('n') | KeyCode::Up | KeyCode::Ctrl('p') => {
+            let n = self.command_palette.filtered_indices().len();
+            if n > 0 {
+              let i = self.command_palette.table_state.current_selection().unwrap_or(0);
+              let i = if input == KeyCode::Down || input == KeyCode::Ctrl('n') {
+                (i + 1) % n
+              } else {
+                (i + n - 1) % n
+              };
+              self.command_palette.table_state.select(Some(i));
+            }
+          }
+          _ => {}
+        },
+        Action::ProjectPicker => match input {
+          KeyCode::Esc => {
+            self.mode = self.previous_mode.take().unwrap_or(Mode::Tasks(Action::Report));
+          }
+          KeyCode::Backspace | KeyCode::Ctrl('h') => {
+            self.project_picker.search.pop();
+            self.project_picker.table_state.select(Some(0));
+          }
+          KeyCode::Char('\n') => {
+            if let Some(to) = self.project_picker.selected() {
+              self.project_picker.update_preview(&to, &self.all_tasks);
+              if self.project_picker.preview.is_empty() {
+                self.error = Some(format!("No task would move to project `{}`", to));
+                self.mode = Mode::Tasks(Action::Error);
+              } else {
+                self.mode = Mode::Tasks(Action::ProjectPreview);
+              }
+            }
+          }
+          KeyCode::Char(c) => {
+            self.project_picker.search.push(c);
+            self.project_picker.table_state.select(Some(0));
+          }
+          KeyCode::Down | KeyCode::Ctrl('n') | KeyCode::Up | KeyCode::Ctrl('p') => {
+            let n = self.project_picker.choices().len();
+            if n > 0 {
+              let i = self.project_picker.table_state.current_selection().unwrap_or(0);
+              let i = if input == KeyCode::Down || input == KeyCode::Ctrl('n') {
+                (i + 1) % n
+              } else {
+                (i + n - 1) % n
+              };
+              self.project_picker.table_state.select(Some(i));
+            }
+          }
+          _ => {}
+        },
+        Action::ColumnEditor => match self.column_editor.mode {
+          ColumnEditorMode::Columns if input == KeyCode::Esc && self.pending_keys.is_empty() => self.column_editor_close(true)?,
+          ColumnEditorMode::Columns if input == KeyCode::Char('\n') && self.pending_keys.is_empty() => self.column_editor_close(false)?,
+          ColumnEditorMode::Columns => match self.resolve_keys(KeyScope::ColumnEditor, input) {
+            Some(Command::SaveColumns) => {
+              let job = self.task_report_save_columns();
+              self.column_editor_close(false)?;
+              self.task_submit(job).await?;
+            }
+            Some(command @ (Command::Down | Command::Up)) => self.column_editor.next(command == Command::Down),
+            Some(command @ (Command::MoveColumnDown | Command::MoveColumnUp)) => {
+              self.column_editor.move_selected(command == Command::MoveColumnDown);
+              self.column_editor_preview();
+            }
+            Some(command @ (Command::PreviousFormat | Command::NextFormat)) => {
+              self.column_editor.cycle_format(command == Command::NextFormat);
+              self.column_editor_preview();
+            }
+            Some(Command::RemoveColumn) => {
+              self.column_editor.remove_selected();
+              self.column_editor_preview();
+            }
+            Some(Command::AddColumn) => self.column_editor.mode = ColumnEditorMode::Add,
+            Some(Command::RelabelColumn) => {
+              if let Some(i) = self.column_editor.selected() {
+                self.column_editor.label.clone_from(&self.column_editor.labels[i]);
+                self.column_editor.mode = ColumnEditorMode::Rename;
+              }
+            }
+            _ => {}
+          },
+          ColumnEditorMode::Add if input == KeyCode::Esc && self.pending_keys.is_empty() => {
+            self.column_editor.mode = ColumnEditorMode::Columns;
+          }
+          ColumnEditorMode::Add if input == KeyCode::Char('\n') && self.pending_keys.is_empty() => {
+            self.column_editor.add_picked();
+            self.column_editor.mode = ColumnEditorMode::Columns;
+            self.column_editor_preview();
+          }
+          ColumnEditorMode::Add => {
+            if let Some(command @ (Command::Down | Command::Up)) = self.resolve_keys(KeyScope::ColumnEditor, input) {
+              let n = self.column_editor.available.len();
+              if n > 0 {
+                let i = self.column_editor.available_state.current_selection().unwrap_or(0);
+                let i = if command == Command::Down { (i + 1) % n } else { (i + n - 1) % n };
+                self.column_editor.available_state.select(Some(i));
+              }
+            }
+          }
+          ColumnEditorMode::Rename => match input {
+            KeyCode::Esc => self.column_editor.mode = ColumnEditorMode::Columns,
+            KeyCode::Char('\n') => {
+              self.column_editor.rename_selected();
+              self.column_editor.mode = ColumnEditorMode::Columns;
+              self.column_editor_preview();
+            }
+            KeyCode::Backspace | KeyCode::Ctrl('h') => {
+              self.column_editor.label.pop();
+            }
+            KeyCode::Char(c) => self.column_editor.label.push(c),
+            _ => {}
+          },
+        },
+        Action::ProjectPreview => {
+          if input == KeyCode::Char('\n') {
+            self.mode = self.previous_mode.take().unwrap_or(Mode::Tasks(Action::Report));
+            let job = self.task_project_change();
+            self.task_submit(job).await?;
+          } else if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::ProjectPicker);
+          }
+        }
         Action::HelpPopup => {
           if input == self.keyconfig.quit || input == KeyCode::Esc {
             self.mode = Mode::Tasks(Action::Report);
@@ -3630,11 +6193,11 @@ impl TaskwarriorTui {
               self.mode = Mode::Tasks(Action::Error);
             } else {
               match self.task_modify() {
-                Ok(_) => {
+                Ok(job) => {
                   self.mode = Mode::Tasks(Action::Report);
                   self.command_history.add(self.modify.as_str());
                   self.modify.update("", 0, &mut self.changes);
-                  self.update(true).await?;
+                  self.task_submit(job).await?;
                 }
                 Err(e) => {
                   self.error = Some(e);
@@ -3709,6 +6272,14 @@ impl TaskwarriorTui {
               ));
             }
           }
+          key if key == self.keyconfig.prompt_undo => {
+            self.changes.undo(&mut self.modify);
+            self.update_input_for_completion();
+          }
+          key if key == self.keyconfig.prompt_redo => {
+            self.changes.redo(&mut self.modify);
+            self.update_input_for_completion();
+          }
           _ => {
             self.command_history.reset();
             handle_movement(&mut self.modify, input, &mut self.changes);
@@ -3843,6 +6414,14 @@ impl TaskwarriorTui {
               ));
             }
           }
+          key if key == self.keyconfig.prompt_undo => {
+            self.changes.undo(&mut self.command);
+            self.update_input_for_completion();
+          }
+          key if key == self.keyconfig.prompt_redo => {
+            self.changes.redo(&mut self.command);
+            self.update_input_for_completion();
+          }
           _ => {
             self.command_history.reset();
             handle_movement(&mut self.command, input, &mut self.changes);
@@ -3872,12 +6451,12 @@ impl TaskwarriorTui {
               self.mode = Mode::Tasks(Action::Error);
             } else {
               match self.task_annotate() {
-                Ok(_) => {
+                Ok(job) => {
                   self.mode = Mode::Tasks(Action::Report);
                   self.command_history.add(self.command.as_str());
                   self.reset_command();
                   self.history_status = None;
-                  self.update(true).await?;
+                  self.task_submit(job).await?;
                 }
                 Err(e) => {
                   self.error = Some(e);
@@ -3952,6 +6531,14 @@ impl TaskwarriorTui {
             }
           }
 
+          key if key == self.keyconfig.prompt_undo => {
+            self.changes.undo(&mut self.command);
+            self.update_input_for_completion();
+          }
+          key if key == self.keyconfig.prompt_redo => {
+            self.changes.redo(&mut self.command);
+            self.update_input_for_completion();
+          }
           _ => {
             self.command_history.reset();
             handle_movement(&mut self.command, input, &mut self.changes);
@@ -4087,6 +6674,14 @@ impl TaskwarriorTui {
               ));
             }
           }
+          key if key == self.keyconfig.prompt_undo => {
+            self.changes.undo(&mut self.command);
+            self.update_input_for_completion();
+          }
+          key if key == self.keyconfig.prompt_redo => {
+            self.changes.redo(&mut self.command);
+            self.update_input_for_completion();
+          }
           _ => {
             self.command_history.reset();
             handle_movement(&mut self.command, input, &mut self.changes);
@@ -4208,6 +6803,16 @@ impl TaskwarriorTui {
             self.update_input_for_completion();
             self.dirty = true;
           }
+          key if key == self.keyconfig.prompt_undo => {
+            self.changes.undo(&mut self.filter);
+            self.update_input_for_completion();
+            self.dirty = true;
+          }
+          key if key == self.keyconfig.prompt_redo => {
+            self.changes.redo(&mut self.filter);
+            self.update_input_for_completion();
+            self.dirty = true;
+          }
           _ => {
             handle_movement(&mut self.filter, input, &mut self.changes);
             self.update_input_for_completion();
@@ -4220,16 +6825,9 @@ impl TaskwarriorTui {
               self.previous_mode = Some(self.mode.clone());
               self.mode = Mode::Tasks(Action::Error);
             } else {
-              match self.task_done() {
-                Ok(_) => {
-                  self.mode = Mode::Tasks(Action::Report);
-                  self.update(true).await?;
-                }
-                Err(e) => {
-                  self.error = Some(e);
-                  self.mode = Mode::Tasks(Action::Error);
-                }
-              }
+              self.mode = Mode::Tasks(Action::Report);
+              let job = self.task_done();
+              self.task_submit(job).await?;
             }
           } else if input == self.keyconfig.quit || input == KeyCode::Esc {
             self.mode = Mode::Tasks(Action::Report);
@@ -4243,16 +6841,9 @@ impl TaskwarriorTui {
               self.previous_mode = Some(self.mode.clone());
               self.mode = Mode::Tasks(Action::Error);
             } else {
-              match self.task_delete() {
-                Ok(_) => {
-                  self.mode = Mode::Tasks(Action::Report);
-                  self.update(true).await?;
-                }
-                Err(e) => {
-                  self.error = Some(e);
-                  self.mode = Mode::Tasks(Action::Error);
-                }
-              }
+              self.mode = Mode::Tasks(Action::Report);
+              let job = self.task_delete();
+              self.task_submit(job).await?;
             }
           } else if input == self.keyconfig.quit || input == KeyCode::Esc {
             self.mode = Mode::Tasks(Action::Report);
@@ -4283,6 +6874,20 @@ impl TaskwarriorTui {
             handle_movement(&mut self.command, input, &mut self.changes);
           }
         }
+        Action::PomodoroBreak => {
+          if input == self.keyconfig.pomodoro || input == KeyCode::Char('\n') || input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = self.previous_mode.take().unwrap_or(Mode::Tasks(Action::Report));
+            let pomodoro = self.pomodoro.take();
+            // Going on starts the next pomodoro on the same task, even if it left the report
+            let task = pomodoro.and_then(|p| self.all_tasks.iter().chain(&self.tasks).find(|t| *t.uuid() == p.uuid).cloned());
+            if (input == self.keyconfig.pomodoro || input == KeyCode::Char('\n'))
+              && let Some(task) = task
+            {
+              let job = self.pomodoro_start(&task);
+              self.task_submit(job).await?;
+            }
+          }
+        }
         Action::Error => {
           // since filter live updates, don't reset error status
           // for other actions, resetting error to None is required otherwise user cannot
@@ -4433,6 +7038,19 @@ impl TaskwarriorTui {
         let input = self.filter.as_str()[i..self.filter.pos()].to_string();
         self.completion_list.input(input, "".to_string());
       }
+      Mode::Tasks(Action::Edit) => {
+        if let Some(editor) = &self.task_editor {
+          let field = editor.current();
+          let i = get_start_word_under_cursor(field.buffer.as_str(), field.buffer.pos());
+          let input = field.buffer.as_str()[i..field.buffer.pos()].to_string();
+          let existing = if field.kind == FieldKind::Tags {
+            field.buffer.as_str().to_string()
+          } else {
+            String::new()
+          };
+          self.completion_list.input_for_context(&field.attribute, input, existing);
+        }
+      }
       _ => {}
     }
   }
@@ -4579,6 +7197,25 @@ mod tests {
     assert_eq!(centered_rect(50, 50, Rect::new(0, 0, 100, 100)), Rect::new(25, 25, 50, 50));
   }
 
+  #[test]
+  fn test_taskrc_files_follow_includes() {
+    let dir = std::env::temp_dir().join(format!("taskwarrior-tui-taskrc-{}", std::process::id()));
+    fs::create_dir_all(dir.join("rc")).unwrap();
+    fs::write(
+      dir.join("taskrc"),
+      "include rc/reports.rc\ninclude solarized-dark-256.theme\nincluded=no\n",
+    )
+    .unwrap();
+    fs::write(
+      dir.join("rc/reports.rc"),
+      format!("include {}\nreport.next.sort=due+\n", dir.join("taskrc").display()),
+    )
+    .unwrap();
+    let files = taskrc_files(dir.join("taskrc"));
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(files, vec![dir.join("taskrc"), dir.join("rc/reports.rc")]);
+  }
+
   #[tokio::test]
   async fn test_task_info_location_auto_and_override() {
     let mut app = TaskwarriorTui::new("next", false).await.unwrap();
@@ -4631,6 +7268,78 @@ mod tests {
     );
   }
 
+  #[tokio::test]
+  async fn test_folded_group_header_runs_no_job() {
+    let mut app = TaskwarriorTui::new("next", false).await.unwrap();
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","project":"home"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","project":"work"}
+    ]"#;
+    app.tasks = task_hookrs::import::import(data.as_bytes()).unwrap();
+    app.marked.clear();
+    app.task_table_state.single_selection();
+    app.task_groups = TaskGroups::new(Some(GroupBy::Project));
+    app.group_tasks();
+    app.current_selection = 0;
+    app.task_report_fold();
+
+    assert!(app.selected_task_uuids().is_empty());
+    assert!(app.task_done().is_none());
+    assert!(app.task_delete().is_none());
+    assert!(app.task_priority("H").is_none());
+    assert!(app.task_start_stop().is_none());
+    assert!(app.task_quick_tag().is_none());
+    assert!(matches!(app.task_modify(), Ok(None)));
+    assert!(matches!(app.task_annotate(), Ok(None)));
+    assert!(app.task_set_date("due").is_none());
+  }
+
+  #[tokio::test]
+  async fn test_mark_range_skips_folded_groups() {
+    let mut app = TaskwarriorTui::new("next", false).await.unwrap();
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","project":"home"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","project":"school"},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"pending","project":"school"},
+      {"id":4,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"20260101T000000Z","status":"pending","project":"work"}
+    ]"#;
+    app.tasks = task_hookrs::import::import(data.as_bytes()).unwrap();
+    app.marked.clear();
+    app.task_groups = TaskGroups::new(Some(GroupBy::Project));
+    app.group_tasks();
+    app.current_selection = 1;
+    app.task_report_fold();
+
+    app.current_selection = 0;
+    app.task_report_mark_range(3);
+    let marked: HashSet<Uuid> = [&app.tasks[0], &app.tasks[3]].iter().map(|t| *t.uuid()).collect();
+    assert_eq!(app.marked, marked);
+  }
+
+  #[tokio::test]
+  async fn test_removing_grouped_task_redraws_report() {
+    let mut app = TaskwarriorTui::new("next", false).await.unwrap();
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","project":"home"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","project":"home"},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"pending","project":"work"}
+    ]"#;
+    app.tasks = task_hookrs::import::import(data.as_bytes()).unwrap();
+    app.config.uda_background_jobs = true;
+    app.task_groups = TaskGroups::new(Some(GroupBy::Project));
+    app.group_tasks();
+    app.current_selection = 2;
+
+    let uuid = *app.tasks[1].uuid();
+    app.task_submit(Some(Job::new("done", "").removes(&[uuid]))).await.unwrap();
+    assert_eq!(app.tasks.len(), 2);
+    assert_eq!(app.task_groups.groups.iter().map(|g| g.len).collect::<Vec<_>>(), vec![1, 1]);
+    assert_eq!(app.current_selection, 1);
+
+    let mut terminal = Terminal::new(TestBackend::new(50, 15)).unwrap();
+    terminal.draw(|f| app.draw(f)).unwrap();
+  }
+
   fn setup() {
     use std::process::Stdio;
     let mut f = File::open(get_taskdata_path().parent().unwrap().join("export.json")).unwrap();
@@ -4838,7 +7547,7 @@ mod tests {
     );
 
     test_draw_task_report_with_extended_modify_command().await;
-    test_draw_timesheet_styles_week_headers_with_actions().await;
+    test_draw_timesheet_styles_rows_by_action().await;
     test_update_timesheet_uses_current_week_events().await;
     // test_draw_task_report();
     test_task_tags().await;
@@ -4910,7 +7619,7 @@ mod tests {
       app.current_selection_uuid = None;
     }
 
-    app.task_quick_tag().unwrap();
+    app.task_quick_tag().unwrap().run().unwrap();
     app.update(true).await.unwrap();
 
     let task = app.task_by_id(11).unwrap();
@@ -4922,7 +7631,7 @@ mod tests {
       assert!(task.tags().unwrap().contains(&tag));
     }
 
-    app.task_quick_tag().unwrap();
+    app.task_quick_tag().unwrap().run().unwrap();
     app.update(true).await.unwrap();
 
     let task = app.task_by_id(11).unwrap();
@@ -6124,10 +8833,10 @@ mod tests {
   async fn test_draw_task_report() {
     let mut expected = Buffer::with_lines(vec![
       "╭Task|Calendar───────────────────────────────────╮",
-      "│  ID Age Deps P Projec Tag     Due Descrip Urg  │",
+      "│  ID Age Deps P Project Tag     Due Descript Urg│",
       "│                                                │",
-      "│• 27 0s       U                    new ta… 15.00│",
-      "│  28 0s       U        none        new ta… 15.00│",
+      "│• 27 0s       U                     new tas… 15 │",
+      "│  28 0s       U         none        new tas… 15 │",
       "╰────────────────────────────────────────────────╯",
       "╭Task 27─────────────────────────────────────────╮",
       "│                                                │",
@@ -6154,11 +8863,11 @@ mod tests {
       6..=8,   // Age
       10..=13, // Deps
       15..=15, // P
-      17..=22, // Projec
-      24..=30, // Tag
-      32..=34, // Due
-      36..=42, // Descr
-      44..=48, // Urg
+      17..=23, // Project
+      25..=31, // Tag
+      33..=35, // Due
+      37..=44, // Descr
+      46..=48, // Urg
     ] {
       for i in r.clone() {
         expected[(i, 1)].set_style(Style::default().add_modifier(Modifier::UNDERLINED));
@@ -6376,42 +9085,53 @@ mod tests {
     terminal.backend().assert_buffer(&expected);
   }
 
-  async fn test_draw_timesheet_styles_week_headers_with_actions() {
+  async fn test_draw_timesheet_styles_rows_by_action() {
     let mut app = TaskwarriorTui::new("next", false).await.unwrap();
 
-    let header_style = Style::default().add_modifier(Modifier::BOLD);
     let active_style = Style::default().fg(Color::Indexed(2));
     let completed_style = Style::default().fg(Color::Indexed(1));
     let alternate_style = Style::default().bg(Color::Indexed(4));
     let footnote_style = Style::default().fg(Color::Indexed(3));
 
-    app.config.color.insert("color.label".to_string(), header_style);
     app.config.color.insert("color.active".to_string(), active_style);
     app.config.color.insert("color.completed".to_string(), completed_style);
     app.config.color.insert("color.alternate".to_string(), alternate_style);
     app.config.color.insert("color.footnote".to_string(), footnote_style);
-
-    app.timesheet_data = [
-      "Wk  Date       Day ID       Action    Project Due Task",
-      "--- ---------- --- -------- --------- ------- --- ----------------",
-      "W14 2026-04-05 Sun 1        Started               first-week-started",
-      "                   first-week continuation",
-      "",
-      "W15 2026-04-12 Sun 2        Started               second-week-started",
-      "                   fa6b0fe3 Completed             second-week-completed",
-      "2 completed, 2 started.",
-    ]
-    .join("\n");
-    app.timesheet_line_count = app.timesheet_data.lines().count() as u16;
-
-    let lines = app.styled_timesheet_lines();
-    assert_eq!(lines[2].style.fg, active_style.fg);
-    assert_eq!(lines[3].style.fg, active_style.fg);
-    assert_eq!(lines[5].style.fg, active_style.fg);
-    assert_eq!(lines[5].style.bg, alternate_style.bg);
-    assert_eq!(lines[6].style.fg, completed_style.fg);
-    assert_eq!(lines[6].style.bg, alternate_style.bg);
-    assert_eq!(lines[7].style.fg, footnote_style.fg);
+    app.config.weekstart = true;
+
+    let data = r#"[
+      {"id":0,"uuid":"10000000-0000-0000-0000-000000000000","description":"first-day-completed","entry":"20260301T000000Z",
+       "status":"completed","project":"work","end":"20260309T120000Z"},
+      {"id":0,"uuid":"20000000-0000-0000-0000-000000000000","description":"second-day-started","entry":"20260301T000000Z",
+       "status":"completed","start":"20260310T120000Z","end":"20260301T000000Z"}
+    ]"#;
+    app.all_tasks = import(data.as_bytes()).unwrap();
+    app.timesheet.day = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+    app.timesheet.update_data(&app.all_tasks, true, None);
+    app.timesheet.current_selection = 1;
+
+    let backend = TestBackend::new(80, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal.draw(|f| app.draw_timesheet(f, f.area())).unwrap();
+    let buffer = terminal.backend().buffer();
+    let view = buffer_view(buffer);
+
+    assert!(view.contains("Week 11 (2026-03-09 to 2026-03-15)"), "{view}");
+    let row = |text: &str| {
+      view
+        .lines()
+        .position(|l| l.contains(text))
+        .unwrap_or_else(|| panic!("{text} not in {view}")) as u16
+    };
+    let completed = row("first-day-completed");
+    let started = row("second-day-started");
+    assert_eq!(buffer[(20, completed)].fg, completed_style.fg.unwrap());
+    assert_eq!(buffer[(20, started)].fg, active_style.fg.unwrap());
+    // The second day has the alternate background
+    assert_eq!(buffer[(20, started)].bg, alternate_style.bg.unwrap());
+    assert!(view.contains("(none)"), "{view}");
+    let total = row("Total ");
+    assert_eq!(buffer[(0, total)].fg, footnote_style.fg.unwrap());
   }
 
   async fn test_update_timesheet_uses_current_week_events() {
@@ -6504,20 +9224,21 @@ mod tests {
     app.terminal_height = 10;
     app.update_timesheet().unwrap();
 
+    let actions: Vec<(&str, TimesheetAction, Option<u64>)> = app.timesheet.rows.iter().map(|r| (r.description.as_str(), r.action, r.id)).collect();
     assert!(
-      app.timesheet_data.contains("Wk  Date       Day ID       Action"),
-      "{}",
-      app.timesheet_data
+      actions.contains(&(started_description, TimesheetAction::Started, Some(started_task_id))),
+      "{:?}",
+      actions
+    );
+    assert!(
+      actions
+        .iter()
+        .any(|(d, a, _)| *d == completed_description && *a == TimesheetAction::Completed),
+      "{:?}",
+      actions
     );
-    assert!(app.timesheet_data.contains(&started_task_id.to_string()), "{}", app.timesheet_data);
-    assert!(app.timesheet_data.contains("Started"), "{}", app.timesheet_data);
-    assert!(app.timesheet_data.contains(started_description), "{}", app.timesheet_data);
-    assert!(app.timesheet_data.contains("Completed"), "{}", app.timesheet_data);
-    assert!(app.timesheet_data.contains(completed_description), "{}", app.timesheet_data);
-    assert!(app.timesheet_line_count >= 3);
-    assert_eq!(app.timesheet_scroll, 0);
 
-    let backend = TestBackend::new(80, 10);
+    let backend = TestBackend::new(80, 14);
     let mut terminal = Terminal::new(backend).unwrap();
     terminal
       .draw(|f| {
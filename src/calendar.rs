@@ -1,7 +1,7 @@
 // Based on https://gist.github.com/diwic/5c20a283ca3a03752e1a27b0f3ebfa30
 // See https://old.reddit.com/r/rust/comments/4xneq5/the_calendar_example_challenge_ii_why_eddyb_all/
 
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 const COL_WIDTH: usize = 21;
 
@@ -27,6 +27,14 @@ pub struct Calendar<'a> {
   pub today_style: Style,
   pub start_on_monday: bool,
   pub title_background_color: Color,
+  /// The day under the cursor, if it is in a year that is drawn
+  pub selected: Option<NaiveDate>,
+  pub selected_style: Style,
+  /// Number of tasks on each day, drawn next to the day when the cells can be made wide enough
+  pub date_counts: HashMap<NaiveDate, usize>,
+  /// Draw the past year as one column per week, shaded by `date_counts`, instead of months
+  pub heatmap: bool,
+  pub heatmap_style: Style,
 }
 
 impl<'a> Default for Calendar<'a> {
@@ -43,6 +51,11 @@ impl<'a> Default for Calendar<'a> {
       today_style: Style::default(),
       start_on_monday: false,
       title_background_color: Color::Reset,
+      selected: None,
+      selected_style: Style::default().add_modifier(Modifier::REVERSED),
+      date_counts: HashMap::new(),
+      heatmap: false,
+      heatmap_style: Style::default().fg(Color::Green),
     }
   }
 }
@@ -90,6 +103,31 @@ impl<'a> Calendar<'a> {
     self.start_on_monday = start_on_monday;
     self
   }
+
+  pub fn selected(mut self, selected: NaiveDate) -> Self {
+    self.selected = Some(selected);
+    self
+  }
+
+  pub fn selected_style(mut self, selected_style: Style) -> Self {
+    self.selected_style = selected_style;
+    self
+  }
+
+  pub fn date_counts(mut self, date_counts: HashMap<NaiveDate, usize>) -> Self {
+    self.date_counts = date_counts;
+    self
+  }
+
+  pub fn heatmap(mut self, heatmap: bool) -> Self {
+    self.heatmap = heatmap;
+    self
+  }
+
+  pub fn heatmap_style(mut self, heatmap_style: Style) -> Self {
+    self.heatmap_style = heatmap_style;
+    self
+  }
 }
 
 impl Widget for Calendar<'_> {
@@ -106,6 +144,11 @@ impl Widget for Calendar<'_> {
       None => area,
     };
 
+    if self.heatmap {
+      self.render_heatmap(area, buf);
+      return;
+    }
+
     if area.height < 7 {
       return;
     }
@@ -138,10 +181,24 @@ impl Widget for Calendar<'_> {
       })
       .collect();
 
-    let mut start_m = 0_usize;
     if self.months_per_row > area.width as usize / 8 / 3 || self.months_per_row == 0 {
       self.months_per_row = area.width as usize / 8 / 3;
     }
+    // Skip rows of months at the start of the year when the selected month would not fit otherwise.
+    // A row takes at most 11 lines: title, weekdays, six weeks and the gap to the next row.
+    let mut start_m = 0_usize;
+    if let Some(selected) = self.selected.filter(|d| d.year() == year && self.months_per_row > 0) {
+      let visible_rows = (area.height.saturating_sub(3) as usize / 11).max(1);
+      let row = selected.month0() as usize / self.months_per_row;
+      start_m = (row + 1).saturating_sub(visible_rows) * self.months_per_row;
+    }
+    // Days take three columns, or six to fit a task count when that does not cost a month per row
+    let cell: u16 = if !self.date_counts.is_empty() && area.width as usize >= (6 * 7 + 1) * self.months_per_row {
+      6
+    } else {
+      3
+    };
+    let month_width = cell * 7;
     let mut y = area.y;
     y += 1;
 
@@ -156,7 +213,7 @@ impl Widget for Calendar<'_> {
       set_string(buf, x, y, &s, style);
     }
 
-    let start_x = (area.width - 3 * 7 * self.months_per_row as u16 - self.months_per_row as u16) / 2;
+    let start_x = (area.width - month_width * self.months_per_row as u16 - self.months_per_row as u16) / 2;
     y += 2;
     loop {
       let endm = std::cmp::min(start_m + self.months_per_row, 12);
@@ -166,7 +223,7 @@ impl Widget for Calendar<'_> {
           x += 1;
         }
         let m = d.0.month() as usize;
-        let s = format!("{:^20}", month_names[m - 1]);
+        let s = format!("{:^width$}", month_names[m - 1], width = month_width as usize - 1);
         let style = Style::default().bg(self.title_background_color);
         if m == today.month() as usize && self.year + new_year as i32 == today.year() {
           set_string(buf, x, y, &s, self.today_style);
@@ -180,13 +237,14 @@ impl Widget for Calendar<'_> {
       for d in days.iter_mut().take(endm).skip(start_m) {
         let m = d.0.month() as usize;
         let style = Style::default().bg(self.title_background_color);
-        let days_string = if self.start_on_monday {
-          "Mo Tu We Th Fr Sa Su"
+        let day_names = if self.start_on_monday {
+          ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
         } else {
-          "Su Mo Tu We Th Fr Sa"
+          ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"]
         };
-        set_string(buf, x, y, days_string, style.add_modifier(Modifier::UNDERLINED));
-        x += 21 + 1;
+        let days_string: String = day_names.iter().map(|n| format!("{:<width$}", n, width = cell as usize)).collect();
+        set_string(buf, x, y, days_string.trim_end(), style.add_modifier(Modifier::UNDERLINED));
+        x += month_width + 1;
       }
       y += 1;
       loop {
@@ -198,10 +256,11 @@ impl Widget for Calendar<'_> {
           }
           let d = &mut days[c + new_year * 12];
           for _ in 0..7 {
-            let s = if d.0.month() == d.1.month() {
+            let in_month = d.0.month() == d.1.month();
+            let s = if in_month {
               format!("{:>2}", d.1.day())
             } else {
-              "   ".to_string()
+              " ".repeat(cell as usize)
             };
             let mut style = Style::default();
             let index = self.date_style.iter().position(|(date, style)| d.1 == *date);
@@ -209,11 +268,16 @@ impl Widget for Calendar<'_> {
               style = self.date_style[i].1;
             }
             if d.1 == Local::now().date_naive() {
-              set_string(buf, x, y, &s, self.today_style);
-            } else {
-              set_string(buf, x, y, &s, style);
+              style = self.today_style;
             }
-            x += 3;
+            if self.selected == Some(d.1) && in_month {
+              style = style.patch(self.selected_style);
+            }
+            set_string(buf, x, y, &s, style);
+            if let Some(count) = self.date_counts.get(&d.1).filter(|_| cell > 3 && in_month) {
+              set_string(buf, x + 3, y, &count.min(&99).to_string(), style.add_modifier(Modifier::DIM));
+            }
+            x += cell;
             d.1 += Duration::days(1);
           }
           moredays |= d.0.month() == d.1.month() || d.1 < d.0;
@@ -254,6 +318,107 @@ impl Widget for Calendar<'_> {
   }
 }
 
+impl Calendar<'_> {
+  /// First day of the week `date` is in
+  fn week_start(&self, date: NaiveDate) -> NaiveDate {
+    let offset = if self.start_on_monday {
+      date.weekday().num_days_from_monday()
+    } else {
+      date.weekday().num_days_from_sunday()
+    };
+    date - Duration::days(i64::from(offset))
+  }
+
+  /// Weeks as columns and weekdays as rows, up to a year ending today or at the selected day if that is later.
+  /// When the selected day is older than the weeks that fit, it is drawn in the last column instead.
+  fn render_heatmap(&self, area: Rect, buf: &mut Buffer) {
+    const SHADES: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+    let label_width = 3;
+    let weeks = (area.width.saturating_sub(label_width) / 2).min(53);
+    if weeks == 0 || area.height < 11 {
+      return;
+    }
+    let set_string = |buf: &mut Buffer, x: u16, y: u16, s: &str, style: Style| {
+      if area.contains(Position { x, y }) {
+        buf.set_string(x, y, s, style);
+      }
+    };
+
+    let today = Local::now().date_naive();
+    let selected = self.selected.unwrap_or(today);
+    let span = Duration::weeks(i64::from(weeks) - 1);
+    let last = if self.week_start(selected) >= self.week_start(today) - span {
+      selected.max(today)
+    } else {
+      selected
+    };
+    let first = self.week_start(last) - span;
+    let in_range = |d: &NaiveDate| *d >= first && *d <= last;
+    let total: usize = self.date_counts.iter().filter(|(d, _)| in_range(d)).map(|(_, n)| n).sum();
+    let max = self.date_counts.iter().filter(|(d, _)| in_range(d)).map(|(_, n)| *n).max().unwrap_or(0);
+
+    let title = format!("{} completed from {} to {}", total, first.format("%Y-%m-%d"), last.format("%Y-%m-%d"));
+    let title = format!("{:^width$}", title, width = area.width as usize);
+    set_string(buf, area.x, area.y + 1, &title, Style::default().add_modifier(Modifier::UNDERLINED));
+
+    let day_names = if self.start_on_monday {
+      ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+    } else {
+      ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"]
+    };
+    let top = area.y + 4;
+    let title_style = Style::default().bg(self.title_background_color);
+    for (row, name) in day_names.iter().enumerate() {
+      set_string(buf, area.x, top + row as u16, name, title_style);
+    }
+
+    let month_names = Self::generate_month_names();
+    let mut label_end = area.x;
+    for week in 0..weeks {
+      let x = area.x + label_width + week * 2;
+      let start = first + Duration::weeks(i64::from(week));
+      // Label the column that holds the first of a month
+      if let Some(first_of_month) = (0..7).map(|i| start + Duration::days(i)).find(|d| d.day() == 1)
+        && x >= label_end
+      {
+        let label = &month_names[first_of_month.month0() as usize][..3];
+        set_string(buf, x, top - 1, label, title_style);
+        label_end = x + label.len() as u16 + 1;
+      }
+      for row in 0..7 {
+        let date = start + Duration::days(row);
+        if date > last.max(today) {
+          continue;
+        }
+        let count = self.date_counts.get(&date).copied().unwrap_or(0);
+        let (shade, mut style) = match count {
+          0 => (SHADES[0], Style::default().add_modifier(Modifier::DIM)),
+          n => (SHADES[n.saturating_mul(4).div_ceil(max).clamp(1, 4)], self.heatmap_style),
+        };
+        if date == today {
+          style = style.patch(self.today_style);
+        }
+        if self.selected == Some(date) {
+          style = style.patch(self.selected_style);
+        }
+        set_string(buf, x, top + row as u16, shade, style);
+      }
+    }
+
+    let legend_y = top + 8;
+    set_string(buf, area.x + label_width, legend_y, "Less", Style::default());
+    for (i, shade) in SHADES.iter().enumerate() {
+      let style = if i == 0 {
+        Style::default().add_modifier(Modifier::DIM)
+      } else {
+        self.heatmap_style
+      };
+      set_string(buf, area.x + label_width + 5 + i as u16 * 2, legend_y, shade, style);
+    }
+    set_string(buf, area.x + label_width + 15, legend_y, "More", Style::default());
+  }
+}
+
 impl<'a> Calendar<'a> {
   fn generate_month_names() -> [&'a str; 12] {
     [
@@ -272,3 +437,61 @@ impl<'a> Calendar<'a> {
     ]
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn render(calendar: Calendar, width: u16) -> Vec<String> {
+    let area = Rect::new(0, 0, width, 12);
+    let mut buf = Buffer::empty(area);
+    calendar.render(area, &mut buf);
+    (0..area.height)
+      .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>().trim_end().to_string())
+      .collect()
+  }
+
+  #[test]
+  fn test_calendar_draws_counts_when_there_is_room() {
+    let counts = HashMap::from([
+      (NaiveDate::from_ymd_opt(2020, 1, 6).unwrap(), 3),
+      (NaiveDate::from_ymd_opt(2020, 1, 7).unwrap(), 120),
+    ]);
+    let calendar = Calendar::default().year(2020).months_per_row(1).date_counts(counts);
+
+    let lines = render(calendar.clone(), 44);
+    assert_eq!(lines[4], "Su    Mo    Tu    We    Th    Fr    Sa");
+    assert_eq!(lines[6], " 5     6 3   7 99  8     9    10    11");
+
+    // Too narrow for wide cells, so the counts are left out
+    let lines = render(calendar, 30);
+    assert_eq!(lines[4], "    Su Mo Tu We Th Fr Sa");
+    assert_eq!(lines[6], "     5  6  7  8  9 10 11");
+  }
+
+  #[test]
+  fn test_heatmap_shades_days_by_count() {
+    let today = Local::now().date_naive();
+    let counts = HashMap::from([(today - Duration::days(1), 4), (today - Duration::days(2), 1)]);
+    let calendar = Calendar::default().heatmap(true).start_on_monday(true).date_counts(counts);
+    let area = Rect::new(0, 0, 23, 14);
+    let mut buf = Buffer::empty(area);
+    calendar.render(area, &mut buf);
+
+    // 10 weeks fit, the last one holds today
+    let first = calendar_week_start(today) - Duration::weeks(9);
+    let cell = |date: NaiveDate| {
+      let x = 3 + (calendar_week_start(date) - first).num_weeks() as u16 * 2;
+      let y = 4 + date.weekday().num_days_from_monday() as u16;
+      buf[(x, y)].symbol().to_string()
+    };
+    assert_eq!(cell(today - Duration::days(1)), "█");
+    assert_eq!(cell(today - Duration::days(2)), "░");
+    assert_eq!(cell(today - Duration::days(3)), "·");
+    assert!((0..area.width).map(|x| buf[(x, 1)].symbol()).collect::<String>().contains("5 completed"));
+  }
+
+  fn calendar_week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+  }
+}
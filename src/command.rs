@@ -0,0 +1,679 @@
+use crate::{event::KeyCode, keyconfig::KeyConfig};
+
+/// Every action that can be bound to a key.
+///
+/// This is the single registry behind key bindings in `KeyConfig`, the help popup
+/// and the command palette, so a new action only has to be added here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+  Quit,
+  Refresh,
+  GoToBottom,
+  GoToTop,
+  Down,
+  Up,
+  PageDown,
+  PageUp,
+  ScrollDetailsDown,
+  ScrollDetailsUp,
+  Done,
+  Delete,
+  StartStop,
+  StopAll,
+  Pomodoro,
+  QuickTag,
+  Select,
+  SelectAll,
+  Undo,
+  Edit,
+  Duplicate,
+  Modify,
+  MoveToProject,
+  Shell,
+  Log,
+  Add,
+  Annotate,
+  Help,
+  Filter,
+  Jump,
+  Zoom,
+  Transpose,
+  Obfuscate,
+  ContextMenu,
+  ReportMenu,
+  Palette,
+  NextTab,
+  PreviousTab,
+  PriorityH,
+  PriorityM,
+  PriorityL,
+  PriorityN,
+  AddDependency,
+  RemoveDependency,
+  ColumnLeft,
+  ColumnRight,
+  MoveLeft,
+  MoveRight,
+  PreviousDay,
+  NextDay,
+  PreviousYear,
+  NextYear,
+  SetDue,
+  SetScheduled,
+  Heatmap,
+  WeekView,
+  PreviousPeriod,
+  NextPeriod,
+  Fold,
+  Sort,
+  SaveSort,
+  EditColumns,
+  RenameProject,
+  MergeProject,
+  AddColumn,
+  RemoveColumn,
+  MoveColumnDown,
+  MoveColumnUp,
+  PreviousFormat,
+  NextFormat,
+  RelabelColumn,
+  SaveColumns,
+  Shortcut0,
+  Shortcut1,
+  Shortcut2,
+  Shortcut3,
+  Shortcut4,
+  Shortcut5,
+  Shortcut6,
+  Shortcut7,
+  Shortcut8,
+  Shortcut9,
+}
+
+impl Command {
+  pub const ALL: [Command; 82] = [
+    Command::Quit,
+    Command::Refresh,
+    Command::GoToBottom,
+    Command::GoToTop,
+    Command::Down,
+    Command::Up,
+    Command::PageDown,
+    Command::PageUp,
+    Command::ScrollDetailsDown,
+    Command::ScrollDetailsUp,
+    Command::Done,
+    Command::Delete,
+    Command::StartStop,
+    Command::StopAll,
+    Command::Pomodoro,
+    Command::QuickTag,
+    Command::Select,
+    Command::SelectAll,
+    Command::Undo,
+    Command::Edit,
+    Command::Duplicate,
+    Command::Modify,
+    Command::MoveToProject,
+    Command::Shell,
+    Command::Log,
+    Command::Add,
+    Command::Annotate,
+    Command::Help,
+    Command::Filter,
+    Command::Jump,
+    Command::Zoom,
+    Command::Transpose,
+    Command::Obfuscate,
+    Command::ContextMenu,
+    Command::ReportMenu,
+    Command::Palette,
+    Command::NextTab,
+    Command::PreviousTab,
+    Command::PriorityH,
+    Command::PriorityM,
+    Command::PriorityL,
+    Command::PriorityN,
+    Command::AddDependency,
+    Command::RemoveDependency,
+    Command::ColumnLeft,
+    Command::ColumnRight,
+    Command::MoveLeft,
+    Command::MoveRight,
+    Command::PreviousDay,
+    Command::NextDay,
+    Command::PreviousYear,
+    Command::NextYear,
+    Command::SetDue,
+    Command::SetScheduled,
+    Command::Heatmap,
+    Command::WeekView,
+    Command::PreviousPeriod,
+    Command::NextPeriod,
+    Command::Fold,
+    Command::Sort,
+    Command::SaveSort,
+    Command::EditColumns,
+    Command::RenameProject,
+    Command::MergeProject,
+    Command::AddColumn,
+    Command::RemoveColumn,
+    Command::MoveColumnDown,
+    Command::MoveColumnUp,
+    Command::PreviousFormat,
+    Command::NextFormat,
+    Command::RelabelColumn,
+    Command::SaveColumns,
+    Command::Shortcut0,
+    Command::Shortcut1,
+    Command::Shortcut2,
+    Command::Shortcut3,
+    Command::Shortcut4,
+    Command::Shortcut5,
+    Command::Shortcut6,
+    Command::Shortcut7,
+    Command::Shortcut8,
+    Command::Shortcut9,
+  ];
+
+  /// Commands that only apply to the Projects, Board, Calendar, Agenda or Timesheet pane or the column editor, so they are
+  /// not bound in the task report
+  pub const PANE_ONLY: [Command; 22] = [
+    Command::MoveLeft,
+    Command::MoveRight,
+    Command::PreviousDay,
+    Command::NextDay,
+    Command::PreviousYear,
+    Command::NextYear,
+    Command::SetDue,
+    Command::SetScheduled,
+    Command::Heatmap,
+    Command::WeekView,
+    Command::PreviousPeriod,
+    Command::NextPeriod,
+    Command::RenameProject,
+    Command::MergeProject,
+    Command::AddColumn,
+    Command::RemoveColumn,
+    Command::MoveColumnDown,
+    Command::MoveColumnUp,
+    Command::PreviousFormat,
+    Command::NextFormat,
+    Command::RelabelColumn,
+    Command::SaveColumns,
+  ];
+
+  /// Commands whose default keys were added after key configs could bind any key, so a user binding that
+  /// collides with one of them unbinds the default instead of being rejected
+  pub const NEWER_DEFAULTS: [Command; 35] = [
+    Command::StopAll,
+    Command::Pomodoro,
+    Command::MoveToProject,
+    Command::Obfuscate,
+    Command::Palette,
+    Command::AddDependency,
+    Command::RemoveDependency,
+    Command::ColumnLeft,
+    Command::ColumnRight,
+    Command::MoveLeft,
+    Command::MoveRight,
+    Command::PreviousDay,
+    Command::NextDay,
+    Command::PreviousYear,
+    Command::NextYear,
+    Command::SetDue,
+    Command::SetScheduled,
+    Command::Heatmap,
+    Command::WeekView,
+    Command::PreviousPeriod,
+    Command::NextPeriod,
+    Command::Fold,
+    Command::Sort,
+    Command::SaveSort,
+    Command::EditColumns,
+    Command::RenameProject,
+    Command::MergeProject,
+    Command::AddColumn,
+    Command::RemoveColumn,
+    Command::MoveColumnDown,
+    Command::MoveColumnUp,
+    Command::PreviousFormat,
+    Command::NextFormat,
+    Command::RelabelColumn,
+    Command::SaveColumns,
+  ];
+
+  /// Name used in `uda.taskwarrior-tui.keyconfig.<name>` and, with `_` instead of `-`, in the help template
+  pub fn name(self) -> &'static str {
+    match self {
+      Command::Quit => "quit",
+      Command::Refresh => "refresh",
+      Command::GoToBottom => "go-to-bottom",
+      Command::GoToTop => "go-to-top",
+      Command::Down => "down",
+      Command::Up => "up",
+      Command::PageDown => "page-down",
+      Command::PageUp => "page-up",
+      Command::ScrollDetailsDown => "scroll-details-down",
+      Command::ScrollDetailsUp => "scroll-details-up",
+      Command::Done => "done",
+      Command::Delete => "delete",
+      Command::StartStop => "start-stop",
+      Command::StopAll => "stop-all",
+      Command::Pomodoro => "pomodoro",
+      Command::QuickTag => "quick-tag",
+      Command::Select => "select",
+      Command::SelectAll => "select-all",
+      Command::Undo => "undo",
+      Command::Edit => "edit",
+      Command::Duplicate => "duplicate",
+      Command::Modify => "modify",
+      Command::MoveToProject => "move-to-project",
+      Command::Shell => "shell",
+      Command::Log => "log",
+      Command::Add => "add",
+      Command::Annotate => "annotate",
+      Command::Help => "help",
+      Command::Filter => "filter",
+      Command::Jump => "jump",
+      Command::Zoom => "zoom",
+      Command::Transpose => "transpose",
+      Command::Obfuscate => "obfuscate",
+      Command::ContextMenu => "context-menu",
+      Command::ReportMenu => "report-menu",
+      Command::Palette => "command-palette",
+      Command::NextTab => "next-tab",
+      Command::PreviousTab => "previous-tab",
+      Command::PriorityH => "priority-h",
+      Command::PriorityM => "priority-m",
+      Command::PriorityL => "priority-l",
+      Command::PriorityN => "priority-n",
+      Command::AddDependency => "add-dependency",
+      Command::RemoveDependency => "remove-dependency",
+      Command::ColumnLeft => "column-left",
+      Command::ColumnRight => "column-right",
+      Command::MoveLeft => "move-left",
+      Command::MoveRight => "move-right",
+      Command::PreviousDay => "previous-day",
+      Command::NextDay => "next-day",
+      Command::PreviousYear => "previous-year",
+      Command::NextYear => "next-year",
+      Command::SetDue => "set-due",
+      Command::SetScheduled => "set-scheduled",
+      Command::Heatmap => "heatmap",
+      Command::WeekView => "week-view",
+      Command::PreviousPeriod => "previous-period",
+      Command::NextPeriod => "next-period",
+      Command::Fold => "fold",
+      Command::Sort => "sort",
+      Command::SaveSort => "save-sort",
+      Command::EditColumns => "edit-columns",
+      Command::RenameProject => "rename-project",
+      Command::MergeProject => "merge-project",
+      Command::AddColumn => "add-column",
+      Command::RemoveColumn => "remove-column",
+      Command::MoveColumnDown => "move-column-down",
+      Command::MoveColumnUp => "move-column-up",
+      Command::PreviousFormat => "previous-format",
+      Command::NextFormat => "next-format",
+      Command::RelabelColumn => "relabel-column",
+      Command::SaveColumns => "save-columns",
+      Command::Shortcut0 => "shortcut0",
+      Command::Shortcut1 => "shortcut1",
+      Command::Shortcut2 => "shortcut2",
+      Command::Shortcut3 => "shortcut3",
+      Command::Shortcut4 => "shortcut4",
+      Command::Shortcut5 => "shortcut5",
+      Command::Shortcut6 => "shortcut6",
+      Command::Shortcut7 => "shortcut7",
+      Command::Shortcut8 => "shortcut8",
+      Command::Shortcut9 => "shortcut9",
+    }
+  }
+
+  /// Human readable description shown in the command palette
+  pub fn title(self) -> &'static str {
+    match self {
+      Command::Quit => "Quit",
+      Command::Refresh => "Refresh tasks",
+      Command::GoToBottom => "Go to bottom",
+      Command::GoToTop => "Go to top",
+      Command::Down => "Move down",
+      Command::Up => "Move up",
+      Command::PageDown => "Move page down",
+      Command::PageUp => "Move page up",
+      Command::ScrollDetailsDown => "Scroll task details down",
+      Command::ScrollDetailsUp => "Scroll task details up",
+      Command::Done => "Mark task as done",
+      Command::Delete => "Delete task",
+      Command::StartStop => "Start or stop task",
+      Command::StopAll => "Stop all active tasks",
+      Command::Pomodoro => "Start or cancel a pomodoro on task",
+      Command::QuickTag => "Toggle quick tag",
+      Command::Select => "Toggle mark on selected task",
+      Command::SelectAll => "Toggle marks on all tasks",
+      Command::Undo => "Undo",
+      Command::Edit => "Edit task",
+      Command::Duplicate => "Duplicate task",
+      Command::Modify => "Modify task",
+      Command::MoveToProject => "Move tasks to a project",
+      Command::Shell => "Run shell command",
+      Command::Log => "Log task",
+      Command::Add => "Add task",
+      Command::Annotate => "Annotate task",
+      Command::Help => "Show help",
+      Command::Filter => "Filter tasks",
+      Command::Jump => "Jump to task id",
+      Command::Zoom => "Toggle task info",
+      Command::Transpose => "Toggle task info location",
+      Command::Obfuscate => "Toggle masking of task text for screen sharing",
+      Command::ContextMenu => "Open context switcher",
+      Command::ReportMenu => "Open report switcher",
+      Command::Palette => "Open command palette",
+      Command::NextTab => "Go to next view",
+      Command::PreviousTab => "Go to previous view",
+      Command::PriorityH => "Set priority H",
+      Command::PriorityM => "Set priority M",
+      Command::PriorityL => "Set priority L",
+      Command::PriorityN => "Remove priority",
+      Command::AddDependency => "Make selected task depend on the other marked task",
+      Command::RemoveDependency => "Remove dependency between the two marked tasks",
+      Command::ColumnLeft => "Go to the column on the left",
+      Command::ColumnRight => "Go to the column on the right",
+      Command::MoveLeft => "Move cards to the column on the left",
+      Command::MoveRight => "Move cards to the column on the right",
+      Command::PreviousDay => "Go to the previous day",
+      Command::NextDay => "Go to the next day",
+      Command::PreviousYear => "Go to the same day last year",
+      Command::NextYear => "Go to the same day next year",
+      Command::SetDue => "Set the selected day as due date",
+      Command::SetScheduled => "Set the selected day as scheduled date",
+      Command::Heatmap => "Toggle the heatmap of completed tasks",
+      Command::WeekView => "Toggle between a day and a week in the agenda or timesheet",
+      Command::PreviousPeriod => "Go to the previous week or day of the timesheet",
+      Command::NextPeriod => "Go to the next week or day of the timesheet",
+      Command::Fold => "Fold or unfold the group of the selected task or project",
+      Command::Sort => "Sort by the focused column",
+      Command::SaveSort => "Save the sort order of the report to taskrc",
+      Command::EditColumns => "Add, remove, reorder and relabel the columns of the report",
+      Command::RenameProject => "Rename or move the selected project",
+      Command::MergeProject => "Merge the selected project into another",
+      Command::AddColumn => "Add a column after the selected one",
+      Command::RemoveColumn => "Remove the selected column",
+      Command::MoveColumnDown => "Move the selected column down",
+      Command::MoveColumnUp => "Move the selected column up",
+      Command::PreviousFormat => "Switch the selected column to its previous format",
+      Command::NextFormat => "Switch the selected column to its next format",
+      Command::RelabelColumn => "Give the selected column a new label",
+      Command::SaveColumns => "Save the columns of the report to taskrc",
+      Command::Shortcut0 => "Run shortcut 0",
+      Command::Shortcut1 => "Run shortcut 1",
+      Command::Shortcut2 => "Run shortcut 2",
+      Command::Shortcut3 => "Run shortcut 3",
+      Command::Shortcut4 => "Run shortcut 4",
+      Command::Shortcut5 => "Run shortcut 5",
+      Command::Shortcut6 => "Run shortcut 6",
+      Command::Shortcut7 => "Run shortcut 7",
+      Command::Shortcut8 => "Run shortcut 8",
+      Command::Shortcut9 => "Run shortcut 9",
+    }
+  }
+
+  /// Looks up a command by its config name, accepting `_` in place of `-`
+  pub fn from_name(name: &str) -> Option<Command> {
+    let name = name.replace('_', "-");
+    Command::ALL.into_iter().find(|c| c.name() == name)
+  }
+
+  /// The configurable key binding of this command, or `None` for commands with a fixed key
+  pub fn key_mut(self, kc: &mut KeyConfig) -> Option<&mut KeyCode> {
+    let key = match self {
+      Command::Quit => &mut kc.quit,
+      Command::Refresh => &mut kc.refresh,
+      Command::GoToBottom => &mut kc.go_to_bottom,
+      Command::GoToTop => &mut kc.go_to_top,
+      Command::Down => &mut kc.down,
+      Command::Up => &mut kc.up,
+      Command::PageDown => &mut kc.page_down,
+      Command::PageUp => &mut kc.page_up,
+      Command::ScrollDetailsDown | Command::ScrollDetailsUp | Command::Jump => return None,
+      Command::Done => &mut kc.done,
+      Command::Delete => &mut kc.delete,
+      Command::StartStop => &mut kc.start_stop,
+      Command::StopAll => &mut kc.stop_all,
+      Command::Pomodoro => &mut kc.pomodoro,
+      Command::QuickTag => &mut kc.quick_tag,
+      Command::Select => &mut kc.select,
+      Command::SelectAll => &mut kc.select_all,
+      Command::Undo => &mut kc.undo,
+      Command::Edit => &mut kc.edit,
+      Command::Duplicate => &mut kc.duplicate,
+      Command::Modify => &mut kc.modify,
+      Command::MoveToProject => &mut kc.move_to_project,
+      Command::Shell => &mut kc.shell,
+      Command::Log => &mut kc.log,
+      Command::Add => &mut kc.add,
+      Command::Annotate => &mut kc.annotate,
+      Command::Help => &mut kc.help,
+      Command::Filter => &mut kc.filter,
+      Command::Zoom => &mut kc.zoom,
+      Command::Transpose => &mut kc.transpose,
+      Command::Obfuscate => &mut kc.obfuscate,
+      Command::ContextMenu => &mut kc.context_menu,
+      Command::ReportMenu => &mut kc.report_menu,
+      Command::Palette => &mut kc.command_palette,
+      Command::NextTab => &mut kc.next_tab,
+      Command::PreviousTab => &mut kc.previous_tab,
+      Command::PriorityH => &mut kc.priority_h,
+      Command::PriorityM => &mut kc.priority_m,
+      Command::PriorityL => &mut kc.priority_l,
+      Command::PriorityN => &mut kc.priority_n,
+      Command::AddDependency => &mut kc.add_dependency,
+      Command::RemoveDependency => &mut kc.remove_dependency,
+      Command::ColumnLeft => &mut kc.column_left,
+      Command::ColumnRight => &mut kc.column_right,
+      Command::MoveLeft => &mut kc.move_left,
+      Command::MoveRight => &mut kc.move_right,
+      Command::PreviousDay => &mut kc.previous_day,
+      Command::NextDay => &mut kc.next_day,
+      Command::PreviousYear => &mut kc.previous_year,
+      Command::NextYear => &mut kc.next_year,
+      Command::SetDue => &mut kc.set_due,
+      Command::SetScheduled => &mut kc.set_scheduled,
+      Command::Heatmap => &mut kc.heatmap,
+      Command::WeekView => &mut kc.week_view,
+      Command::PreviousPeriod => &mut kc.previous_period,
+      Command::NextPeriod => &mut kc.next_period,
+      Command::Fold => &mut kc.fold,
+      Command::Sort => &mut kc.sort,
+      Command::SaveSort => &mut kc.save_sort,
+      Command::EditColumns => &mut kc.edit_columns,
+      Command::RenameProject => &mut kc.rename_project,
+      Command::MergeProject => &mut kc.merge_project,
+      Command::AddColumn => &mut kc.add_column,
+      Command::RemoveColumn => &mut kc.remove_column,
+      Command::MoveColumnDown => &mut kc.move_column_down,
+      Command::MoveColumnUp => &mut kc.move_column_up,
+      Command::PreviousFormat => &mut kc.previous_format,
+      Command::NextFormat => &mut kc.next_format,
+      Command::RelabelColumn => &mut kc.relabel_column,
+      Command::SaveColumns => &mut kc.save_columns,
+      Command::Shortcut0 => &mut kc.shortcut0,
+      Command::Shortcut1 => &mut kc.shortcut1,
+      Command::Shortcut2 => &mut kc.shortcut2,
+      Command::Shortcut3 => &mut kc.shortcut3,
+      Command::Shortcut4 => &mut kc.shortcut4,
+      Command::Shortcut5 => &mut kc.shortcut5,
+      Command::Shortcut6 => &mut kc.shortcut6,
+      Command::Shortcut7 => &mut kc.shortcut7,
+      Command::Shortcut8 => &mut kc.shortcut8,
+      Command::Shortcut9 => &mut kc.shortcut9,
+    };
+    Some(key)
+  }
+
+  /// The key currently bound to this command
+  pub fn key(self, kc: &KeyConfig) -> KeyCode {
+    match self {
+      Command::ScrollDetailsDown => KeyCode::Ctrl('e'),
+      Command::ScrollDetailsUp => KeyCode::Ctrl('y'),
+      Command::Jump => KeyCode::Char(':'),
+      Command::Quit => kc.quit,
+      Command::Refresh => kc.refresh,
+      Command::GoToBottom => kc.go_to_bottom,
+      Command::GoToTop => kc.go_to_top,
+      Command::Down => kc.down,
+      Command::Up => kc.up,
+      Command::PageDown => kc.page_down,
+      Command::PageUp => kc.page_up,
+      Command::Done => kc.done,
+      Command::Delete => kc.delete,
+      Command::StartStop => kc.start_stop,
+      Command::StopAll => kc.stop_all,
+      Command::Pomodoro => kc.pomodoro,
+      Command::QuickTag => kc.quick_tag,
+      Command::Select => kc.select,
+      Command::SelectAll => kc.select_all,
+      Command::Undo => kc.undo,
+      Command::Edit => kc.edit,
+      Command::Duplicate => kc.duplicate,
+      Command::Modify => kc.modify,
+      Command::MoveToProject => kc.move_to_project,
+      Command::Shell => kc.shell,
+      Command::Log => kc.log,
+      Command::Add => kc.add,
+      Command::Annotate => kc.annotate,
+      Command::Help => kc.help,
+      Command::Filter => kc.filter,
+      Command::Zoom => kc.zoom,
+      Command::Transpose => kc.transpose,
+      Command::Obfuscate => kc.obfuscate,
+      Command::ContextMenu => kc.context_menu,
+      Command::ReportMenu => kc.report_menu,
+      Command::Palette => kc.command_palette,
+      Command::NextTab => kc.next_tab,
+      Command::PreviousTab => kc.previous_tab,
+      Command::PriorityH => kc.priority_h,
+      Command::PriorityM => kc.priority_m,
+      Command::PriorityL => kc.priority_l,
+      Command::PriorityN => kc.priority_n,
+      Command::AddDependency => kc.add_dependency,
+      Command::RemoveDependency => kc.remove_dependency,
+      Command::ColumnLeft => kc.column_left,
+      Command::ColumnRight => kc.column_right,
+      Command::MoveLeft => kc.move_left,
+      Command::MoveRight => kc.move_right,
+      Command::PreviousDay => kc.previous_day,
+      Command::NextDay => kc.next_day,
+      Command::PreviousYear => kc.previous_year,
+      Command::NextYear => kc.next_year,
+      Command::SetDue => kc.set_due,
+      Command::SetScheduled => kc.set_scheduled,
+      Command::Heatmap => kc.heatmap,
+      Command::WeekView => kc.week_view,
+      Command::PreviousPeriod => kc.previous_period,
+      Command::NextPeriod => kc.next_period,
+      Command::Fold => kc.fold,
+      Command::Sort => kc.sort,
+      Command::SaveSort => kc.save_sort,
+      Command::EditColumns => kc.edit_columns,
+      Command::RenameProject => kc.rename_project,
+      Command::MergeProject => kc.merge_project,
+      Command::AddColumn => kc.add_column,
+      Command::RemoveColumn => kc.remove_column,
+      Command::MoveColumnDown => kc.move_column_down,
+      Command::MoveColumnUp => kc.move_column_up,
+      Command::PreviousFormat => kc.previous_format,
+      Command::NextFormat => kc.next_format,
+      Command::RelabelColumn => kc.relabel_column,
+      Command::SaveColumns => kc.save_columns,
+      Command::Shortcut0 => kc.shortcut0,
+      Command::Shortcut1 => kc.shortcut1,
+      Command::Shortcut2 => kc.shortcut2,
+      Command::Shortcut3 => kc.shortcut3,
+      Command::Shortcut4 => kc.shortcut4,
+      Command::Shortcut5 => kc.shortcut5,
+      Command::Shortcut6 => kc.shortcut6,
+      Command::Shortcut7 => kc.shortcut7,
+      Command::Shortcut8 => kc.shortcut8,
+      Command::Shortcut9 => kc.shortcut9,
+    }
+  }
+
+  /// A fixed key that runs this command in addition to the configured one
+  pub fn alternate_key(self) -> Option<KeyCode> {
+    match self {
+      Command::Quit => Some(KeyCode::Ctrl('c')),
+      Command::GoToBottom => Some(KeyCode::End),
+      Command::GoToTop => Some(KeyCode::Home),
+      Command::Down => Some(KeyCode::Down),
+      Command::Up => Some(KeyCode::Up),
+      Command::PageDown => Some(KeyCode::PageDown),
+      Command::PageUp => Some(KeyCode::PageUp),
+      Command::PreviousFormat => Some(KeyCode::Left),
+      Command::NextFormat => Some(KeyCode::Right),
+      _ => None,
+    }
+  }
+
+  /// Index into `Config::uda_shortcuts` for the numbered shortcut commands
+  pub fn shortcut(self) -> Option<usize> {
+    match self {
+      Command::Shortcut0 => Some(0),
+      Command::Shortcut1 => Some(1),
+      Command::Shortcut2 => Some(2),
+      Command::Shortcut3 => Some(3),
+      Command::Shortcut4 => Some(4),
+      Command::Shortcut5 => Some(5),
+      Command::Shortcut6 => Some(6),
+      Command::Shortcut7 => Some(7),
+      Command::Shortcut8 => Some(8),
+      Command::Shortcut9 => Some(9),
+      _ => None,
+    }
+  }
+}
+
+/// Renders a key the way it is written in the help popup and the command palette
+pub fn key_label(key: KeyCode) -> String {
+  match key {
+    KeyCode::Char('\n') => "Enter".to_string(),
+    KeyCode::Char(' ') => "Space".to_string(),
+    KeyCode::Char(c) => c.to_string(),
+    KeyCode::Ctrl(c) => format!("Ctrl-{}", c),
+    KeyCode::Alt(c) => format!("Alt-{}", c),
+    KeyCode::F(n) => format!("F{}", n),
+    KeyCode::Null => String::new(),
+    key => format!("{:?}", key),
+  }
+}
+
+/// Renders a key sequence, e.g. `gg` or `Space p`
+pub fn sequence_label(keys: &[KeyCode]) -> String {
+  let labels: Vec<String> = keys.iter().map(|k| key_label(*k)).collect();
+  if labels.iter().all(|l| l.chars().count() <= 1) {
+    labels.concat()
+  } else {
+    labels.join(" ")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_command_names_round_trip() {
+    for command in Command::ALL {
+      assert_eq!(Command::from_name(command.name()), Some(command));
+      assert_eq!(Command::from_name(&command.name().replace('-', "_")), Some(command));
+    }
+  }
+
+  #[test]
+  fn test_sequence_label() {
+    assert_eq!(sequence_label(&[KeyCode::Char('g'), KeyCode::Char('g')]), "gg");
+    assert_eq!(sequence_label(&[KeyCode::Char(' '), KeyCode::Char('p')]), "Space p");
+    assert_eq!(sequence_label(&[KeyCode::Ctrl('p')]), "Ctrl-p");
+  }
+}
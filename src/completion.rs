@@ -212,6 +212,15 @@ impl CompletionList {
     }
     self.pos = self.current.len();
   }
+
+  /// Completes `current` against the candidates of a single `context`,
+  /// skipping candidates already present in `i`.
+  pub fn input_for_context(&mut self, context: &str, current: String, i: String) {
+    self.helper.input = i;
+    self.helper.context = context.to_string();
+    self.current = current;
+    self.pos = self.current.len();
+  }
 }
 
 #[cfg(test)]
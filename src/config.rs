@@ -1,10 +1,14 @@
-use std::{collections::HashMap, error::Error, str};
+use std::{collections::HashMap, error::Error, path::PathBuf, str};
 
 use anyhow::{Context, Result};
+use log::warn;
 use ratatui::{
   style::{Color, Modifier, Style},
   symbols::{bar::FULL, line::DOUBLE_VERTICAL},
 };
+use task_hookrs::{date::Date, task::Task};
+
+use crate::{group::GroupBy, task_report::taskwarrior_to_chrono};
 
 trait TaskWarriorBool {
   fn get_bool(&self) -> Option<bool>;
@@ -34,13 +38,81 @@ impl TaskWarriorBool for str {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Uda {
-  label: String,
-  kind: String,
-  values: Option<Vec<String>>,
-  default: Option<String>,
-  urgency: Option<f64>,
+  pub name: String,
+  pub label: String,
+  pub kind: String,
+  pub values: Option<Vec<String>>,
+  pub default: Option<String>,
+  pub urgency: Option<f64>,
+}
+
+/// What the columns of the Board pane stand for
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BoardGroup {
+  /// pending, active, waiting and completed
+  #[default]
+  Status,
+  /// One column per configured tag
+  Tags,
+  /// One column per value of a UDA
+  Uda(String),
+}
+
+impl BoardGroup {
+  fn from_config_value(value: &str) -> Self {
+    match value.trim() {
+      "" | "status" => Self::Status,
+      "tags" | "tag" => Self::Tags,
+      uda => Self::Uda(uda.strip_prefix("uda.").unwrap_or(uda).to_string()),
+    }
+  }
+}
+
+/// A task date that can be marked on the Calendar pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalendarDateSource {
+  Due,
+  Scheduled,
+  Wait,
+  Until,
+  Entry,
+  End,
+}
+
+impl CalendarDateSource {
+  pub const ALL: [CalendarDateSource; 6] = [
+    CalendarDateSource::Due,
+    CalendarDateSource::Scheduled,
+    CalendarDateSource::Wait,
+    CalendarDateSource::Until,
+    CalendarDateSource::Entry,
+    CalendarDateSource::End,
+  ];
+
+  /// Name used in `uda.taskwarrior-tui.calendar.date-sources` and `uda.taskwarrior-tui.style.calendar.<name>`
+  pub fn name(self) -> &'static str {
+    match self {
+      CalendarDateSource::Due => "due",
+      CalendarDateSource::Scheduled => "scheduled",
+      CalendarDateSource::Wait => "wait",
+      CalendarDateSource::Until => "until",
+      CalendarDateSource::Entry => "entry",
+      CalendarDateSource::End => "end",
+    }
+  }
+
+  pub fn date(self, task: &Task) -> Option<&Date> {
+    match self {
+      CalendarDateSource::Due => task.due(),
+      CalendarDateSource::Scheduled => task.scheduled(),
+      CalendarDateSource::Wait => task.wait(),
+      CalendarDateSource::Until => task.until(),
+      CalendarDateSource::Entry => Some(task.entry()),
+      CalendarDateSource::End => task.end(),
+    }
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -87,6 +159,8 @@ pub struct Config {
   pub print_empty_columns: bool,
   pub due: usize,
   pub weekstart: bool,
+  pub journal_time_start_annotation: String,
+  pub journal_time_stop_annotation: String,
   pub rule_precedence_color: Vec<String>,
   pub uda_priority_values: Vec<String>,
   pub uda_tick_rate: u64,
@@ -97,9 +171,15 @@ pub struct Config {
   pub uda_reset_filter_on_esc: bool,
   pub uda_task_detail_prefetch: usize,
   pub uda_task_report_use_all_tasks_for_completion: bool,
+  pub uda_task_report_incremental_export: bool,
+  pub uda_background_jobs: bool,
+  pub uda_board_group_by: BoardGroup,
+  pub uda_task_report_group_by: Option<GroupBy>,
+  pub uda_board_columns: Vec<String>,
   pub uda_task_report_use_alternate_style: bool,
   pub uda_task_report_info_show: bool,
   pub uda_task_report_looping: bool,
+  pub uda_task_report_wrap_description: bool,
   pub uda_task_report_jump_to_task_on_add: bool,
   pub uda_selection_indicator: String,
   pub uda_mark_highlight_indicator: String,
@@ -110,18 +190,35 @@ pub struct Config {
   pub uda_scrollbar_area: String,
   pub uda_style_report_scrollbar: Style,
   pub uda_style_report_scrollbar_area: Style,
+  pub uda_style_report_group: Style,
   pub uda_selection_bold: bool,
   pub uda_selection_italic: bool,
   pub uda_selection_dim: bool,
   pub uda_selection_blink: bool,
   pub uda_selection_reverse: bool,
   pub uda_calendar_months_per_row: usize,
+  pub uda_calendar_date_sources: Vec<CalendarDateSource>,
+  pub uda_calendar_all_tasks: bool,
+  pub uda_timer_show: bool,
+  /// Duration UDA that keeps the time spent on a task; empty to read it from `journal.time` annotations
+  pub uda_timer_uda: String,
+  /// Length of a pomodoro and of the break after it, in minutes
+  pub uda_pomodoro_work: u64,
+  pub uda_pomodoro_break: u64,
+  pub uda_pomodoro_annotate: bool,
+  /// Numeric UDA that counts the pomodoros completed on a task; empty if it is not defined
+  pub uda_pomodoro_uda: String,
   pub uda_style_context_active: Style,
   pub uda_style_report_menu_active: Style,
   pub uda_style_report_selection: Style,
   pub uda_style_calendar_title: Style,
   pub uda_style_calendar_today: Style,
+  pub uda_style_calendar_selection: Style,
+  pub uda_style_calendar_heatmap: Style,
+  /// Styles of the dates from each source; sources without one use the style of the task
+  pub uda_style_calendar_sources: HashMap<CalendarDateSource, Style>,
   pub uda_style_navbar: Style,
+  pub uda_style_timer: Style,
   pub uda_style_command: Style,
   pub uda_style_report_completion_pane: Style,
   pub uda_style_report_completion_pane_highlight: Style,
@@ -143,6 +240,11 @@ pub struct Config {
   pub uda_context_menu_close_on_select: bool,
   pub uda_report_menu_select_on_move: bool,
   pub uda_report_menu_close_on_select: bool,
+  pub uda_mouse: bool,
+  pub uda_mouse_double_click_edit: bool,
+  pub uda_task_editor_native: bool,
+  /// Taskwarrior's `dateformat` as a chrono format, used to check dates typed in the task editor
+  pub dateformat: String,
   pub uda: Vec<Uda>,
 }
 
@@ -168,6 +270,8 @@ impl Config {
     let data_location = Self::get_data_location(data);
     let due = Self::get_due(data);
     let weekstart = Self::get_weekstart(data);
+    let journal_time_start_annotation = Self::get_config("journal.time.start.annotation", data).unwrap_or_else(|| "Started task".to_string());
+    let journal_time_stop_annotation = Self::get_config("journal.time.stop.annotation", data).unwrap_or_else(|| "Stopped task".to_string());
     let rule_precedence_color = Self::get_rule_precedence_color(data);
     let uda_priority_values = Self::get_uda_priority_values(data);
     let uda_tick_rate = Self::get_uda_tick_rate(data);
@@ -179,9 +283,15 @@ impl Config {
     let uda_reset_filter_on_esc = Self::get_uda_reset_filter_on_esc(data);
     let uda_task_detail_prefetch = Self::get_uda_task_detail_prefetch(data);
     let uda_task_report_use_all_tasks_for_completion = Self::get_uda_task_report_use_all_tasks_for_completion(data);
+    let uda_task_report_incremental_export = Self::get_uda_task_report_incremental_export(data);
+    let uda_background_jobs = Self::get_uda_background_jobs(data);
+    let uda_board_group_by = Self::get_uda_board_group_by(data);
+    let uda_board_columns = Self::get_uda_board_columns(data, &uda_board_group_by);
+    let uda_task_report_group_by = Self::get_uda_task_report_group_by(data);
     let uda_task_report_use_alternate_style = Self::get_uda_task_report_use_alternate_style(data);
     let uda_task_report_info_show = Self::get_uda_task_report_info_show(data);
     let uda_task_report_looping = Self::get_uda_task_report_looping(data);
+    let uda_task_report_wrap_description = Self::get_uda_task_report_wrap_description(data);
     let uda_task_report_jump_to_task_on_add = Self::get_uda_task_report_jump_to_task_on_add(data);
     let uda_selection_indicator = Self::get_uda_selection_indicator(data);
     let uda_mark_highlight_indicator = Self::get_uda_mark_highlight_indicator(data);
@@ -196,12 +306,28 @@ impl Config {
     let uda_selection_blink = Self::get_uda_selection_blink(data);
     let uda_selection_reverse = Self::get_uda_selection_reverse(data);
     let uda_calendar_months_per_row = Self::get_uda_months_per_row(data);
+    let uda_calendar_date_sources = Self::get_uda_calendar_date_sources(data);
+    let uda_calendar_all_tasks = Self::get_uda_calendar_all_tasks(data);
+    let uda_timer_show = Self::get_uda_timer_show(data);
+    let uda_timer_uda = Self::get_config("uda.taskwarrior-tui.timer.uda", data).unwrap_or_default();
+    let uda_pomodoro_work = Self::get_uda_pomodoro_minutes("work", data, 25);
+    let uda_pomodoro_break = Self::get_uda_pomodoro_minutes("break", data, 5);
+    let uda_pomodoro_annotate = Self::get_uda_pomodoro_annotate(data);
+    let uda_pomodoro_uda = Self::get_uda_pomodoro_uda(data);
     let uda_style_report_selection = Self::get_uda_style("report.selection", data);
     let uda_style_report_scrollbar = Self::get_uda_style("report.scrollbar", data);
     let uda_style_report_scrollbar_area = Self::get_uda_style("report.scrollbar.area", data);
+    let uda_style_report_group = Self::get_uda_style("report.group", data);
     let uda_style_calendar_title = Self::get_uda_style("calendar.title", data);
     let uda_style_calendar_today = Self::get_uda_style("calendar.today", data);
+    let uda_style_calendar_selection = Self::get_uda_style("calendar.selection", data);
+    let uda_style_calendar_heatmap = Self::get_uda_style("calendar.heatmap", data);
+    let uda_style_calendar_sources = CalendarDateSource::ALL
+      .into_iter()
+      .filter_map(|source| Some((source, Self::get_uda_style(&format!("calendar.{}", source.name()), data)?)))
+      .collect();
     let uda_style_navbar = Self::get_uda_style("navbar", data);
+    let uda_style_timer = Self::get_uda_style("timer", data);
     let uda_style_command = Self::get_uda_style("command", data);
     let uda_style_context_active = Self::get_uda_style("context.active", data);
     let uda_style_report_menu_active = Self::get_uda_style("report-menu.active", data);
@@ -217,9 +343,13 @@ impl Config {
     let uda_style_report_selection = uda_style_report_selection.unwrap_or_default();
     let uda_style_report_scrollbar = uda_style_report_scrollbar.unwrap_or_else(|| Style::default().fg(Color::Black));
     let uda_style_report_scrollbar_area = uda_style_report_scrollbar_area.unwrap_or_default();
+    let uda_style_report_group = uda_style_report_group.unwrap_or_else(|| Style::default().add_modifier(Modifier::BOLD));
     let uda_style_calendar_title = uda_style_calendar_title.unwrap_or_default();
     let uda_style_calendar_today = uda_style_calendar_today.unwrap_or_else(|| Style::default().add_modifier(Modifier::BOLD));
+    let uda_style_calendar_selection = uda_style_calendar_selection.unwrap_or_else(|| Style::default().add_modifier(Modifier::REVERSED));
+    let uda_style_calendar_heatmap = uda_style_calendar_heatmap.unwrap_or_else(|| Style::default().fg(Color::Green));
     let uda_style_navbar = uda_style_navbar.unwrap_or_else(|| Style::default().add_modifier(Modifier::REVERSED));
+    let uda_style_timer = uda_style_timer.unwrap_or_else(|| Style::default().add_modifier(Modifier::BOLD));
     let uda_style_command = uda_style_command.unwrap_or_else(|| Style::default().add_modifier(Modifier::REVERSED));
     let uda_style_context_active = uda_style_context_active.unwrap_or_else(|| Style::default().add_modifier(Modifier::BOLD));
     let uda_style_report_menu_active = uda_style_report_menu_active.unwrap_or_else(|| Style::default().add_modifier(Modifier::BOLD));
@@ -240,6 +370,11 @@ impl Config {
     let uda_report_menu_select_on_move = Self::get_uda_report_menu_select_on_move(data);
     let uda_report_menu_close_on_select = Self::get_uda_report_menu_close_on_select(data);
     let uda_task_report_date_time_vague_more_precise = Self::get_uda_task_report_date_time_vague_more_precise(data);
+    let uda_mouse = Self::get_uda_mouse(data);
+    let uda_mouse_double_click_edit = Self::get_uda_mouse_double_click_edit(data);
+    let uda_task_editor_native = Self::get_uda_task_editor_native(data);
+    let dateformat = Self::get_dateformat(data);
+    let uda = Self::get_udas(data);
 
     Ok(Self {
       enabled,
@@ -251,6 +386,8 @@ impl Config {
       print_empty_columns,
       due,
       weekstart,
+      journal_time_start_annotation,
+      journal_time_stop_annotation,
       rule_precedence_color,
       uda_priority_values,
       uda_tick_rate,
@@ -262,9 +399,15 @@ impl Config {
       uda_reset_filter_on_esc,
       uda_task_detail_prefetch,
       uda_task_report_use_all_tasks_for_completion,
+      uda_task_report_incremental_export,
+      uda_background_jobs,
+      uda_board_group_by,
+      uda_board_columns,
+      uda_task_report_group_by,
       uda_task_report_use_alternate_style,
       uda_task_report_info_show,
       uda_task_report_looping,
+      uda_task_report_wrap_description,
       uda_task_report_jump_to_task_on_add,
       uda_selection_indicator,
       uda_mark_highlight_indicator,
@@ -279,12 +422,25 @@ impl Config {
       uda_selection_blink,
       uda_selection_reverse,
       uda_calendar_months_per_row,
+      uda_calendar_date_sources,
+      uda_calendar_all_tasks,
+      uda_timer_show,
+      uda_timer_uda,
+      uda_pomodoro_work,
+      uda_pomodoro_break,
+      uda_pomodoro_annotate,
+      uda_pomodoro_uda,
       uda_style_report_selection,
       uda_style_report_scrollbar,
       uda_style_report_scrollbar_area,
+      uda_style_report_group,
       uda_style_calendar_title,
       uda_style_calendar_today,
+      uda_style_calendar_selection,
+      uda_style_calendar_heatmap,
+      uda_style_calendar_sources,
       uda_style_navbar,
+      uda_style_timer,
       uda_style_command,
       uda_style_context_active,
       uda_style_report_menu_active,
@@ -307,10 +463,19 @@ impl Config {
       uda_report_menu_select_on_move,
       uda_report_menu_close_on_select,
       uda_task_report_date_time_vague_more_precise,
-      uda: vec![],
+      uda_mouse,
+      uda_mouse_double_click_edit,
+      uda_task_editor_native,
+      dateformat,
+      uda,
     })
   }
 
+  /// `data.location` with `~` expanded
+  pub fn data_dir(&self) -> PathBuf {
+    PathBuf::from(shellexpand::tilde(&self.data_location).as_ref())
+  }
+
   fn get_bool_collection() -> HashMap<String, bool> {
     HashMap::new()
   }
@@ -676,6 +841,41 @@ impl Config {
       .unwrap_or(false)
   }
 
+  fn get_uda_task_report_incremental_export(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.task-report.incremental-export", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  fn get_uda_background_jobs(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.background-jobs", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  fn get_uda_board_group_by(data: &str) -> BoardGroup {
+    BoardGroup::from_config_value(&Self::get_config("uda.taskwarrior-tui.board.group-by", data).unwrap_or_default())
+  }
+
+  fn get_uda_task_report_group_by(data: &str) -> Option<GroupBy> {
+    GroupBy::parse(&Self::get_config("uda.taskwarrior-tui.task-report.group-by", data).unwrap_or_default())
+  }
+
+  /// The configured columns, or for a UDA its `uda.<name>.values`. An empty value stands for tasks without one.
+  fn get_uda_board_columns(data: &str, group: &BoardGroup) -> Vec<String> {
+    if let Some(columns) = Self::get_config("uda.taskwarrior-tui.board.columns", data) {
+      return columns.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect();
+    }
+    match group {
+      BoardGroup::Uda(name) => Self::get_config(&format!("uda.{}.values", name), data)
+        .map(|values| values.split(',').map(|v| v.trim().to_string()).collect())
+        .unwrap_or_default(),
+      _ => vec![],
+    }
+  }
+
   fn get_uda_task_report_use_alternate_style(data: &str) -> bool {
     Self::get_config("uda.taskwarrior-tui.task-report.use-alternate-style", data)
       .unwrap_or_default()
@@ -705,6 +905,13 @@ impl Config {
       .unwrap_or(true)
   }
 
+  fn get_uda_task_report_wrap_description(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.task-report.wrap-description", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
   fn get_uda_task_report_date_time_vague_more_precise(data: &str) -> bool {
     Self::get_config("uda.taskwarrior-tui.task-report.date-time-vague-more-precise", data)
       .unwrap_or_default()
@@ -747,6 +954,74 @@ impl Config {
       .unwrap_or(true)
   }
 
+  fn get_uda_mouse(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.mouse.enabled", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  fn get_uda_mouse_double_click_edit(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.mouse.double-click-edit", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_uda_task_editor_native(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.task-editor.native", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_dateformat(data: &str) -> String {
+    // The trailing space keeps `dateformat.report` and the like from matching
+    let dateformat = Self::get_config("dateformat ", data).unwrap_or_else(|| "Y-M-D".to_string());
+    taskwarrior_to_chrono(&dateformat)
+  }
+
+  /// Collects every user defined attribute declared with `uda.<name>.type`.
+  /// `priority` is skipped since it has a dedicated field wherever UDAs are listed.
+  fn get_udas(data: &str) -> Vec<Uda> {
+    let mut udas: Vec<Uda> = vec![];
+    for line in data.split('\n') {
+      let Some(name) = line
+        .split_whitespace()
+        .next()
+        .and_then(|key| key.strip_prefix("uda."))
+        .and_then(|key| key.strip_suffix(".type"))
+      else {
+        continue;
+      };
+      if name.contains('.') || name == "priority" || udas.iter().any(|u| u.name == name) {
+        continue;
+      }
+      let kind = Self::get_config(&format!("uda.{}.type", name), data).unwrap_or_else(|| "string".to_string());
+      let label = Self::get_config(&format!("uda.{}.label", name), data)
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| name.to_string());
+      let values = Self::get_config(&format!("uda.{}.values", name), data).map(|v| {
+        v.split(',')
+          .map(str::trim)
+          .filter(|v| !v.is_empty())
+          .map(ToString::to_string)
+          .collect::<Vec<_>>()
+      });
+      let default = Self::get_config(&format!("uda.{}.default", name), data).filter(|d| !d.is_empty());
+      let urgency = Self::get_config(&format!("urgency.uda.{}.coefficient", name), data).and_then(|u| u.parse::<f64>().ok());
+      udas.push(Uda {
+        name: name.to_string(),
+        label,
+        kind,
+        values,
+        default,
+        urgency,
+      });
+    }
+    udas
+  }
+
   fn get_uda_task_report_prompt_on_undo(data: &str) -> bool {
     Self::get_config("uda.taskwarrior-tui.task-report.prompt-on-undo", data)
       .unwrap_or_default()
@@ -862,6 +1137,63 @@ impl Config {
       .unwrap_or(4)
   }
 
+  fn get_uda_calendar_date_sources(data: &str) -> Vec<CalendarDateSource> {
+    let Some(sources) = Self::get_config("uda.taskwarrior-tui.calendar.date-sources", data) else {
+      return vec![CalendarDateSource::Due];
+    };
+    sources
+      .split(',')
+      .map(str::trim)
+      .filter(|name| !name.is_empty())
+      .filter_map(|name| {
+        let source = CalendarDateSource::ALL.into_iter().find(|s| s.name() == name);
+        if source.is_none() {
+          warn!("Unknown calendar date source `{}`", name);
+        }
+        source
+      })
+      .collect()
+  }
+
+  fn get_uda_calendar_all_tasks(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.calendar.all-tasks", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_uda_timer_show(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.timer.show", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  fn get_uda_pomodoro_minutes(name: &str, data: &str, default: u64) -> u64 {
+    Self::get_config(&format!("uda.taskwarrior-tui.pomodoro.{}", name), data)
+      .unwrap_or_default()
+      .parse::<u64>()
+      .ok()
+      .filter(|minutes| *minutes > 0)
+      .unwrap_or(default)
+  }
+
+  fn get_uda_pomodoro_annotate(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.pomodoro.annotate", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  fn get_uda_pomodoro_uda(data: &str) -> String {
+    let uda = Self::get_config("uda.taskwarrior-tui.pomodoro.uda", data).unwrap_or_else(|| "pomodoros".to_string());
+    // Modifying a task with an unknown attribute would add it to the description instead
+    if uda.is_empty() || Self::get_config(&format!("uda.{}.type", uda), data).is_none() {
+      return String::new();
+    }
+    uda
+  }
+
   fn get_uda_quick_tag_name(data: &str) -> String {
     let tag_name = Self::get_config("uda.taskwarrior-tui.quick-tag.name", data);
     match tag_name {
@@ -933,6 +1265,38 @@ mod tests {
     assert_eq!(config.color.get("color.uda.jirastatus.To Do"), Some(&Config::get_tcolor("bright white")));
   }
 
+  #[test]
+  fn test_config_collects_udas() {
+    let data = [
+      "data.location /tmp/taskwarrior-tui-tests",
+      "rule.precedence.color uda.,tag.,project.",
+      "uda.priority.type string",
+      "uda.priority.values H,M,L,",
+      "uda.estimate.type numeric",
+      "uda.estimate.label Est",
+      "uda.size.type string",
+      "uda.size.values S,M,L",
+      "uda.size.default M",
+      "uda.taskwarrior-tui.selection.indicator >",
+      "urgency.uda.estimate.coefficient 2.5",
+      "report.next.filter status:pending",
+    ]
+    .join("\n");
+
+    let config = Config::new(&data, "next").unwrap();
+
+    assert_eq!(config.uda.len(), 2);
+    assert_eq!(config.uda[0].name, "estimate");
+    assert_eq!(config.uda[0].label, "Est");
+    assert_eq!(config.uda[0].kind, "numeric");
+    assert_eq!(config.uda[0].values, None);
+    assert_eq!(config.uda[0].urgency, Some(2.5));
+    assert_eq!(config.uda[1].name, "size");
+    assert_eq!(config.uda[1].label, "size");
+    assert_eq!(config.uda[1].values, Some(vec!["S".to_string(), "M".to_string(), "L".to_string()]));
+    assert_eq!(config.uda[1].default, Some("M".to_string()));
+  }
+
   #[test]
   fn test_named_colors_and_backgrounds() {
     // --- Basic named colors ---
@@ -1392,4 +1756,14 @@ mod tests {
     let data = "uda.taskwarrior-tui.context-menu.close-on-select false";
     assert!(!Config::get_uda_context_menu_close_on_select(data));
   }
+
+  #[test]
+  fn test_get_uda_calendar_date_sources() {
+    assert_eq!(Config::get_uda_calendar_date_sources(""), vec![CalendarDateSource::Due]);
+    let data = "uda.taskwarrior-tui.calendar.date-sources scheduled, due,nope,end";
+    assert_eq!(
+      Config::get_uda_calendar_date_sources(data),
+      vec![CalendarDateSource::Scheduled, CalendarDateSource::Due, CalendarDateSource::End]
+    );
+  }
 }
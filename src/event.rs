@@ -1,24 +1,34 @@
 use crossterm::event::{
   KeyCode::{BackTab, Backspace, Char, Delete, Down, End, Enter, Esc, F, Home, Insert, Left, Null, PageDown, PageUp, Right, Tab, Up},
-  KeyEvent, KeyModifiers, MouseEventKind,
+  KeyEvent, KeyModifiers, MouseEvent,
 };
+use std::path::Path;
+
 use futures::StreamExt;
 use log::{Level, LevelFilter, debug, error, info, log_enabled, trace, warn};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use tokio::{
   sync::{mpsc, oneshot},
   task::JoinHandle,
 };
 
+use crate::job::JobOutcome;
+
 #[derive(Debug, Clone)]
 pub enum Event<I> {
   Input(I),
+  Mouse(MouseEvent),
   Paste(String),
   Tick,
+  /// Something in the task data directory changed, e.g. another `task` process, a hook or a sync
+  DataChanged,
+  /// A background job that changes tasks finished
+  JobFinished(JobOutcome),
   Closed,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd, Eq, Hash)]
 pub enum KeyCode {
   CtrlBackspace,
   CtrlDelete,
@@ -50,10 +60,27 @@ pub struct EventLoop {
   pub tx: mpsc::UnboundedSender<Event<KeyCode>>,
   pub abort: mpsc::UnboundedSender<()>,
   pub tick_rate: std::time::Duration,
+  /// Kept alive for as long as the event loop runs; `None` if the data directory could not be watched
+  pub watcher: Option<RecommendedWatcher>,
+}
+
+/// A single `task` command touches the database several times, so changes are batched for this long
+const DATA_CHANGED_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(100);
+
+fn watch_data_location(path: &Path, tx: mpsc::UnboundedSender<()>) -> notify::Result<RecommendedWatcher> {
+  let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+    Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+      tx.send(()).unwrap_or_else(|_| trace!("Unable to send data change notification"));
+    }
+    Ok(_) => {}
+    Err(e) => warn!("Error watching task data directory: {}", e),
+  })?;
+  watcher.watch(path, RecursiveMode::NonRecursive)?;
+  Ok(watcher)
 }
 
 impl EventLoop {
-  pub fn new(tick_rate: Option<std::time::Duration>, init: bool) -> Self {
+  pub fn new(tick_rate: Option<std::time::Duration>, data_location: Option<&Path>, init: bool) -> Self {
     let (tx, rx) = mpsc::unbounded_channel();
     let _tx = tx.clone();
     let should_tick = tick_rate.is_some();
@@ -61,11 +88,22 @@ impl EventLoop {
 
     let (abort, mut abort_recv) = mpsc::unbounded_channel();
 
+    let (watch_tx, mut watch_rx) = mpsc::unbounded_channel();
+    let watcher = match data_location {
+      Some(path) if init => watch_data_location(path, watch_tx)
+        .inspect_err(|e| warn!("Unable to watch {}, falling back to polling: {}", path.display(), e))
+        .ok(),
+      _ => None,
+    };
+
     if init {
       let mut reader = crossterm::event::EventStream::new();
       tokio::spawn(async move {
+        // When to report the changes seen so far, so keys are still handled while they settle
+        let mut data_changed_at: Option<tokio::time::Instant> = None;
         loop {
           let delay = tokio::time::sleep(tick_rate);
+          let data_changed = tokio::time::sleep_until(data_changed_at.unwrap_or_else(tokio::time::Instant::now));
           let event = reader.next();
 
           tokio::select! {
@@ -78,6 +116,13 @@ impl EventLoop {
                   _tx.send(Event::Tick).unwrap_or_else(|_| warn!("Unable to send Tick event"));
               },
               _ = _tx.closed() => break,
+              Some(()) = watch_rx.recv() => {
+                  data_changed_at.get_or_insert_with(|| tokio::time::Instant::now() + DATA_CHANGED_DEBOUNCE);
+              },
+              _ = data_changed, if data_changed_at.is_some() => {
+                  data_changed_at = None;
+                  _tx.send(Event::DataChanged).unwrap_or_else(|_| warn!("Unable to send DataChanged event"));
+              },
               maybe_event = event => {
                   if let Some(Ok(event)) = maybe_event {
                       match event {
@@ -126,19 +171,11 @@ impl EventLoop {
                               _tx.send(Event::Paste(paste)).unwrap_or_else(|_| warn!("Unable to send paste event"));
                           }
                           crossterm::event::Event::Mouse(mouse) => {
-                              // Translate scroll wheel events into synthetic Up/Down keypresses
-                              // so the existing arrow-key handlers in app.rs scroll the task
-                              // list. Other mouse event kinds (clicks, drags, motion) are
-                              // intentionally ignored to avoid interfering with terminal text
-                              // selection.
-                              let key = match mouse.kind {
-                                  MouseEventKind::ScrollUp => Some(KeyCode::Up),
-                                  MouseEventKind::ScrollDown => Some(KeyCode::Down),
-                                  _ => None,
-                              };
-                              if let Some(key) = key {
-                                  _tx.send(Event::Input(key)).unwrap_or_else(|_| warn!("Unable to send {:?} event", key));
-                              }
+                              // Mouse events are forwarded as-is; the app hit-tests them against
+                              // the areas recorded during the last draw. Mouse capture is only
+                              // enabled when `uda.taskwarrior-tui.mouse.enabled` is on, so terminal text
+                              // selection keeps working for users who opt out.
+                              _tx.send(Event::Mouse(mouse)).unwrap_or_else(|_| warn!("Unable to send mouse event"));
                           }
                           _ => {}
                       }
@@ -149,6 +186,30 @@ impl EventLoop {
       });
     }
 
-    Self { tx, rx, tick_rate, abort }
+    Self {
+      tx,
+      rx,
+      tick_rate,
+      abort,
+      watcher,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_watch_data_location_notifies_on_write() {
+    let dir = std::env::temp_dir().join(format!("taskwarrior-tui-watch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let _watcher = watch_data_location(&dir, tx).unwrap();
+
+    std::fs::write(dir.join("taskchampion.sqlite3"), "data").unwrap();
+    let notified = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv()).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(notified, Ok(Some(())));
   }
 }
@@ -0,0 +1,276 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, Days, NaiveDateTime};
+use task_hookrs::{task::Task, uda::UDAValue};
+
+use crate::{datetime, pane::project::NO_PROJECT};
+
+/// What the task report is grouped by, from `uda.taskwarrior-tui.task-report.group-by`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupBy {
+  Project,
+  /// Overdue, today, this week and later, by the `due` date
+  Due,
+  Priority,
+  /// Tasks with the tag, then those without it
+  Tag(String),
+  Uda(String),
+}
+
+impl GroupBy {
+  /// Parses `project`, `due`, `priority`, `tag:<name>` or `+<name>`, or the name of a UDA. Empty means no groups.
+  pub fn parse(s: &str) -> Option<Self> {
+    match s.trim() {
+      "" => None,
+      "project" => Some(Self::Project),
+      "due" => Some(Self::Due),
+      "priority" => Some(Self::Priority),
+      s => match s.strip_prefix("tag:").or_else(|| s.strip_prefix('+')) {
+        Some(tag) => Some(Self::Tag(tag.to_string())),
+        None => Some(Self::Uda(s.to_string())),
+      },
+    }
+  }
+
+  /// The rank that orders the groups, and the label of the group `task` is in
+  fn key(&self, task: &Task, now: NaiveDateTime, start_on_monday: bool, priority_values: &[String]) -> (usize, String) {
+    match self {
+      Self::Project => match task.project() {
+        Some(project) => (0, project.clone()),
+        None => (1, NO_PROJECT.to_string()),
+      },
+      Self::Due => {
+        let Some(due) = task.due() else {
+          return (4, "no due date".to_string());
+        };
+        let today = datetime::local_from_utc(&now).date_naive();
+        let weekday = if start_on_monday {
+          today.weekday().num_days_from_monday()
+        } else {
+          today.weekday().num_days_from_sunday()
+        };
+        let next_week = today + Days::new(7 - u64::from(weekday));
+        let date = datetime::local_from_utc(due).date_naive();
+        if **due < now {
+          (0, "overdue".to_string())
+        } else if date == today {
+          (1, "today".to_string())
+        } else if date < next_week {
+          (2, "this week".to_string())
+        } else {
+          (3, "later".to_string())
+        }
+      }
+      Self::Priority => {
+        let value = task.priority().map(String::as_str).unwrap_or_default();
+        let rank = priority_values.iter().position(|p| p == value).unwrap_or(priority_values.len());
+        (
+          rank,
+          if value.is_empty() {
+            "no priority".to_string()
+          } else {
+            value.to_string()
+          },
+        )
+      }
+      Self::Tag(tag) => {
+        if task.tags().is_some_and(|tags| tags.contains(tag)) {
+          (0, format!("+{}", tag))
+        } else {
+          (1, format!("without +{}", tag))
+        }
+      }
+      Self::Uda(name) => match task.uda().get(name) {
+        Some(UDAValue::Str(s)) => (0, s.clone()),
+        Some(UDAValue::U64(n)) => (0, n.to_string()),
+        Some(UDAValue::F64(n)) => (0, n.to_string()),
+        None => (1, format!("no {}", name)),
+      },
+    }
+  }
+}
+
+/// A run of tasks in the report that are in the same group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskGroup {
+  pub label: String,
+  /// Index of its first task in the report
+  pub start: usize,
+  pub len: usize,
+}
+
+/// A row of the task report, by index into the groups or the tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportRow {
+  Group(usize),
+  Task(usize),
+}
+
+/// The groups of the task report. Folded groups are drawn as their header alone, which the cursor
+/// stops on; the headers of unfolded groups are skipped.
+#[derive(Debug, Default)]
+pub struct TaskGroups {
+  pub by: Option<GroupBy>,
+  pub groups: Vec<TaskGroup>,
+  /// Labels of the folded groups, kept when the report is refreshed
+  pub collapsed: HashSet<String>,
+}
+
+impl TaskGroups {
+  pub fn new(by: Option<GroupBy>) -> Self {
+    Self { by, ..Self::default() }
+  }
+
+  /// Orders `tasks` by group, keeping the order of the report within each group, and records the groups
+  pub fn update(&mut self, tasks: &mut [Task], now: NaiveDateTime, start_on_monday: bool, priority_values: &[String]) {
+    self.groups.clear();
+    let Some(by) = &self.by else {
+      return;
+    };
+    tasks.sort_by_cached_key(|t| by.key(t, now, start_on_monday, priority_values));
+    for (i, task) in tasks.iter().enumerate() {
+      let (_, label) = by.key(task, now, start_on_monday, priority_values);
+      match self.groups.last_mut() {
+        Some(group) if group.label == label => group.len += 1,
+        _ => self.groups.push(TaskGroup { label, start: i, len: 1 }),
+      }
+    }
+  }
+
+  pub fn is_collapsed(&self, group: &TaskGroup) -> bool {
+    self.collapsed.contains(&group.label)
+  }
+
+  pub fn group_of(&self, task: usize) -> Option<&TaskGroup> {
+    self.groups.iter().find(|g| (g.start..g.start + g.len).contains(&task))
+  }
+
+  /// Whether task `i` is folded away. The cursor on a folded group is on its first task, which is hidden too.
+  pub fn is_hidden(&self, i: usize) -> bool {
+    self.group_of(i).is_some_and(|g| self.is_collapsed(g))
+  }
+
+  /// The rows to draw for a report of `len` tasks. Tasks past `len`, left over from before tasks were
+  /// removed and the groups updated, are skipped.
+  pub fn rows(&self, len: usize) -> Vec<ReportRow> {
+    if self.groups.is_empty() {
+      return (0..len).map(ReportRow::Task).collect();
+    }
+    let mut rows = vec![];
+    for (g, group) in self.groups.iter().enumerate().filter(|(_, group)| group.start < len) {
+      rows.push(ReportRow::Group(g));
+      if !self.is_collapsed(group) {
+        rows.extend((group.start..(group.start + group.len).min(len)).map(ReportRow::Task));
+      }
+    }
+    rows
+  }
+
+  /// The tasks the cursor can be on, where a folded group stands for its first task
+  pub fn selectable(&self, len: usize) -> Vec<usize> {
+    if self.groups.is_empty() {
+      return (0..len).collect();
+    }
+    let mut tasks = vec![];
+    for group in self.groups.iter().filter(|group| group.start < len) {
+      if self.is_collapsed(group) {
+        tasks.push(group.start);
+      } else {
+        tasks.extend(group.start..(group.start + group.len).min(len));
+      }
+    }
+    tasks
+  }
+
+  /// The row the cursor is drawn on when it is on task `i`
+  pub fn row_of(&self, i: usize) -> usize {
+    let mut row = 0;
+    for group in &self.groups {
+      if (group.start..group.start + group.len).contains(&i) {
+        return if self.is_collapsed(group) { row } else { row + 1 + i - group.start };
+      }
+      row += 1 + if self.is_collapsed(group) { 0 } else { group.len };
+    }
+    row + i
+  }
+
+  /// Folds or unfolds the group of task `i`, and returns its first task for the cursor to move to
+  pub fn toggle(&mut self, i: usize) -> Option<usize> {
+    let group = self.group_of(i)?.clone();
+    if !self.collapsed.remove(&group.label) {
+      self.collapsed.insert(group.label);
+    }
+    Some(group.start)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_groups_fold_and_skip_headers() {
+    assert_eq!(GroupBy::parse("tag:next"), Some(GroupBy::Tag("next".to_string())));
+    assert_eq!(GroupBy::parse("+next"), Some(GroupBy::Tag("next".to_string())));
+    assert_eq!(GroupBy::parse("estimate"), Some(GroupBy::Uda("estimate".to_string())));
+    assert_eq!(GroupBy::parse(" "), None);
+
+    let now = chrono::Utc::now().naive_utc();
+    let date = |d: NaiveDateTime| d.format("%Y%m%dT%H%M%SZ").to_string();
+    let data = format!(
+      r#"[
+      {{"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","project":"work","due":"{later}"}},
+      {{"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending"}},
+      {{"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"pending","project":"home","due":"{overdue}"}},
+      {{"id":4,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"20260101T000000Z","status":"pending","project":"work","due":"{overdue}"}}
+    ]"#,
+      later = date(now + Duration::days(30)),
+      overdue = date(now - Duration::hours(1)),
+    );
+    let mut tasks = import(data.as_bytes()).unwrap();
+    let ids = |tasks: &[Task]| tasks.iter().map(|t| t.id().unwrap()).collect::<Vec<_>>();
+
+    let mut groups = TaskGroups::new(GroupBy::parse("due"));
+    groups.update(&mut tasks, now, true, &[]);
+    assert_eq!(ids(&tasks), vec![3, 4, 1, 2]);
+    let labels: Vec<&str> = groups.groups.iter().map(|g| g.label.as_str()).collect();
+    assert_eq!(labels, vec!["overdue", "later", "no due date"]);
+
+    // Project groups are alphabetical, with tasks without a project last, and keep the report order
+    groups.by = GroupBy::parse("project");
+    groups.update(&mut tasks, now, true, &[]);
+    assert_eq!(ids(&tasks), vec![3, 4, 1, 2]);
+    assert_eq!(groups.selectable(4), vec![0, 1, 2, 3]);
+    assert_eq!(groups.row_of(1), 3);
+
+    // A folded group is its header alone, which stands for its first task
+    assert_eq!(groups.toggle(2), Some(1));
+    assert_eq!(
+      groups.rows(4),
+      vec![
+        ReportRow::Group(0),
+        ReportRow::Task(0),
+        ReportRow::Group(1),
+        ReportRow::Group(2),
+        ReportRow::Task(3)
+      ]
+    );
+    assert_eq!(groups.selectable(4), vec![0, 1, 3]);
+    assert_eq!(groups.row_of(1), 2);
+    assert_eq!(groups.row_of(3), 4);
+    assert!(groups.is_hidden(1) && groups.is_hidden(2) && !groups.is_hidden(3));
+
+    groups.toggle(1);
+    assert_eq!(groups.rows(4).len(), 7);
+
+    // Groups that still count a removed task do not point past the report
+    assert_eq!(
+      groups.rows(2),
+      vec![ReportRow::Group(0), ReportRow::Task(0), ReportRow::Group(1), ReportRow::Task(1)]
+    );
+    assert_eq!(groups.selectable(2), vec![0, 1]);
+  }
+}
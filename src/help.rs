@@ -10,7 +10,10 @@ use ratatui::{
 
 const TEMPLATE: &str = include_str!("help.tmpl");
 
-use crate::{event::KeyCode, keyconfig::KeyConfig};
+use crate::{
+  command::{Command, key_label, sequence_label},
+  keyconfig::{KeyConfig, KeyScope},
+};
 
 pub struct Help {
   pub title: String,
@@ -20,56 +23,12 @@ pub struct Help {
   pub text: String,
 }
 
-/// Returns the configured KeyCode for a given name string
-fn keycode_for(name: &str, kc: &KeyConfig) -> KeyCode {
-  match name {
-    "quit" => kc.quit,
-    "next_tab" => kc.next_tab,
-    "previous_tab" => kc.previous_tab,
-    "filter" => kc.filter,
-    "add" => kc.add,
-    "done" => kc.done,
-    "edit" => kc.edit,
-    "duplicate" => kc.duplicate,
-    "down" => kc.down,
-    "up" => kc.up,
-    "page_down" => kc.page_down,
-    "page_up" => kc.page_up,
-    "go_to_top" => kc.go_to_top,
-    "go_to_bottom" => kc.go_to_bottom,
-    "log" => kc.log,
-    "modify" => kc.modify,
-    "start_stop" => kc.start_stop,
-    "quick_tag" => kc.quick_tag,
-    "undo" => kc.undo,
-    "select" => kc.select,
-    "select_all" => kc.select_all,
-    "delete" => kc.delete,
-    "zoom" => kc.zoom,
-    "annotate" => kc.annotate,
-    "shell" => kc.shell,
-    "shortcut0" => kc.shortcut0,
-    "shortcut1" => kc.shortcut1,
-    "shortcut2" => kc.shortcut2,
-    "shortcut3" => kc.shortcut3,
-    "shortcut4" => kc.shortcut4,
-    "shortcut5" => kc.shortcut5,
-    "shortcut6" => kc.shortcut6,
-    "shortcut7" => kc.shortcut7,
-    "shortcut8" => kc.shortcut8,
-    "context_menu" => kc.context_menu,
-    "report_menu" => kc.report_menu,
-    "help" => kc.help,
-    _ => KeyCode::Null,
-  }
-}
-
 /// Generates the Help text from the template based on the current
 /// key configuration. Every substring `{{token}}` in the template
-/// is replaced with the character held by `kc.token`.
+/// is replaced with the keys bound to the `Command` named `token`
+/// in the task report, or with one of the prompt keys.
+/// `{{pane.token}}` uses the keys of that pane instead, e.g. `{{timesheet.next_tab}}`.
 fn render_help(kc: &KeyConfig, tmpl: &str) -> String {
-  // NOTE: assumes KeyCode is always a single char (which is currently the case)
-  // this function MUST be updated if that ever changes
   let mut out = String::with_capacity(tmpl.len());
   let mut i = 0;
   while let Some(start) = tmpl[i..].find("{{") {
@@ -78,8 +37,18 @@ fn render_help(kc: &KeyConfig, tmpl: &str) -> String {
     if let Some(end) = tmpl[s + 2..].find("}}") {
       let e = s + 2 + end;
       let name = &tmpl[s + 2..e];
-      if let KeyCode::Char(c) = keycode_for(name, kc) {
-        out.push(c);
+      match name {
+        "prompt_undo" => out.push_str(&key_label(kc.prompt_undo)),
+        "prompt_redo" => out.push_str(&key_label(kc.prompt_redo)),
+        _ => {
+          let (scope, name) = match name.split_once('.') {
+            Some((pane, name)) => (KeyScope::ALL.into_iter().find(|s| s.name() == pane), name),
+            None => (Some(KeyScope::Tasks), name),
+          };
+          if let (Some(scope), Some(command)) = (scope, Command::from_name(name)) {
+            out.push_str(&sequence_label(&kc.sequence(scope, command)));
+          }
+        }
       }
       i = e + 2;
       continue;
@@ -125,3 +94,36 @@ impl Widget for &Help {
       .render(area, buf);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_help_lists_every_command() {
+    for command in Command::ALL {
+      let name = command.name().replace('-', "_");
+      let token = format!("{{{{{}}}}}", name);
+      // Commands of a single pane may be listed with that pane's keys only, e.g. `{{calendar.next_day}}`
+      let pane_token = format!(".{}}}}}", name);
+      assert!(
+        TEMPLATE.contains(&token) || TEMPLATE.contains(&pane_token),
+        "help.tmpl is missing {}",
+        token
+      );
+    }
+  }
+
+  #[test]
+  fn test_render_help_uses_configured_keys() {
+    let kc = KeyConfig::new("uda.taskwarrior-tui.keyconfig.quit Q\nuda.taskwarrior-tui.keyconfig.go-to-top gg").unwrap();
+    let text = render_help(&kc, "{{quit}}: exit, {{go_to_top}}: top, {{command_palette}}: palette, {{unknown}}");
+    assert_eq!(text, "Q: exit, gg: top, Ctrl-p: palette, ");
+    assert_eq!(render_help(&kc, "{{prompt_undo}}/{{prompt_redo}}"), "Ctrl-z/Ctrl-y");
+    assert_eq!(render_help(&kc, "{{timesheet.next_tab}} {{next_tab}}"), "} ]");
+    assert_eq!(render_help(&kc, "{{column-editor.save_columns}}"), "Ctrl-s");
+
+    let kc = KeyConfig::new("uda.taskwarrior-tui.keyconfig.calendar.next-day n").unwrap();
+    assert_eq!(render_help(&kc, "{{calendar.next_day}} {{next_day}}"), "n l");
+  }
+}
@@ -0,0 +1,199 @@
+use std::{collections::VecDeque, io, process::Output, time::Instant};
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// One or more `task` invocations that change tasks, e.g. `task <uuids> done`
+#[derive(Debug)]
+pub struct Job {
+  pub title: String,
+  commands: Vec<std::process::Command>,
+  /// Message shown if a command cannot be run at all
+  spawn_error: String,
+  /// If set, a non-zero exit status is a failure reported with this prefix and the output of `task`
+  failure: Option<String>,
+  /// Tasks that leave the report once the job succeeds, so they can be hidden right away
+  pub removes: Vec<Uuid>,
+}
+
+impl Job {
+  pub fn new(title: impl Into<String>, spawn_error: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      commands: vec![],
+      spawn_error: spawn_error.into(),
+      failure: None,
+      removes: vec![],
+    }
+  }
+
+  pub fn command(mut self, command: std::process::Command) -> Self {
+    self.commands.push(command);
+    self
+  }
+
+  pub fn fails_with(mut self, prefix: impl Into<String>) -> Self {
+    self.failure = Some(prefix.into());
+    self
+  }
+
+  pub fn removes(mut self, uuids: &[Uuid]) -> Self {
+    self.removes = uuids.to_vec();
+    self
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.commands.is_empty()
+  }
+
+  fn check(&self, output: io::Result<Output>) -> Result<(), String> {
+    match output {
+      Ok(o) => match &self.failure {
+        Some(prefix) if !o.status.success() => Err(format!("{} {}", prefix, String::from_utf8_lossy(&o.stdout))),
+        _ => Ok(()),
+      },
+      Err(_) => Err(self.spawn_error.clone()),
+    }
+  }
+
+  /// Runs the commands in order and waits for them, stopping at the first failure
+  pub fn run(mut self) -> Result<(), String> {
+    for mut command in std::mem::take(&mut self.commands) {
+      self.check(command.output())?;
+    }
+    Ok(())
+  }
+
+  async fn run_async(mut self) -> Result<(), String> {
+    for command in std::mem::take(&mut self.commands) {
+      let output = tokio::process::Command::from(command).output().await;
+      self.check(output)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobOutcome {
+  pub title: String,
+  pub result: Result<(), String>,
+}
+
+/// Runs jobs one at a time on a background task, in the order they were pushed,
+/// so taskwarrior never sees two of our changes at once.
+pub struct JobQueue {
+  tx: mpsc::UnboundedSender<Job>,
+  rx: mpsc::UnboundedReceiver<JobOutcome>,
+  /// Titles of the jobs that have not finished yet, oldest first
+  pending: VecDeque<String>,
+  started: Instant,
+}
+
+impl Default for JobQueue {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl JobQueue {
+  pub fn new() -> Self {
+    let (tx, mut jobs) = mpsc::unbounded_channel::<Job>();
+    let (outcomes, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+      while let Some(job) = jobs.recv().await {
+        let title = job.title.clone();
+        let result = job.run_async().await;
+        if outcomes.send(JobOutcome { title, result }).is_err() {
+          break;
+        }
+      }
+    });
+    Self {
+      tx,
+      rx,
+      pending: VecDeque::new(),
+      started: Instant::now(),
+    }
+  }
+
+  pub fn push(&mut self, job: Job) {
+    if self.pending.is_empty() {
+      self.started = Instant::now();
+    }
+    self.pending.push_back(job.title.clone());
+    if let Err(e) = self.tx.send(job) {
+      // The worker only stops when the queue is dropped, so this should never happen
+      log::error!("Unable to queue `{}`", e.0.title);
+      self.pending.pop_back();
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.pending.is_empty()
+  }
+
+  /// Waits for the next job to finish. Never resolves while the queue is empty.
+  pub async fn next(&mut self) -> Option<JobOutcome> {
+    let outcome = self.rx.recv().await?;
+    self.pending.pop_front();
+    Some(outcome)
+  }
+
+  /// A spinner and the title of the running job, e.g. `⠙ done (+2 queued)`
+  pub fn status(&self) -> Option<String> {
+    let title = self.pending.front()?;
+    let frame = SPINNER[(self.started.elapsed().as_millis() / 100) as usize % SPINNER.len()];
+    Some(match self.pending.len() - 1 {
+      0 => format!("{} {}", frame, title),
+      queued => format!("{} {} (+{} queued)", frame, title, queued),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sh(script: &str) -> std::process::Command {
+    let mut command = std::process::Command::new("sh");
+    command.arg("-c").arg(script);
+    command
+  }
+
+  #[tokio::test]
+  async fn test_job_queue_runs_jobs_in_order() {
+    let mut queue = JobQueue::new();
+    assert!(queue.status().is_none());
+
+    queue.push(Job::new("first", "cannot run").command(sh("sleep 0.1")));
+    queue.push(
+      Job::new("second", "cannot run")
+        .command(sh("echo nope; exit 1"))
+        .fails_with("Second failed."),
+    );
+    queue.push(Job::new("third", "cannot run").command(sh("exit 1")));
+    assert!(queue.status().unwrap().ends_with("first (+2 queued)"));
+
+    let outcome = queue.next().await.unwrap();
+    assert_eq!(outcome.title, "first");
+    assert_eq!(outcome.result, Ok(()));
+    assert!(queue.status().unwrap().ends_with("second (+1 queued)"));
+
+    let outcome = queue.next().await.unwrap();
+    assert_eq!(outcome.result, Err("Second failed. nope\n".to_string()));
+
+    // Without `fails_with` only failing to run the command is an error
+    let outcome = queue.next().await.unwrap();
+    assert_eq!(outcome.result, Ok(()));
+    assert!(queue.is_empty());
+  }
+
+  #[test]
+  fn test_job_run_stops_at_first_failure() {
+    let missing = std::process::Command::new("/nonexistent/task");
+    let job = Job::new("start", "Cannot run `task start`").command(missing).command(sh("exit 0"));
+    assert_eq!(job.run(), Err("Cannot run `task start`".to_string()));
+  }
+}
@@ -4,7 +4,196 @@ use anyhow::{Result, anyhow};
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 
-use crate::event::KeyCode;
+use crate::{
+  command::{Command, sequence_label},
+  event::KeyCode,
+};
+
+/// The panes, and the column editor popup, that can have their own `uda.taskwarrior-tui.keyconfig.<pane>.*` section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyScope {
+  Tasks,
+  Projects,
+  Timesheet,
+  Calendar,
+  Agenda,
+  Dependencies,
+  Board,
+  ColumnEditor,
+}
+
+impl KeyScope {
+  pub const ALL: [KeyScope; 8] = [
+    KeyScope::Tasks,
+    KeyScope::Projects,
+    KeyScope::Timesheet,
+    KeyScope::Calendar,
+    KeyScope::Agenda,
+    KeyScope::Dependencies,
+    KeyScope::Board,
+    KeyScope::ColumnEditor,
+  ];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      KeyScope::Tasks => "tasks",
+      KeyScope::Projects => "projects",
+      KeyScope::Timesheet => "timesheet",
+      KeyScope::Calendar => "calendar",
+      KeyScope::Agenda => "agenda",
+      KeyScope::Dependencies => "dependencies",
+      KeyScope::Board => "board",
+      KeyScope::ColumnEditor => "column-editor",
+    }
+  }
+
+  /// Commands the pane responds to; only these take part in its key map
+  pub fn commands(self) -> Vec<Command> {
+    let commands: &[Command] = match self {
+      KeyScope::Tasks => return Command::ALL.into_iter().filter(|c| !Command::PANE_ONLY.contains(c)).collect(),
+      KeyScope::Projects => &[
+        Command::Quit,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::Down,
+        Command::Up,
+        Command::PageDown,
+        Command::PageUp,
+        Command::GoToTop,
+        Command::GoToBottom,
+        Command::Select,
+        Command::Fold,
+        Command::ColumnLeft,
+        Command::ColumnRight,
+        Command::Sort,
+        Command::RenameProject,
+        Command::MergeProject,
+        Command::Obfuscate,
+      ],
+      KeyScope::Timesheet => &[
+        Command::Quit,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::Down,
+        Command::Up,
+        Command::PageDown,
+        Command::PageUp,
+        Command::GoToTop,
+        Command::GoToBottom,
+        Command::Select,
+        Command::Done,
+        Command::Delete,
+        Command::StartStop,
+        Command::StopAll,
+        Command::Undo,
+        Command::Edit,
+        Command::Modify,
+        Command::Annotate,
+        Command::Refresh,
+        Command::WeekView,
+        Command::PreviousPeriod,
+        Command::NextPeriod,
+        Command::Obfuscate,
+      ],
+      KeyScope::Calendar => &[
+        Command::Quit,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::Down,
+        Command::Up,
+        Command::PageDown,
+        Command::PageUp,
+        Command::ScrollDetailsDown,
+        Command::ScrollDetailsUp,
+        Command::Done,
+        Command::Select,
+        Command::PreviousDay,
+        Command::NextDay,
+        Command::PreviousYear,
+        Command::NextYear,
+        Command::SetDue,
+        Command::SetScheduled,
+        Command::Heatmap,
+        Command::Obfuscate,
+      ],
+      KeyScope::Agenda => &[
+        Command::Quit,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::Down,
+        Command::Up,
+        Command::PageDown,
+        Command::PageUp,
+        Command::GoToTop,
+        Command::GoToBottom,
+        Command::Select,
+        Command::PreviousDay,
+        Command::NextDay,
+        Command::WeekView,
+        Command::Obfuscate,
+      ],
+      KeyScope::Dependencies => &[
+        Command::Quit,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::Down,
+        Command::Up,
+        Command::PageDown,
+        Command::PageUp,
+        Command::GoToTop,
+        Command::GoToBottom,
+        Command::Select,
+        Command::AddDependency,
+        Command::RemoveDependency,
+      ],
+      KeyScope::Board => &[
+        Command::Quit,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::Down,
+        Command::Up,
+        Command::GoToTop,
+        Command::GoToBottom,
+        Command::Select,
+        Command::ColumnLeft,
+        Command::ColumnRight,
+        Command::MoveLeft,
+        Command::MoveRight,
+        Command::Obfuscate,
+      ],
+      KeyScope::ColumnEditor => &[
+        Command::Down,
+        Command::Up,
+        Command::AddColumn,
+        Command::RemoveColumn,
+        Command::MoveColumnDown,
+        Command::MoveColumnUp,
+        Command::PreviousFormat,
+        Command::NextFormat,
+        Command::RelabelColumn,
+        Command::SaveColumns,
+      ],
+    };
+    commands.to_vec()
+  }
+}
+
+/// A key sequence bound to a command, either everywhere or only in one pane
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBinding {
+  pub scope: Option<KeyScope>,
+  pub keys: Vec<KeyCode>,
+  pub command: Command,
+}
+
+/// Result of looking up the keys typed so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMatch {
+  Command(Command),
+  /// The keys are the start of one or more longer sequences
+  Pending,
+  Unbound,
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct KeyConfig {
@@ -19,6 +208,8 @@ pub struct KeyConfig {
   pub delete: KeyCode,
   pub done: KeyCode,
   pub start_stop: KeyCode,
+  pub stop_all: KeyCode,
+  pub pomodoro: KeyCode,
   pub quick_tag: KeyCode,
   pub select: KeyCode,
   pub select_all: KeyCode,
@@ -26,6 +217,7 @@ pub struct KeyConfig {
   pub edit: KeyCode,
   pub duplicate: KeyCode,
   pub modify: KeyCode,
+  pub move_to_project: KeyCode,
   pub shell: KeyCode,
   pub log: KeyCode,
   pub add: KeyCode,
@@ -34,6 +226,7 @@ pub struct KeyConfig {
   pub filter: KeyCode,
   pub zoom: KeyCode,
   pub transpose: KeyCode,
+  pub obfuscate: KeyCode,
   pub context_menu: KeyCode,
   pub report_menu: KeyCode,
   pub next_tab: KeyCode,
@@ -42,6 +235,36 @@ pub struct KeyConfig {
   pub priority_m: KeyCode,
   pub priority_l: KeyCode,
   pub priority_n: KeyCode,
+  pub add_dependency: KeyCode,
+  pub remove_dependency: KeyCode,
+  pub column_left: KeyCode,
+  pub column_right: KeyCode,
+  pub move_left: KeyCode,
+  pub move_right: KeyCode,
+  pub previous_day: KeyCode,
+  pub next_day: KeyCode,
+  pub previous_year: KeyCode,
+  pub next_year: KeyCode,
+  pub set_due: KeyCode,
+  pub set_scheduled: KeyCode,
+  pub heatmap: KeyCode,
+  pub week_view: KeyCode,
+  pub previous_period: KeyCode,
+  pub next_period: KeyCode,
+  pub fold: KeyCode,
+  pub sort: KeyCode,
+  pub save_sort: KeyCode,
+  pub edit_columns: KeyCode,
+  pub rename_project: KeyCode,
+  pub merge_project: KeyCode,
+  pub add_column: KeyCode,
+  pub remove_column: KeyCode,
+  pub move_column_down: KeyCode,
+  pub move_column_up: KeyCode,
+  pub previous_format: KeyCode,
+  pub next_format: KeyCode,
+  pub relabel_column: KeyCode,
+  pub save_columns: KeyCode,
   pub shortcut0: KeyCode,
   pub shortcut1: KeyCode,
   pub shortcut2: KeyCode,
@@ -52,6 +275,13 @@ pub struct KeyConfig {
   pub shortcut7: KeyCode,
   pub shortcut8: KeyCode,
   pub shortcut9: KeyCode,
+  pub command_palette: KeyCode,
+  /// Undo and redo edits in the filter, add, modify, annotate and log prompts
+  pub prompt_undo: KeyCode,
+  pub prompt_redo: KeyCode,
+  /// Multi-key sequences and per-pane overrides, on top of the single keys above
+  #[serde(skip)]
+  pub bindings: Vec<KeyBinding>,
 }
 
 impl Default for KeyConfig {
@@ -68,6 +298,8 @@ impl Default for KeyConfig {
       delete: KeyCode::Char('x'),
       done: KeyCode::Char('d'),
       start_stop: KeyCode::Char('s'),
+      stop_all: KeyCode::Char('T'),
+      pomodoro: KeyCode::Char('P'),
       quick_tag: KeyCode::Char('t'),
       select: KeyCode::Char('v'),
       select_all: KeyCode::Char('V'),
@@ -75,6 +307,7 @@ impl Default for KeyConfig {
       edit: KeyCode::Char('e'),
       duplicate: KeyCode::Char('y'),
       modify: KeyCode::Char('m'),
+      move_to_project: KeyCode::Char('p'),
       shell: KeyCode::Char('!'),
       log: KeyCode::Char('l'),
       add: KeyCode::Char('a'),
@@ -83,6 +316,7 @@ impl Default for KeyConfig {
       filter: KeyCode::Char('/'),
       zoom: KeyCode::Char('z'),
       transpose: KeyCode::Char('\\'),
+      obfuscate: KeyCode::Char('O'),
       context_menu: KeyCode::Char('c'),
       report_menu: KeyCode::Char('R'),
       next_tab: KeyCode::Char(']'),
@@ -91,6 +325,36 @@ impl Default for KeyConfig {
       priority_m: KeyCode::Char('M'),
       priority_l: KeyCode::Char('L'),
       priority_n: KeyCode::Char('N'),
+      add_dependency: KeyCode::Char('>'),
+      remove_dependency: KeyCode::Char('<'),
+      column_left: KeyCode::Left,
+      column_right: KeyCode::Right,
+      move_left: KeyCode::Char('h'),
+      move_right: KeyCode::Char('l'),
+      previous_day: KeyCode::Char('h'),
+      next_day: KeyCode::Char('l'),
+      previous_year: KeyCode::Char('H'),
+      next_year: KeyCode::Char('L'),
+      set_due: KeyCode::Char('D'),
+      set_scheduled: KeyCode::Char('S'),
+      heatmap: KeyCode::Char('c'),
+      week_view: KeyCode::Char('W'),
+      previous_period: KeyCode::Char('['),
+      next_period: KeyCode::Char(']'),
+      fold: KeyCode::Char('f'),
+      sort: KeyCode::Char('o'),
+      save_sort: KeyCode::Ctrl('s'),
+      edit_columns: KeyCode::Char('C'),
+      rename_project: KeyCode::Char('R'),
+      merge_project: KeyCode::Char('M'),
+      add_column: KeyCode::Char('a'),
+      remove_column: KeyCode::Char('x'),
+      move_column_down: KeyCode::Char('J'),
+      move_column_up: KeyCode::Char('K'),
+      previous_format: KeyCode::Char('h'),
+      next_format: KeyCode::Char('l'),
+      relabel_column: KeyCode::Char('r'),
+      save_columns: KeyCode::Ctrl('s'),
       shortcut0: KeyCode::Char('0'),
       shortcut1: KeyCode::Char('1'),
       shortcut2: KeyCode::Char('2'),
@@ -101,10 +365,32 @@ impl Default for KeyConfig {
       shortcut7: KeyCode::Char('7'),
       shortcut8: KeyCode::Char('8'),
       shortcut9: KeyCode::Char('9'),
+      command_palette: KeyCode::Ctrl('p'),
+      prompt_undo: KeyCode::Ctrl('z'),
+      prompt_redo: KeyCode::Ctrl('y'),
+      bindings: pane_defaults(),
     }
   }
 }
 
+/// Default keys that only apply in one pane. The Timesheet pane goes through its periods with `[` and `]`,
+/// so there the tabs are switched with `{` and `}`.
+fn pane_defaults() -> Vec<KeyBinding> {
+  [
+    (KeyScope::Timesheet, Command::PreviousPeriod, '['),
+    (KeyScope::Timesheet, Command::NextPeriod, ']'),
+    (KeyScope::Timesheet, Command::PreviousTab, '{'),
+    (KeyScope::Timesheet, Command::NextTab, '}'),
+  ]
+  .into_iter()
+  .map(|(scope, command, key)| KeyBinding {
+    scope: Some(scope),
+    keys: vec![KeyCode::Char(key)],
+    command,
+  })
+  .collect()
+}
+
 impl KeyConfig {
   pub fn new(data: &str) -> Result<Self> {
     let mut kc = Self::default();
@@ -113,153 +399,247 @@ impl KeyConfig {
   }
 
   pub fn update(&mut self, data: &str) -> Result<()> {
-    let quit = Self::get_config("uda.taskwarrior-tui.keyconfig.quit", data);
-    let refresh = Self::get_config("uda.taskwarrior-tui.keyconfig.refresh", data);
-    let go_to_bottom = Self::get_config("uda.taskwarrior-tui.keyconfig.go-to-bottom", data);
-    let go_to_top = Self::get_config("uda.taskwarrior-tui.keyconfig.go-to-top", data);
-    let down = Self::get_config("uda.taskwarrior-tui.keyconfig.down", data);
-    let up = Self::get_config("uda.taskwarrior-tui.keyconfig.up", data);
-    let page_down = Self::get_config("uda.taskwarrior-tui.keyconfig.page-down", data);
-    let page_up = Self::get_config("uda.taskwarrior-tui.keyconfig.page-up", data);
-    let delete = Self::get_config("uda.taskwarrior-tui.keyconfig.delete", data);
-    let done = Self::get_config("uda.taskwarrior-tui.keyconfig.done", data);
-    let start_stop = Self::get_config("uda.taskwarrior-tui.keyconfig.start-stop", data);
-    let quick_tag = Self::get_config("uda.taskwarrior-tui.keyconfig.quick-tag", data);
-    let select = Self::get_config("uda.taskwarrior-tui.keyconfig.select", data);
-    let select_all = Self::get_config("uda.taskwarrior-tui.keyconfig.select-all", data);
-    let undo = Self::get_config("uda.taskwarrior-tui.keyconfig.undo", data);
-    let edit = Self::get_config("uda.taskwarrior-tui.keyconfig.edit", data);
-    let duplicate = Self::get_config("uda.taskwarrior-tui.keyconfig.duplicate", data);
-    let modify = Self::get_config("uda.taskwarrior-tui.keyconfig.modify", data);
-    let shell = Self::get_config("uda.taskwarrior-tui.keyconfig.shell", data);
-    let log = Self::get_config("uda.taskwarrior-tui.keyconfig.log", data);
-    let add = Self::get_config("uda.taskwarrior-tui.keyconfig.add", data);
-    let annotate = Self::get_config("uda.taskwarrior-tui.keyconfig.annotate", data);
-    let filter = Self::get_config("uda.taskwarrior-tui.keyconfig.filter", data);
-    let zoom = Self::get_config("uda.taskwarrior-tui.keyconfig.zoom", data);
-    let transpose = Self::get_config("uda.taskwarrior-tui.keyconfig.transpose", data);
-    let context_menu = Self::get_config("uda.taskwarrior-tui.keyconfig.context-menu", data);
-    let report_menu = Self::get_config("uda.taskwarrior-tui.keyconfig.report-menu", data);
-    let next_tab = Self::get_config("uda.taskwarrior-tui.keyconfig.next-tab", data);
-    let previous_tab = Self::get_config("uda.taskwarrior-tui.keyconfig.previous-tab", data);
-    let shortcut0 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut0", data);
-    let shortcut1 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut1", data);
-    let shortcut2 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut2", data);
-    let shortcut3 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut3", data);
-    let shortcut4 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut4", data);
-    let shortcut5 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut5", data);
-    let shortcut6 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut6", data);
-    let shortcut7 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut7", data);
-    let shortcut8 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut8", data);
-    let shortcut9 = Self::get_config("uda.taskwarrior-tui.keyconfig.shortcut9", data);
-
-    self.quit = quit.unwrap_or(self.quit);
-    self.refresh = refresh.unwrap_or(self.refresh);
-    self.go_to_bottom = go_to_bottom.unwrap_or(self.go_to_bottom);
-    self.go_to_top = go_to_top.unwrap_or(self.go_to_top);
-    self.down = down.unwrap_or(self.down);
-    self.up = up.unwrap_or(self.up);
-    self.page_down = page_down.unwrap_or(self.page_down);
-    self.page_up = page_up.unwrap_or(self.page_up);
-    self.delete = delete.unwrap_or(self.delete);
-    self.done = done.unwrap_or(self.done);
-    self.start_stop = start_stop.unwrap_or(self.start_stop);
-    self.quick_tag = quick_tag.unwrap_or(self.quick_tag);
-    self.select = select.unwrap_or(self.select);
-    self.select_all = select_all.unwrap_or(self.select_all);
-    self.undo = undo.unwrap_or(self.undo);
-    self.edit = edit.unwrap_or(self.edit);
-    self.duplicate = duplicate.unwrap_or(self.duplicate);
-    self.modify = modify.unwrap_or(self.modify);
-    self.shell = shell.unwrap_or(self.shell);
-    self.log = log.unwrap_or(self.log);
-    self.add = add.unwrap_or(self.add);
-    self.annotate = annotate.unwrap_or(self.annotate);
-    self.filter = filter.unwrap_or(self.filter);
-    self.zoom = zoom.unwrap_or(self.zoom);
-    self.transpose = transpose.unwrap_or(self.transpose);
-    self.context_menu = context_menu.unwrap_or(self.context_menu);
-    self.report_menu = report_menu.unwrap_or(self.report_menu);
-    self.next_tab = next_tab.unwrap_or(self.next_tab);
-    self.previous_tab = previous_tab.unwrap_or(self.previous_tab);
-    self.shortcut0 = shortcut0.unwrap_or(self.shortcut0);
-    self.shortcut1 = shortcut1.unwrap_or(self.shortcut1);
-    self.shortcut2 = shortcut2.unwrap_or(self.shortcut2);
-    self.shortcut3 = shortcut3.unwrap_or(self.shortcut3);
-    self.shortcut4 = shortcut4.unwrap_or(self.shortcut4);
-    self.shortcut5 = shortcut5.unwrap_or(self.shortcut5);
-    self.shortcut6 = shortcut6.unwrap_or(self.shortcut6);
-    self.shortcut7 = shortcut7.unwrap_or(self.shortcut7);
-    self.shortcut8 = shortcut8.unwrap_or(self.shortcut8);
-    self.shortcut9 = shortcut9.unwrap_or(self.shortcut9);
+    let mut configured = HashSet::new();
+    for command in Command::ALL {
+      let config = format!("uda.taskwarrior-tui.keyconfig.{}", command.name());
+      if let Some(keys) = Self::get_config(&config, data) {
+        configured.insert(command);
+        // A key picked for every pane replaces the pane defaults of the command too
+        self.bindings.retain(|b| b.command != command);
+        if let [key] = keys[..] {
+          if let Some(binding) = command.key_mut(self) {
+            *binding = key;
+          }
+        } else if let Some(binding) = command.key_mut(self) {
+          // Sequences live in `bindings`, so the single key must not fire on its own
+          *binding = KeyCode::Null;
+          self.bindings.push(KeyBinding { scope: None, keys, command });
+        }
+      }
+      for scope in KeyScope::ALL {
+        let config = format!("uda.taskwarrior-tui.keyconfig.{}.{}", scope.name(), command.name());
+        if let Some(keys) = Self::get_config(&config, data) {
+          if scope.commands().contains(&command) {
+            configured.insert(command);
+            self.bindings.push(KeyBinding {
+              scope: Some(scope),
+              keys,
+              command,
+            });
+          } else {
+            warn!("{} has no effect, the {} pane does not use {}", config, scope.name(), command.name());
+          }
+        }
+      }
+    }
+
+    // Prompt keys are only active while typing, so they are kept out of the command registry
+    let mut prompt_configured = [false; 2];
+    for ((name, binding), configured) in [("prompt-undo", &mut self.prompt_undo), ("prompt-redo", &mut self.prompt_redo)]
+      .into_iter()
+      .zip(&mut prompt_configured)
+    {
+      let config = format!("uda.taskwarrior-tui.keyconfig.{}", name);
+      match Self::get_config(&config, data).as_deref() {
+        Some([key]) => {
+          *binding = *key;
+          *configured = true;
+        }
+        Some(_) => error!("{} must be a single key", config),
+        None => {}
+      }
+    }
+    if self.prompt_undo == self.prompt_redo {
+      match prompt_configured {
+        [true, false] => {
+          warn!("prompt-undo is bound to the default key of prompt-redo, which is left unbound");
+          self.prompt_redo = KeyCode::Null;
+        }
+        [false, true] => {
+          warn!("prompt-redo is bound to the default key of prompt-undo, which is left unbound");
+          self.prompt_undo = KeyCode::Null;
+        }
+        _ => {}
+      }
+    }
 
+    self.drop_colliding_defaults(&configured);
     self.check()
   }
 
+  /// Unbinds the keys of `Command::NEWER_DEFAULTS` and the pane defaults that collide with a key the user
+  /// bound, pane by pane, so that key configs written before those defaults existed keep working
+  fn drop_colliding_defaults(&mut self, configured: &HashSet<Command>) {
+    for scope in KeyScope::ALL {
+      let keymap = self.keymap(scope);
+      for (keys, command) in &keymap {
+        // Commands the user did not bind only have pane defaults in `bindings`
+        let pane_default = self.bindings.iter().any(|b| b.command == *command && b.scope == Some(scope));
+        if configured.contains(command) || !(Command::NEWER_DEFAULTS.contains(command) || pane_default) {
+          continue;
+        }
+        let collision = keymap
+          .iter()
+          .find(|(other, c)| configured.contains(c) && (other.starts_with(keys) || keys.starts_with(other)));
+        if let Some((other, c)) = collision {
+          warn!(
+            "`{}` is bound to {} in the key config, so {} is left without a key in the {} pane",
+            sequence_label(other),
+            c.name(),
+            command.name(),
+            scope.name()
+          );
+          self.bindings.retain(|b| !(b.command == *command && b.scope == Some(scope)));
+          self.bindings.push(KeyBinding {
+            scope: Some(scope),
+            keys: vec![KeyCode::Null],
+            command: *command,
+          });
+        }
+      }
+    }
+  }
+
+  /// The keys that run `command` in `scope`: a pane override, else a global sequence, else the single key
+  pub fn sequence(&self, scope: KeyScope, command: Command) -> Vec<KeyCode> {
+    self
+      .bindings
+      .iter()
+      .find(|b| b.command == command && b.scope == Some(scope))
+      .or_else(|| self.bindings.iter().find(|b| b.command == command && b.scope.is_none()))
+      .map(|b| b.keys.clone())
+      .unwrap_or_else(|| vec![command.key(self)])
+  }
+
+  /// Every key sequence that is active in `scope`, including the fixed alternate keys
+  pub fn keymap(&self, scope: KeyScope) -> Vec<(Vec<KeyCode>, Command)> {
+    scope
+      .commands()
+      .into_iter()
+      .flat_map(|command| {
+        std::iter::once(self.sequence(scope, command))
+          .chain(command.alternate_key().map(|k| vec![k]))
+          .filter(|keys| !keys.contains(&KeyCode::Null))
+          .map(move |keys| (keys, command))
+      })
+      .collect()
+  }
+
+  pub fn resolve(&self, scope: KeyScope, keys: &[KeyCode]) -> KeyMatch {
+    let mut pending = false;
+    for (sequence, command) in self.keymap(scope) {
+      if sequence == keys {
+        return KeyMatch::Command(command);
+      }
+      pending |= sequence.starts_with(keys);
+    }
+    if pending { KeyMatch::Pending } else { KeyMatch::Unbound }
+  }
+
   pub fn check(&self) -> Result<()> {
-    let mut elements = vec![
-      &self.quit,
-      &self.refresh,
-      &self.go_to_bottom,
-      &self.go_to_top,
-      &self.down,
-      &self.up,
-      &self.page_down,
-      &self.page_up,
-      &self.delete,
-      &self.done,
-      &self.select,
-      &self.select_all,
-      &self.start_stop,
-      &self.quick_tag,
-      &self.undo,
-      &self.edit,
-      &self.duplicate,
-      &self.modify,
-      &self.shell,
-      &self.log,
-      &self.add,
-      &self.annotate,
-      &self.help,
-      &self.filter,
-      &self.zoom,
-      &self.transpose,
-      &self.context_menu,
-      &self.report_menu,
-      &self.next_tab,
-      &self.previous_tab,
-    ];
-    let l = elements.len();
-    elements.dedup();
-    if l == elements.len() {
-      Ok(())
-    } else {
-      Err(anyhow!("Duplicate keys found in key config"))
+    if self.prompt_undo == self.prompt_redo {
+      return Err(anyhow!(
+        "Duplicate keys found in key config: `{}` is bound to both prompt-undo and prompt-redo",
+        sequence_label(&[self.prompt_undo])
+      ));
+    }
+    for scope in KeyScope::ALL {
+      let keymap = self.keymap(scope);
+      for (i, (keys, _)) in keymap.iter().enumerate() {
+        for (other, _) in &keymap[i + 1..] {
+          if other == keys {
+            return Err(anyhow!(
+              "Duplicate keys found in key config: `{}` is bound to more than one action in the {} pane",
+              sequence_label(keys),
+              scope.name()
+            ));
+          } else if other.starts_with(keys) || keys.starts_with(other) {
+            let (short, long) = if keys.len() < other.len() { (keys, other) } else { (other, keys) };
+            return Err(anyhow!(
+              "Conflicting keys found in key config: `{}` is the start of `{}` in the {} pane",
+              sequence_label(short),
+              sequence_label(long),
+              scope.name()
+            ));
+          }
+        }
+      }
     }
+    Ok(())
   }
 
-  fn get_config(config: &str, data: &str) -> Option<KeyCode> {
+  fn get_config(config: &str, data: &str) -> Option<Vec<KeyCode>> {
+    let underscored = config.replace('-', "_");
     for line in data.split('\n') {
-      if line.starts_with(config) {
-        let line = line.trim_start_matches(config).trim_start().trim_end().to_string();
-        if has_just_one_char(&line) {
-          return Some(KeyCode::Char(line.chars().next().unwrap()));
-        } else {
-          error!("Found multiple characters in {} for {}", line, config);
-        }
-      } else if line.starts_with(&config.replace('-', "_")) {
-        let line = line.trim_start_matches(&config.replace('-', "_")).trim_start().trim_end().to_string();
-        if has_just_one_char(&line) {
-          return Some(KeyCode::Char(line.chars().next().unwrap()));
-        } else {
-          error!("Found multiple characters in {} for {}", line, config);
-        }
+      // Require whitespace after the key so that e.g. `select` does not match `select-all`
+      let Some(value) = line
+        .strip_prefix(config)
+        .or_else(|| line.strip_prefix(underscored.as_str()))
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+      else {
+        continue;
+      };
+      let value = value.trim();
+      if has_just_one_char(value) {
+        return Some(vec![KeyCode::Char(value.chars().next().unwrap())]);
+      }
+      match parse_keys(value) {
+        Some(keys) => return Some(keys),
+        None => error!("Unable to parse key sequence {} for {}", value, config),
       }
     }
     None
   }
 }
 
+/// Parses a vim style key sequence such as `gg`, `<Space>p` or `<C-x>d`
+fn parse_keys(value: &str) -> Option<Vec<KeyCode>> {
+  let mut keys = vec![];
+  let mut rest = value;
+  while let Some(c) = rest.chars().next() {
+    let special = rest
+      .strip_prefix('<')
+      .and_then(|r| r.split_once('>'))
+      .and_then(|(name, tail)| parse_special_key(name).map(|k| (k, tail)));
+    if let Some((key, tail)) = special {
+      keys.push(key);
+      rest = tail;
+    } else if c.is_whitespace() {
+      return None;
+    } else {
+      keys.push(KeyCode::Char(c));
+      rest = &rest[c.len_utf8()..];
+    }
+  }
+  if keys.is_empty() { None } else { Some(keys) }
+}
+
+fn parse_special_key(name: &str) -> Option<KeyCode> {
+  let lower = name.to_lowercase();
+  let modified = |prefix: &str| {
+    lower
+      .strip_prefix(prefix)
+      .filter(|c| has_just_one_char(c))
+      .and_then(|_| name.chars().last())
+  };
+  match lower.as_str() {
+    "space" => Some(KeyCode::Char(' ')),
+    "cr" | "enter" | "return" => Some(KeyCode::Char('\n')),
+    "tab" => Some(KeyCode::Tab),
+    "bs" | "backspace" => Some(KeyCode::Backspace),
+    "lt" => Some(KeyCode::Char('<')),
+    "gt" => Some(KeyCode::Char('>')),
+    "left" => Some(KeyCode::Left),
+    "right" => Some(KeyCode::Right),
+    "up" => Some(KeyCode::Up),
+    "down" => Some(KeyCode::Down),
+    _ => modified("c-")
+      .map(KeyCode::Ctrl)
+      .or_else(|| modified("a-").map(KeyCode::Alt))
+      .or_else(|| modified("m-").map(KeyCode::Alt)),
+  }
+}
+
 fn has_just_one_char(s: &str) -> bool {
   let mut chars = s.chars();
   chars.next().is_some() && chars.next().is_none()
@@ -268,4 +648,178 @@ fn has_just_one_char(s: &str) -> bool {
 #[cfg(test)]
 mod tests {
   use super::*;
+
+  #[test]
+  fn test_keyconfig_update_from_registry() {
+    let data = [
+      "uda.taskwarrior-tui.keyconfig.select-all S",
+      "uda.taskwarrior-tui.keyconfig.select w",
+      "uda.taskwarrior-tui.keyconfig.shortcut9 n",
+    ]
+    .join("\n");
+    let kc = KeyConfig::new(&data).unwrap();
+    assert_eq!(kc.select, KeyCode::Char('w'));
+    assert_eq!(kc.select_all, KeyCode::Char('S'));
+    assert_eq!(kc.shortcut9, KeyCode::Char('n'));
+  }
+
+  #[test]
+  fn test_keyconfig_rejects_duplicate_keys() {
+    assert!(KeyConfig::default().check().is_ok());
+    assert!(KeyConfig::new("uda.taskwarrior-tui.keyconfig.shortcut1 q").is_err());
+  }
+
+  #[test]
+  fn test_keyconfig_resolve() {
+    let kc = KeyConfig::default();
+    let resolve = |key| kc.resolve(KeyScope::Tasks, &[key]);
+    assert_eq!(resolve(KeyCode::Char('q')), KeyMatch::Command(Command::Quit));
+    assert_eq!(resolve(KeyCode::Ctrl('c')), KeyMatch::Command(Command::Quit));
+    assert_eq!(resolve(KeyCode::Down), KeyMatch::Command(Command::Down));
+    assert_eq!(resolve(KeyCode::Char(':')), KeyMatch::Command(Command::Jump));
+    assert_eq!(resolve(KeyCode::Ctrl('p')), KeyMatch::Command(Command::Palette));
+    assert_eq!(resolve(KeyCode::Char('0')), KeyMatch::Command(Command::Shortcut0));
+    assert_eq!(resolve(KeyCode::Char('_')), KeyMatch::Unbound);
+    assert_eq!(kc.resolve(KeyScope::Projects, &[KeyCode::Char('d')]), KeyMatch::Unbound);
+  }
+
+  #[test]
+  fn test_keyconfig_sequences() {
+    let data = [
+      "uda.taskwarrior-tui.keyconfig.go-to-top gg",
+      "uda.taskwarrior-tui.keyconfig.done dd",
+      "uda.taskwarrior-tui.keyconfig.next-tab gt",
+      "uda.taskwarrior-tui.keyconfig.command-palette <Space>p",
+    ]
+    .join("\n");
+    let kc = KeyConfig::new(&data).unwrap();
+    let g = KeyCode::Char('g');
+    assert_eq!(kc.go_to_top, KeyCode::Null);
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[g]), KeyMatch::Pending);
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[g, g]), KeyMatch::Command(Command::GoToTop));
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[g, KeyCode::Char('t')]), KeyMatch::Command(Command::NextTab));
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[g, KeyCode::Char('x')]), KeyMatch::Unbound);
+    assert_eq!(
+      kc.resolve(KeyScope::Tasks, &[KeyCode::Char(' '), KeyCode::Char('p')]),
+      KeyMatch::Command(Command::Palette)
+    );
+    assert_eq!(kc.sequence(KeyScope::Projects, Command::NextTab), vec![g, KeyCode::Char('t')]);
+
+    // `g` alone would shadow `gg`
+    let data = "uda.taskwarrior-tui.keyconfig.go-to-top gg\nuda.taskwarrior-tui.keyconfig.shortcut1 g";
+    assert!(KeyConfig::new(data).is_err());
+  }
+
+  #[test]
+  fn test_keyconfig_scoped_sections() {
+    let data = [
+      "uda.taskwarrior-tui.keyconfig.projects.select d",
+      "uda.taskwarrior-tui.keyconfig.projects.down <C-n>",
+      "uda.taskwarrior-tui.keyconfig.calendar.quit Q",
+    ]
+    .join("\n");
+    let kc = KeyConfig::new(&data).unwrap();
+    let d = KeyCode::Char('d');
+    assert_eq!(kc.resolve(KeyScope::Projects, &[d]), KeyMatch::Command(Command::Select));
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[d]), KeyMatch::Command(Command::Done));
+    assert_eq!(kc.resolve(KeyScope::Projects, &[KeyCode::Ctrl('n')]), KeyMatch::Command(Command::Down));
+    assert_eq!(kc.resolve(KeyScope::Projects, &[KeyCode::Char('j')]), KeyMatch::Unbound);
+    assert_eq!(kc.resolve(KeyScope::Projects, &[KeyCode::Down]), KeyMatch::Command(Command::Down));
+    assert_eq!(kc.resolve(KeyScope::Calendar, &[KeyCode::Char('Q')]), KeyMatch::Command(Command::Quit));
+    assert_eq!(kc.resolve(KeyScope::Calendar, &[KeyCode::Char('q')]), KeyMatch::Unbound);
+
+    // Duplicates are only reported within a pane
+    assert!(KeyConfig::new("uda.taskwarrior-tui.keyconfig.projects.select j").is_err());
+  }
+
+  #[test]
+  fn test_keyconfig_prompt_keys() {
+    let kc = KeyConfig::new("uda.taskwarrior-tui.keyconfig.prompt-undo <C-u>").unwrap();
+    assert_eq!(kc.prompt_undo, KeyCode::Ctrl('u'));
+    assert_eq!(kc.prompt_redo, KeyCode::Ctrl('y'));
+
+    // A prompt key set to the default of the other one unbinds that default
+    let kc = KeyConfig::new("uda.taskwarrior-tui.keyconfig.prompt-undo <C-y>").unwrap();
+    assert_eq!((kc.prompt_undo, kc.prompt_redo), (KeyCode::Ctrl('y'), KeyCode::Null));
+    let data = "uda.taskwarrior-tui.keyconfig.prompt-undo <C-u>\nuda.taskwarrior-tui.keyconfig.prompt-redo <C-u>";
+    assert!(KeyConfig::new(data).is_err());
+  }
+
+  #[test]
+  fn test_keyconfig_newer_defaults_yield_to_user_keys() {
+    // `P` and `O` are the defaults of pomodoro and obfuscate, which came after these could be bound
+    let data = "uda.taskwarrior-tui.keyconfig.shortcut1 P\nuda.taskwarrior-tui.keyconfig.projects.select O";
+    let kc = KeyConfig::new(data).unwrap();
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[KeyCode::Char('P')]), KeyMatch::Command(Command::Shortcut1));
+    assert_eq!(kc.resolve(KeyScope::Projects, &[KeyCode::Char('O')]), KeyMatch::Command(Command::Select));
+    // Obfuscate keeps its key where it does not collide
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[KeyCode::Char('O')]), KeyMatch::Command(Command::Obfuscate));
+
+    // Older defaults still have to be unbound explicitly
+    assert!(KeyConfig::new("uda.taskwarrior-tui.keyconfig.shortcut1 q").is_err());
+
+    // Pane defaults give way too
+    let kc = KeyConfig::new("uda.taskwarrior-tui.keyconfig.timesheet.select {").unwrap();
+    assert_eq!(kc.resolve(KeyScope::Timesheet, &[KeyCode::Char('{')]), KeyMatch::Command(Command::Select));
+    assert_eq!(
+      kc.resolve(KeyScope::Timesheet, &[KeyCode::Char('}')]),
+      KeyMatch::Command(Command::NextTab)
+    );
+  }
+
+  #[test]
+  fn test_keyconfig_pane_defaults() {
+    let kc = KeyConfig::default();
+    let (open, close) = (KeyCode::Char('['), KeyCode::Char(']'));
+    assert_eq!(kc.resolve(KeyScope::Timesheet, &[open]), KeyMatch::Command(Command::PreviousPeriod));
+    assert_eq!(kc.resolve(KeyScope::Timesheet, &[close]), KeyMatch::Command(Command::NextPeriod));
+    assert_eq!(
+      kc.resolve(KeyScope::Timesheet, &[KeyCode::Char('}')]),
+      KeyMatch::Command(Command::NextTab)
+    );
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[close]), KeyMatch::Command(Command::NextTab));
+
+    // A key set for every pane replaces the pane default
+    let kc = KeyConfig::new("uda.taskwarrior-tui.keyconfig.next-tab <Tab>").unwrap();
+    assert_eq!(kc.resolve(KeyScope::Timesheet, &[KeyCode::Tab]), KeyMatch::Command(Command::NextTab));
+    assert_eq!(kc.resolve(KeyScope::Timesheet, &[KeyCode::Char('}')]), KeyMatch::Unbound);
+  }
+
+  #[test]
+  fn test_keyconfig_column_editor() {
+    let kc = KeyConfig::default();
+    let resolve = |key| kc.resolve(KeyScope::ColumnEditor, &[key]);
+    assert_eq!(resolve(KeyCode::Char('r')), KeyMatch::Command(Command::RelabelColumn));
+    assert_eq!(resolve(KeyCode::Left), KeyMatch::Command(Command::PreviousFormat));
+    assert_eq!(resolve(KeyCode::Ctrl('s')), KeyMatch::Command(Command::SaveColumns));
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[KeyCode::Char('r')]), KeyMatch::Command(Command::Refresh));
+
+    // Rebinding a task report command leaves the editor alone, and the editor takes sequences and its own section
+    let data = [
+      "uda.taskwarrior-tui.keyconfig.rename-project E",
+      "uda.taskwarrior-tui.keyconfig.relabel-column cl",
+      "uda.taskwarrior-tui.keyconfig.column-editor.remove-column d",
+    ]
+    .join("\n");
+    let kc = KeyConfig::new(&data).unwrap();
+    let (c, l) = (KeyCode::Char('c'), KeyCode::Char('l'));
+    assert_eq!(kc.resolve(KeyScope::ColumnEditor, &[KeyCode::Char('E')]), KeyMatch::Unbound);
+    assert_eq!(kc.resolve(KeyScope::ColumnEditor, &[c, l]), KeyMatch::Command(Command::RelabelColumn));
+    assert_eq!(
+      kc.resolve(KeyScope::ColumnEditor, &[KeyCode::Char('d')]),
+      KeyMatch::Command(Command::RemoveColumn)
+    );
+    assert_eq!(kc.resolve(KeyScope::Tasks, &[KeyCode::Char('d')]), KeyMatch::Command(Command::Done));
+  }
+
+  #[test]
+  fn test_parse_keys() {
+    assert_eq!(parse_keys("gg"), Some(vec![KeyCode::Char('g'), KeyCode::Char('g')]));
+    assert_eq!(parse_keys("<Space>p"), Some(vec![KeyCode::Char(' '), KeyCode::Char('p')]));
+    assert_eq!(parse_keys("<C-x>d"), Some(vec![KeyCode::Ctrl('x'), KeyCode::Char('d')]));
+    assert_eq!(parse_keys("<lt>"), Some(vec![KeyCode::Char('<')]));
+    assert_eq!(parse_keys("<Left>"), Some(vec![KeyCode::Left]));
+    assert_eq!(parse_keys("<nope>"), Some("<nope>".chars().map(KeyCode::Char).collect()));
+    assert_eq!(parse_keys("a b"), None);
+  }
 }
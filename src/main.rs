@@ -7,17 +7,25 @@ mod action;
 mod app;
 mod calendar;
 mod cli;
+mod command;
 mod completion;
 mod config;
 mod datetime;
 mod event;
+mod group;
 mod help;
 mod history;
+mod job;
 mod keyconfig;
+mod mouse;
 mod pane;
+mod pomodoro;
 mod scrollbar;
+mod sort;
 mod table;
+mod task_editor;
 mod task_report;
+mod timer;
 mod ui;
 mod utils;
 
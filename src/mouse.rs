@@ -0,0 +1,170 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::{Position, Rect};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Mode;
+
+/// Two clicks on the same cell within this interval count as a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Rows taken up by the header and the header gap of a `table::Table`
+pub const TABLE_HEADER_HEIGHT: u16 = 2;
+
+/// Areas drawn during the last call to `TaskwarriorTui::draw`, used to map mouse
+/// events back to the widget underneath the cursor.
+#[derive(Debug, Default)]
+pub struct ClickTargets {
+  pub tabs: Vec<(Rect, Mode)>,
+  pub task_report: Option<Rect>,
+  /// Widths of the columns shown in the task report
+  pub task_report_columns: Vec<usize>,
+  pub context_menu: Option<Rect>,
+  pub report_menu: Option<Rect>,
+  last_click: Option<(Instant, Position)>,
+}
+
+impl ClickTargets {
+  /// Forget every area recorded by the previous frame.
+  /// The double-click tracker is kept so that it survives redraws.
+  pub fn clear(&mut self) {
+    self.tabs.clear();
+    self.task_report = None;
+    self.task_report_columns.clear();
+    self.context_menu = None;
+    self.report_menu = None;
+  }
+
+  /// Records the area of each tab title, laid out the same way as `ratatui::widgets::Tabs`
+  /// with a single space of padding on both sides and a one column divider.
+  pub fn set_tabs(&mut self, area: Rect, titles: &[(&str, Mode)]) {
+    self.tabs.clear();
+    let mut x = area.x;
+    for (title, mode) in titles {
+      if x >= area.right() {
+        break;
+      }
+      let width = (title.width() as u16 + 2).min(area.right() - x);
+      self.tabs.push((Rect::new(x, area.y, width, 1), mode.clone()));
+      x = x.saturating_add(width + 1);
+    }
+  }
+
+  pub fn tab_at(&self, position: Position) -> Option<Mode> {
+    self.tabs.iter().find(|(r, _)| r.contains(position)).map(|(_, m)| m.clone())
+  }
+
+  /// Returns true if `position` was clicked twice in quick succession.
+  /// A detected double-click resets the tracker so a third click starts over.
+  pub fn register_click(&mut self, position: Position) -> bool {
+    let now = Instant::now();
+    let double = matches!(self.last_click, Some((t, p)) if p == position && now.duration_since(t) <= DOUBLE_CLICK_INTERVAL);
+    self.last_click = if double { None } else { Some((now, position)) };
+    double
+  }
+}
+
+/// Maps a position inside a table drawn in `area` to the index of the row under it.
+/// `offset` is the scroll offset of the table state and `len` the number of rows.
+pub fn table_row_at(area: Rect, offset: usize, len: usize, position: Position) -> Option<usize> {
+  if !area.contains(position) || position.y < area.y + TABLE_HEADER_HEIGHT {
+    return None;
+  }
+  let index = offset + (position.y - area.y - TABLE_HEADER_HEIGHT) as usize;
+  (index < len).then_some(index)
+}
+
+/// Like `table_row_at`, for a table whose rows take up `heights` lines each
+pub fn table_row_at_heights(area: Rect, offset: usize, heights: &[usize], position: Position) -> Option<usize> {
+  if !area.contains(position) || position.y < area.y + TABLE_HEADER_HEIGHT {
+    return None;
+  }
+  let mut y = (position.y - area.y - TABLE_HEADER_HEIGHT) as usize;
+  for (i, height) in heights.iter().enumerate().skip(offset) {
+    if y < *height {
+      return Some(i);
+    }
+    y -= height;
+  }
+  None
+}
+
+/// Maps a position on the header of a table drawn in `area` to the index of the column under it.
+/// `widths` are the widths of the columns, which are one cell apart.
+pub fn table_column_at(area: Rect, widths: &[usize], position: Position) -> Option<usize> {
+  if !area.contains(position) || position.y != area.y {
+    return None;
+  }
+  let mut x = usize::from(area.x);
+  for (i, width) in widths.iter().enumerate() {
+    if (x..x + width).contains(&usize::from(position.x)) {
+      return Some(i);
+    }
+    x += width + 1;
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::action::Action;
+
+  #[test]
+  fn test_tab_at_matches_padded_titles() {
+    let mut targets = ClickTargets::default();
+    targets.set_tabs(
+      Rect::new(0, 0, 80, 1),
+      &[("Tasks", Mode::Tasks(Action::Report)), ("Projects", Mode::Projects)],
+    );
+    assert_eq!(targets.tab_at(Position::new(0, 0)), Some(Mode::Tasks(Action::Report)));
+    assert_eq!(targets.tab_at(Position::new(6, 0)), Some(Mode::Tasks(Action::Report)));
+    assert_eq!(targets.tab_at(Position::new(7, 0)), None);
+    assert_eq!(targets.tab_at(Position::new(8, 0)), Some(Mode::Projects));
+    assert_eq!(targets.tab_at(Position::new(30, 0)), None);
+  }
+
+  #[test]
+  fn test_table_row_at_skips_header_and_applies_offset() {
+    let area = Rect::new(0, 1, 40, 10);
+    assert_eq!(table_row_at(area, 0, 5, Position::new(3, 1)), None);
+    assert_eq!(table_row_at(area, 0, 5, Position::new(3, 2)), None);
+    assert_eq!(table_row_at(area, 0, 5, Position::new(3, 3)), Some(0));
+    assert_eq!(table_row_at(area, 4, 10, Position::new(3, 5)), Some(6));
+    assert_eq!(table_row_at(area, 0, 5, Position::new(3, 9)), None);
+    assert_eq!(table_row_at(area, 0, 5, Position::new(50, 3)), None);
+  }
+
+  #[test]
+  fn test_table_row_at_heights_spans_multi_line_rows() {
+    let area = Rect::new(0, 1, 40, 10);
+    let heights = [1, 3, 2];
+    assert_eq!(table_row_at_heights(area, 0, &heights, Position::new(3, 3)), Some(0));
+    assert_eq!(table_row_at_heights(area, 0, &heights, Position::new(3, 4)), Some(1));
+    assert_eq!(table_row_at_heights(area, 0, &heights, Position::new(3, 6)), Some(1));
+    assert_eq!(table_row_at_heights(area, 0, &heights, Position::new(3, 7)), Some(2));
+    assert_eq!(table_row_at_heights(area, 1, &heights, Position::new(3, 6)), Some(2));
+    assert_eq!(table_row_at_heights(area, 0, &heights, Position::new(3, 9)), None);
+  }
+
+  #[test]
+  fn test_table_column_at_maps_header_cells() {
+    let area = Rect::new(2, 1, 40, 10);
+    let widths = [3, 10, 5];
+    assert_eq!(table_column_at(area, &widths, Position::new(2, 1)), Some(0));
+    assert_eq!(table_column_at(area, &widths, Position::new(5, 1)), None);
+    assert_eq!(table_column_at(area, &widths, Position::new(6, 1)), Some(1));
+    assert_eq!(table_column_at(area, &widths, Position::new(17, 1)), Some(2));
+    assert_eq!(table_column_at(area, &widths, Position::new(30, 1)), None);
+    assert_eq!(table_column_at(area, &widths, Position::new(6, 2)), None);
+  }
+
+  #[test]
+  fn test_register_click_detects_double_click_on_same_cell() {
+    let mut targets = ClickTargets::default();
+    assert!(!targets.register_click(Position::new(1, 1)));
+    assert!(targets.register_click(Position::new(1, 1)));
+    assert!(!targets.register_click(Position::new(1, 1)));
+    assert!(!targets.register_click(Position::new(2, 1)));
+  }
+}
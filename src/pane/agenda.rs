@@ -0,0 +1,262 @@
+use anyhow::Result;
+use chrono::{Datelike, Days, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use task_hookrs::{status::TaskStatus, task::Task};
+use uuid::Uuid;
+
+use crate::{app::TaskwarriorTui, command::Command, datetime, pane::Pane};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgendaKind {
+  Scheduled,
+  Due,
+  /// A started task, drawn in every hour from its start until now; `first` is the hour it was started in
+  Active {
+    first: bool,
+  },
+}
+
+/// A task placed in an hour of the agenda
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgendaEntry {
+  pub uuid: Uuid,
+  pub day: NaiveDate,
+  pub hour: u32,
+  pub minute: u32,
+  pub kind: AgendaKind,
+}
+
+/// The cursor of the Agenda pane, which lays out a day or a week on an hour grid
+pub struct AgendaState {
+  pub day: NaiveDate,
+  pub hour: u32,
+  /// Which of the entries in the cursor's hour is selected
+  pub item: usize,
+  /// Show the whole week of `day` instead of only `day`
+  pub week: bool,
+  /// The entry of a task picked up to be moved to another hour
+  pub grabbed: Option<AgendaEntry>,
+  pub scroll: u16,
+}
+
+impl AgendaState {
+  pub(crate) fn new() -> Self {
+    let now = Local::now();
+    Self {
+      day: now.date_naive(),
+      hour: now.hour(),
+      item: 0,
+      week: false,
+      grabbed: None,
+      scroll: 0,
+    }
+  }
+
+  /// The days shown, in order
+  pub fn days(&self, start_on_monday: bool) -> Vec<NaiveDate> {
+    if !self.week {
+      return vec![self.day];
+    }
+    let offset = if start_on_monday {
+      self.day.weekday().num_days_from_monday()
+    } else {
+      self.day.weekday().num_days_from_sunday()
+    };
+    let first = self.day - Days::new(u64::from(offset));
+    (0..7).map(|i| first + Days::new(i)).collect()
+  }
+
+  /// Places the tasks that are scheduled, due or active on the days shown. `now` is the local time.
+  pub fn entries(&self, tasks: &[Task], start_on_monday: bool, now: NaiveDateTime) -> Vec<AgendaEntry> {
+    let days = self.days(start_on_monday);
+    let mut entries = vec![];
+    for task in tasks {
+      for (kind, date) in [(AgendaKind::Scheduled, task.scheduled()), (AgendaKind::Due, task.due())] {
+        let Some(local) = date.map(|d| datetime::local_from_utc(d).naive_local()) else {
+          continue;
+        };
+        if days.contains(&local.date()) {
+          entries.push(AgendaEntry {
+            uuid: *task.uuid(),
+            day: local.date(),
+            hour: local.hour(),
+            minute: local.minute(),
+            kind,
+          });
+        }
+      }
+      if *task.status() != TaskStatus::Pending {
+        continue;
+      }
+      let Some(start) = task.start().map(|d| datetime::local_from_utc(d).naive_local()) else {
+        continue;
+      };
+      // A task started before the first day shown fills it from midnight
+      let mut hour = start.max(days[0].and_hms_opt(0, 0, 0).unwrap_or(start));
+      hour = hour.with_minute(0).and_then(|h| h.with_second(0)).unwrap_or(hour);
+      while hour <= now && hour.date() <= days[days.len() - 1] {
+        let first = hour.date() == start.date() && hour.hour() == start.hour();
+        entries.push(AgendaEntry {
+          uuid: *task.uuid(),
+          day: hour.date(),
+          hour: hour.hour(),
+          minute: if first { start.minute() } else { 0 },
+          kind: AgendaKind::Active { first },
+        });
+        hour += Duration::hours(1);
+      }
+    }
+    entries.sort_by_key(|e| (e.day, e.hour, e.minute));
+    entries
+  }
+
+  pub fn slot(entries: &[AgendaEntry], day: NaiveDate, hour: u32) -> Vec<&AgendaEntry> {
+    entries.iter().filter(|e| e.day == day && e.hour == hour).collect()
+  }
+
+  pub fn selected<'a>(&self, entries: &'a [AgendaEntry]) -> Option<&'a AgendaEntry> {
+    Self::slot(entries, self.day, self.hour).get(self.item).copied()
+  }
+
+  /// Moves the cursor through the hours, and through the entries of an hour unless a task is being moved
+  pub fn move_cursor(&mut self, command: Command, entries: &[AgendaEntry]) {
+    let count = |day, hour| Self::slot(entries, day, hour).len();
+    let step_items = self.grabbed.is_none();
+    match command {
+      Command::Down if step_items && self.item + 1 < count(self.day, self.hour) => self.item += 1,
+      Command::Down if self.hour < 23 => {
+        self.hour += 1;
+        self.item = 0;
+      }
+      Command::Up if step_items && self.item > 0 => self.item -= 1,
+      Command::Up if self.hour > 0 => {
+        self.hour -= 1;
+        self.item = if step_items { count(self.day, self.hour).saturating_sub(1) } else { 0 };
+      }
+      Command::PreviousDay => self.day = self.day - Days::new(1),
+      Command::NextDay => self.day = self.day + Days::new(1),
+      Command::PageUp => self.day = self.day - Days::new(7),
+      Command::PageDown => self.day = self.day + Days::new(7),
+      Command::GoToTop => self.hour = 0,
+      Command::GoToBottom => self.hour = 23,
+      _ => {}
+    }
+    self.item = self.item.min(count(self.day, self.hour).saturating_sub(1));
+  }
+
+  /// `minute` past the hour under the cursor, in UTC
+  pub fn slot_time(&self, minute: u32) -> Option<NaiveDateTime> {
+    let local = self.day.and_hms_opt(self.hour, minute, 0)?;
+    Local.from_local_datetime(&local).earliest().map(|t| t.naive_utc())
+  }
+}
+
+impl Pane for AgendaState {
+  fn handle_command(app: &mut TaskwarriorTui, command: Command) -> Result<()> {
+    match command {
+      Command::Quit => app.should_quit = true,
+      Command::NextTab => app.next_tab(),
+      Command::Obfuscate => app.toggle_obfuscate(),
+      Command::PreviousTab => app.previous_tab(),
+      Command::WeekView => app.agenda.week = !app.agenda.week,
+      _ => {
+        let entries = app.agenda_entries();
+        app.agenda.move_cursor(command, &entries);
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  /// A local time as it is stored in a task
+  fn utc(day: NaiveDate, hour: u32, minute: u32) -> String {
+    let local = Local.from_local_datetime(&day.and_hms_opt(hour, minute, 0).unwrap()).earliest().unwrap();
+    local.naive_utc().format("%Y%m%dT%H%M%SZ").to_string()
+  }
+
+  fn tasks(day: NaiveDate) -> Vec<Task> {
+    let data = format!(
+      r#"[
+      {{"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","scheduled":"{}"}},
+      {{"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","due":"{}"}},
+      {{"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"pending","start":"{}"}},
+      {{"id":4,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"20260101T000000Z","status":"pending","scheduled":"{}"}}
+    ]"#,
+      utc(day, 9, 30),
+      utc(day, 9, 0),
+      utc(day, 10, 15),
+      utc(day + Days::new(1), 9, 0),
+    );
+    import(data.as_bytes()).unwrap()
+  }
+
+  #[test]
+  fn test_agenda_places_tasks_in_hours() {
+    let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let tasks = tasks(day);
+    let mut state = AgendaState::new();
+    state.day = day;
+    state.hour = 9;
+    let now = day.and_hms_opt(12, 5, 0).unwrap();
+
+    let placed: Vec<(u128, u32, AgendaKind)> = state
+      .entries(&tasks, true, now)
+      .iter()
+      .map(|e| (e.uuid.as_u128() >> 124, e.hour, e.kind))
+      .collect();
+    assert_eq!(
+      placed,
+      vec![
+        (2, 9, AgendaKind::Due),
+        (1, 9, AgendaKind::Scheduled),
+        (3, 10, AgendaKind::Active { first: true }),
+        (3, 11, AgendaKind::Active { first: false }),
+        (3, 12, AgendaKind::Active { first: false }),
+      ]
+    );
+
+    // The week view also shows tomorrow's task
+    state.week = true;
+    assert_eq!(state.entries(&tasks, true, now).len(), 6);
+  }
+
+  #[test]
+  fn test_agenda_cursor_and_slot_time() {
+    let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let tasks = tasks(day);
+    let mut state = AgendaState::new();
+    state.day = day;
+    state.hour = 8;
+    let entries = state.entries(&tasks, true, day.and_hms_opt(12, 5, 0).unwrap());
+
+    // Both tasks at 9 are visited before moving on to 10
+    state.move_cursor(Command::Down, &entries);
+    assert_eq!((state.hour, state.item), (9, 0));
+    state.move_cursor(Command::Down, &entries);
+    assert_eq!((state.hour, state.item), (9, 1));
+    assert_eq!(state.selected(&entries).unwrap().kind, AgendaKind::Scheduled);
+    state.move_cursor(Command::Down, &entries);
+    assert_eq!((state.hour, state.item), (10, 0));
+    state.move_cursor(Command::Up, &entries);
+    assert_eq!((state.hour, state.item), (9, 1));
+
+    // A picked up task moves by whole hours
+    state.grabbed = state.selected(&entries).cloned();
+    state.move_cursor(Command::Up, &entries);
+    assert_eq!((state.hour, state.item), (8, 0));
+    state.move_cursor(Command::NextDay, &entries);
+    assert_eq!(
+      datetime::format_taskwarrior_datetime_literal(&state.slot_time(30).unwrap()),
+      Local
+        .from_local_datetime(&NaiveDate::from_ymd_opt(2026, 3, 11).unwrap().and_hms_opt(8, 30, 0).unwrap())
+        .unwrap()
+        .format("'%Y-%m-%dT%H:%M:%S%:z'")
+        .to_string()
+    );
+  }
+}
@@ -0,0 +1,286 @@
+use anyhow::Result;
+use chrono::Utc;
+use task_hookrs::{status::TaskStatus, task::Task, uda::UDAValue};
+use uuid::Uuid;
+
+use crate::{app::TaskwarriorTui, command::Command, config::BoardGroup, pane::Pane};
+
+const STATUS_COLUMNS: [&str; 4] = ["pending", "active", "waiting", "completed"];
+
+pub struct BoardColumn {
+  /// The status, tag or UDA value of the column; empty for tasks that have none
+  pub value: String,
+  pub tasks: Vec<Uuid>,
+  pub selection: usize,
+}
+
+impl BoardColumn {
+  pub fn title(&self) -> &str {
+    if self.value.is_empty() { "(none)" } else { &self.value }
+  }
+}
+
+/// The tasks of the current report laid out in columns by status, tag or UDA value
+pub struct BoardState {
+  pub group: BoardGroup,
+  pub columns: Vec<BoardColumn>,
+  pub focus: usize,
+}
+
+impl BoardState {
+  pub(crate) fn new() -> Self {
+    Self {
+      group: BoardGroup::Status,
+      columns: vec![],
+      focus: 0,
+    }
+  }
+
+  fn column_values(group: &BoardGroup, columns: &[String]) -> Vec<String> {
+    match group {
+      BoardGroup::Status => STATUS_COLUMNS.map(String::from).to_vec(),
+      // Tasks without any of the tags are the backlog, so they come first
+      BoardGroup::Tags => std::iter::once(String::new()).chain(columns.iter().cloned()).collect(),
+      BoardGroup::Uda(_) => {
+        let mut values = columns.to_vec();
+        if !values.iter().any(String::is_empty) {
+          values.push(String::new());
+        }
+        values
+      }
+    }
+  }
+
+  /// The value of the column `task` belongs in, or `None` if it is not shown on the board
+  fn value_of(&self, task: &Task) -> Option<String> {
+    match &self.group {
+      BoardGroup::Status => {
+        let waiting = task.wait().is_some_and(|w| **w > Utc::now().naive_utc());
+        let status = match task.status() {
+          TaskStatus::Deleted => return None,
+          TaskStatus::Completed => "completed",
+          TaskStatus::Waiting => "waiting",
+          _ if waiting => "waiting",
+          _ if task.start().is_some() => "active",
+          _ => "pending",
+        };
+        Some(status.to_string())
+      }
+      BoardGroup::Tags => Some(
+        self
+          .columns
+          .iter()
+          .map(|c| &c.value)
+          .find(|v| !v.is_empty() && task.tags().is_some_and(|tags| tags.contains(v)))
+          .cloned()
+          .unwrap_or_default(),
+      ),
+      BoardGroup::Uda(name) => {
+        let value = match task.uda().get(name) {
+          Some(UDAValue::Str(s)) => s.clone(),
+          Some(UDAValue::U64(n)) => n.to_string(),
+          Some(UDAValue::F64(n)) => n.to_string(),
+          None => String::new(),
+        };
+        // Values that are not a column go to the column for tasks without one
+        Some(if self.columns.iter().any(|c| c.value == value) {
+          value
+        } else {
+          String::new()
+        })
+      }
+    }
+  }
+
+  /// Rebuilds the columns from the report's tasks, keeping the focus on `current` if it is on the board
+  pub fn update_data(&mut self, tasks: &[Task], group: &BoardGroup, columns: &[String], current: Option<Uuid>) {
+    let selections: Vec<(String, usize)> = self.columns.iter().map(|c| (c.value.clone(), c.selection)).collect();
+    self.group = group.clone();
+    self.columns = Self::column_values(group, columns)
+      .into_iter()
+      .map(|value| BoardColumn {
+        selection: selections.iter().find(|(v, _)| *v == value).map(|(_, s)| *s).unwrap_or_default(),
+        value,
+        tasks: vec![],
+      })
+      .collect();
+    for task in tasks {
+      if let Some(value) = self.value_of(task)
+        && let Some(column) = self.columns.iter_mut().find(|c| c.value == value)
+      {
+        column.tasks.push(*task.uuid());
+      }
+    }
+    for (i, column) in self.columns.iter_mut().enumerate() {
+      if let Some(j) = current.and_then(|uuid| column.tasks.iter().position(|t| *t == uuid)) {
+        column.selection = j;
+        self.focus = i;
+      }
+      column.selection = column.selection.min(column.tasks.len().saturating_sub(1));
+    }
+    self.focus = self.focus.min(self.columns.len().saturating_sub(1));
+  }
+
+  pub fn selected(&self) -> Option<Uuid> {
+    let column = self.columns.get(self.focus)?;
+    column.tasks.get(column.selection).copied()
+  }
+
+  /// Arguments for the `task <uuid> ...` invocations that move `task` into column `to`
+  pub fn move_args(&self, task: &Task, to: usize) -> Vec<Vec<String>> {
+    let (Some(from), Some(to)) = (self.value_of(task), self.columns.get(to).map(|c| c.value.clone())) else {
+      return vec![];
+    };
+    if from == to {
+      return vec![];
+    }
+    let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<String>>();
+    match &self.group {
+      BoardGroup::Status => {
+        let mut commands = vec![];
+        match from.as_str() {
+          "active" => commands.push(args(&["stop"])),
+          "waiting" => commands.push(args(&["modify", "wait:"])),
+          "completed" => commands.push(args(&["modify", "status:pending"])),
+          _ => {}
+        }
+        match to.as_str() {
+          "active" => commands.push(args(&["start"])),
+          "waiting" => commands.push(args(&["modify", "wait:someday"])),
+          "completed" => commands.push(args(&["done"])),
+          _ => {}
+        }
+        commands
+      }
+      BoardGroup::Tags => {
+        let mut modify = args(&["modify"]);
+        if !from.is_empty() {
+          modify.push(format!("-{}", from));
+        }
+        if !to.is_empty() {
+          modify.push(format!("+{}", to));
+        }
+        vec![modify]
+      }
+      BoardGroup::Uda(name) => vec![vec!["modify".to_string(), format!("{}:{}", name, to)]],
+    }
+  }
+}
+
+impl Pane for BoardState {
+  fn handle_command(app: &mut TaskwarriorTui, command: Command) -> Result<()> {
+    let board = &mut app.board;
+    let last_column = board.columns.len().saturating_sub(1);
+    if let Some(column) = board.columns.get_mut(board.focus) {
+      let last = column.tasks.len().saturating_sub(1);
+      match command {
+        Command::Down => column.selection = (column.selection + 1).min(last),
+        Command::Up => column.selection = column.selection.saturating_sub(1),
+        Command::GoToTop => column.selection = 0,
+        Command::GoToBottom => column.selection = last,
+        _ => {}
+      }
+    }
+    match command {
+      Command::Quit => app.should_quit = true,
+      Command::NextTab => app.next_tab(),
+      Command::Obfuscate => app.toggle_obfuscate(),
+      Command::PreviousTab => app.previous_tab(),
+      Command::ColumnLeft => board.focus = board.focus.saturating_sub(1),
+      Command::ColumnRight => board.focus = (board.focus + 1).min(last_column),
+      Command::Select if select_in_report(app) => {
+        app.task_table_state.multiple_selection();
+        app.toggle_mark();
+      }
+      _ => {}
+    }
+    select_in_report(app);
+    Ok(())
+  }
+}
+
+/// Moves the task report's selection to the card under the cursor
+pub fn select_in_report(app: &mut TaskwarriorTui) -> bool {
+  let Some(uuid) = app.board.selected() else {
+    return false;
+  };
+  match app.tasks.iter().position(|t| *t.uuid() == uuid) {
+    Some(i) => {
+      app.task_report_select(i);
+      true
+    }
+    None => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  fn tasks() -> Vec<Task> {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","tags":["review"]},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","start":"20260102T000000Z","state":"doing"},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"waiting","wait":"29990101T000000Z","state":"other"},
+      {"id":4,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"20260101T000000Z","status":"pending","tags":["later","review"]}
+    ]"#;
+    import(data.as_bytes()).unwrap()
+  }
+
+  fn columns(board: &BoardState) -> Vec<(String, Vec<u128>)> {
+    board
+      .columns
+      .iter()
+      .map(|c| (c.title().to_string(), c.tasks.iter().map(|u| u.as_u128() >> 124).collect()))
+      .collect()
+  }
+
+  #[test]
+  fn test_board_groups_by_status_tags_and_uda() {
+    let tasks = tasks();
+    let mut board = BoardState::new();
+
+    board.update_data(&tasks, &BoardGroup::Status, &[], None);
+    assert_eq!(
+      columns(&board),
+      vec![
+        ("pending".to_string(), vec![1, 4]),
+        ("active".to_string(), vec![2]),
+        ("waiting".to_string(), vec![3]),
+        ("completed".to_string(), vec![]),
+      ]
+    );
+    assert_eq!(board.move_args(&tasks[1], 3), vec![vec!["stop"], vec!["done"]]);
+    assert_eq!(board.move_args(&tasks[2], 1), vec![vec!["modify", "wait:"], vec!["start"]]);
+
+    // A task with several tags goes in the first matching column
+    let tags = ["review".to_string(), "later".to_string()];
+    board.update_data(&tasks, &BoardGroup::Tags, &tags, Some(tasks[3].uuid().to_owned()));
+    assert_eq!(
+      columns(&board),
+      vec![
+        ("(none)".to_string(), vec![2, 3]),
+        ("review".to_string(), vec![1, 4]),
+        ("later".to_string(), vec![]),
+      ]
+    );
+    assert_eq!((board.focus, board.columns[1].selection), (1, 1));
+    assert_eq!(board.move_args(&tasks[3], 2), vec![vec!["modify", "-review", "+later"]]);
+    assert_eq!(board.move_args(&tasks[1], 1), vec![vec!["modify", "+review"]]);
+
+    let values = ["todo".to_string(), "doing".to_string()];
+    board.update_data(&tasks, &BoardGroup::Uda("state".to_string()), &values, None);
+    assert_eq!(
+      columns(&board),
+      vec![
+        ("todo".to_string(), vec![]),
+        ("doing".to_string(), vec![2]),
+        ("(none)".to_string(), vec![1, 3, 4]),
+      ]
+    );
+    assert_eq!(board.move_args(&tasks[0], 0), vec![vec!["modify", "state:todo"]]);
+    assert_eq!(board.move_args(&tasks[1], 2), vec![vec!["modify", "state:"]]);
+  }
+}
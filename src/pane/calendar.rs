@@ -0,0 +1,170 @@
+use anyhow::Result;
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use task_hookrs::{status::TaskStatus, task::Task};
+
+use crate::{app::TaskwarriorTui, command::Command, datetime, pane::Pane};
+
+/// The day cursor of the Calendar pane
+pub struct CalendarState {
+  pub date: NaiveDate,
+  /// Whether the tasks on `date` are listed next to the calendar
+  pub agenda: bool,
+  /// Whether completed tasks are drawn as a heatmap instead of the months of the year
+  pub heatmap: bool,
+}
+
+impl CalendarState {
+  pub(crate) fn new() -> Self {
+    Self {
+      date: Local::now().date_naive(),
+      agenda: false,
+      heatmap: false,
+    }
+  }
+
+  /// Moves the cursor for one of the calendar's navigation commands; other commands are ignored
+  pub fn move_cursor(&mut self, command: Command) {
+    // The heatmap has a column per week, so there left and right move by a week and up and down by a day
+    let (across, down) = if self.heatmap { (7, 1) } else { (1, 7) };
+    let date = match command {
+      Command::PreviousDay => self.date.checked_sub_days(Days::new(across)),
+      Command::NextDay => self.date.checked_add_days(Days::new(across)),
+      Command::Up => self.date.checked_sub_days(Days::new(down)),
+      Command::Down => self.date.checked_add_days(Days::new(down)),
+      Command::PageUp => self.date.checked_sub_months(Months::new(1)),
+      Command::PageDown => self.date.checked_add_months(Months::new(1)),
+      Command::PreviousYear => self.date.checked_sub_months(Months::new(12)),
+      Command::NextYear => self.date.checked_add_months(Months::new(12)),
+      _ => None,
+    };
+    // The calendar cannot draw years before 0
+    if let Some(date) = date.filter(|d| d.year() >= 0) {
+      self.date = date;
+    }
+  }
+
+  /// The tasks that are due, scheduled or waiting until the selected day, with the date that matched.
+  /// On the heatmap, the tasks that were completed that day.
+  pub fn agenda<'a>(&self, tasks: &[&'a Task]) -> Vec<(&'static str, &'a Task)> {
+    let on_date = |date: Option<&task_hookrs::date::Date>| date.is_some_and(|d| datetime::local_from_utc(d).date_naive() == self.date);
+    if self.heatmap {
+      return tasks
+        .iter()
+        .filter(|t| *t.status() == TaskStatus::Completed && on_date(t.end()))
+        .map(|&t| ("done", t))
+        .collect();
+    }
+    let mut entries = vec![];
+    for &task in tasks {
+      for (kind, date) in [("due", task.due()), ("scheduled", task.scheduled()), ("wait", task.wait())] {
+        if on_date(date) {
+          entries.push((kind, task));
+        }
+      }
+    }
+    entries
+  }
+}
+
+impl Pane for CalendarState {
+  fn handle_command(app: &mut TaskwarriorTui, command: Command) -> Result<()> {
+    match command {
+      Command::Quit => app.should_quit = true,
+      Command::NextTab => app.next_tab(),
+      Command::Obfuscate => app.toggle_obfuscate(),
+      Command::PreviousTab => app.previous_tab(),
+      Command::Select => app.calendar.agenda = !app.calendar.agenda,
+      Command::Heatmap => {
+        app.calendar.heatmap = !app.calendar.heatmap;
+        // Completed tasks are usually not in the report, so the heatmap reads them from `task export all`
+        if app.calendar.heatmap {
+          app.export_all_tasks()?;
+        }
+      }
+      _ => {
+        app.calendar.move_cursor(command);
+        app.calendar_year = app.calendar.date.year();
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_calendar_cursor_crosses_months_and_years() {
+    let mut state = CalendarState::new();
+    state.date = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+
+    state.move_cursor(Command::PageDown);
+    assert_eq!(state.date, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    state.move_cursor(Command::NextYear);
+    assert_eq!(state.date, NaiveDate::from_ymd_opt(2025, 2, 28).unwrap());
+    state.move_cursor(Command::Down);
+    assert_eq!(state.date, NaiveDate::from_ymd_opt(2025, 3, 7).unwrap());
+    state.move_cursor(Command::PreviousDay);
+    state.move_cursor(Command::Up);
+    assert_eq!(state.date, NaiveDate::from_ymd_opt(2025, 2, 27).unwrap());
+
+    state.date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+    state.move_cursor(Command::NextDay);
+    assert_eq!(state.date, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+
+    state.date = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+    state.move_cursor(Command::PreviousDay);
+    assert_eq!(state.date, NaiveDate::from_ymd_opt(0, 1, 1).unwrap());
+  }
+
+  #[test]
+  fn test_calendar_agenda() {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","due":"20260310T120000Z"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending",
+       "scheduled":"20260310T120000Z","due":"20260310T120000Z"},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"waiting","wait":"20260310T120000Z"},
+      {"id":4,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"20260101T000000Z","status":"pending","due":"20260311T120000Z"}
+    ]"#;
+    let tasks = import(data.as_bytes()).unwrap();
+    let mut state = CalendarState::new();
+    state.date = datetime::local_from_utc(tasks[0].due().unwrap()).date_naive();
+
+    let agenda: Vec<(&str, &str)> = state
+      .agenda(&tasks.iter().collect::<Vec<_>>())
+      .into_iter()
+      .map(|(kind, t)| (kind, t.description().as_str()))
+      .collect();
+    assert_eq!(agenda, vec![("due", "a"), ("due", "b"), ("scheduled", "b"), ("wait", "c")]);
+  }
+
+  #[test]
+  fn test_calendar_heatmap_lists_completed_tasks() {
+    let data = r#"[
+      {"id":0,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"completed","end":"20260310T120000Z"},
+      {"id":0,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"deleted","end":"20260310T120000Z"},
+      {"id":1,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"pending","due":"20260310T120000Z"}
+    ]"#;
+    let tasks = import(data.as_bytes()).unwrap();
+    let mut state = CalendarState::new();
+    state.heatmap = true;
+    state.date = datetime::local_from_utc(tasks[0].end().unwrap()).date_naive();
+
+    let agenda: Vec<(&str, &str)> = state
+      .agenda(&tasks.iter().collect::<Vec<_>>())
+      .into_iter()
+      .map(|(kind, t)| (kind, t.description().as_str()))
+      .collect();
+    assert_eq!(agenda, vec![("done", "a")]);
+
+    // Weeks are columns, so left and right move a week
+    let date = state.date;
+    state.move_cursor(Command::NextDay);
+    assert_eq!(state.date, date + Days::new(7));
+    state.move_cursor(Command::Up);
+    assert_eq!(state.date, date + Days::new(6));
+  }
+}
@@ -0,0 +1,208 @@
+use crate::{
+  config::Uda,
+  table::TaskwarriorTuiTableState,
+  task_report::{COLUMN_FORMATS, default_label, uda_formats},
+};
+
+/// What the keys of the column editor do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnEditorMode {
+  Columns,
+  /// Picking a column to add after the selected one
+  Add,
+  /// Typing a new label for the selected column
+  Rename,
+}
+
+/// A column that can be added, with its formats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvailableColumn {
+  pub name: String,
+  pub formats: Vec<String>,
+  pub label: String,
+}
+
+/// A popup to add, remove, reorder and relabel the columns of the current report
+pub struct ColumnEditorState {
+  pub table_state: TaskwarriorTuiTableState,
+  pub available_state: TaskwarriorTuiTableState,
+  pub mode: ColumnEditorMode,
+  pub columns: Vec<String>,
+  pub labels: Vec<String>,
+  pub available: Vec<AvailableColumn>,
+  /// The label being typed
+  pub label: String,
+  /// The columns picked for the session before the editor was opened, brought back if it is cancelled
+  pub original: Option<(Vec<String>, Vec<String>)>,
+}
+
+impl ColumnEditorState {
+  pub(crate) fn new() -> Self {
+    Self {
+      table_state: TaskwarriorTuiTableState::default(),
+      available_state: TaskwarriorTuiTableState::default(),
+      mode: ColumnEditorMode::Columns,
+      columns: vec![],
+      labels: vec![],
+      available: vec![],
+      label: String::new(),
+      original: None,
+    }
+  }
+
+  pub fn open(&mut self, columns: &[String], labels: &[String], udas: &[Uda]) {
+    self.columns = columns.to_vec();
+    self.labels = labels.to_vec();
+    self.available = COLUMN_FORMATS
+      .iter()
+      .map(|(name, formats)| AvailableColumn {
+        name: name.to_string(),
+        formats: formats.iter().map(ToString::to_string).collect(),
+        label: default_label(name),
+      })
+      .chain(udas.iter().map(|uda| AvailableColumn {
+        name: uda.name.clone(),
+        formats: uda_formats(&uda.kind).iter().map(ToString::to_string).collect(),
+        label: if uda.label.is_empty() {
+          default_label(&uda.name)
+        } else {
+          uda.label.clone()
+        },
+      }))
+      .collect();
+    self.mode = ColumnEditorMode::Columns;
+    self.label.clear();
+    self.table_state.select(Some(0));
+    self.available_state.select(Some(0));
+  }
+
+  pub fn selected(&self) -> Option<usize> {
+    self.table_state.current_selection().filter(|i| *i < self.columns.len())
+  }
+
+  fn select(&mut self, i: usize) {
+    self.table_state.select(Some(i.min(self.columns.len().saturating_sub(1))));
+  }
+
+  /// Moves the cursor by one column, wrapping around
+  pub fn next(&mut self, forward: bool) {
+    let n = self.columns.len();
+    if n > 0 {
+      let i = self.table_state.current_selection().unwrap_or(0);
+      self.select(if forward { (i + 1) % n } else { (i + n - 1) % n });
+    }
+  }
+
+  /// Swaps the selected column with the one before or after it
+  pub fn move_selected(&mut self, forward: bool) {
+    let Some(i) = self.selected() else {
+      return;
+    };
+    let j = if forward { i + 1 } else { i.wrapping_sub(1) };
+    if j < self.columns.len() {
+      self.columns.swap(i, j);
+      self.labels.swap(i, j);
+      self.select(j);
+    }
+  }
+
+  /// Removes the selected column, unless it is the last one
+  pub fn remove_selected(&mut self) {
+    if let Some(i) = self.selected().filter(|_| self.columns.len() > 1) {
+      self.columns.remove(i);
+      self.labels.remove(i);
+      self.select(i);
+    }
+  }
+
+  /// Adds the picked column after the selected one, in its default format
+  pub fn add_picked(&mut self) {
+    let Some(column) = self.available_state.current_selection().and_then(|i| self.available.get(i)) else {
+      return;
+    };
+    let (name, label) = (column.name.clone(), column.label.clone());
+    let i = self.selected().map_or(0, |i| i + 1);
+    self.columns.insert(i, name);
+    self.labels.insert(i, label);
+    self.select(i);
+  }
+
+  /// The formats of column `column`, e.g. `due.relative`, and the index of the one it uses
+  pub fn formats(&self, column: &str) -> Option<(&[String], usize)> {
+    let (name, format) = column.split_once('.').unwrap_or((column, ""));
+    let formats = &self.available.iter().find(|c| c.name == name)?.formats;
+    Some((formats, formats.iter().position(|f| f == format).unwrap_or(0)))
+  }
+
+  /// Switches the selected column to its next or previous format
+  pub fn cycle_format(&mut self, forward: bool) {
+    let Some(i) = self.selected() else {
+      return;
+    };
+    let Some((formats, current)) = self.formats(&self.columns[i]) else {
+      return;
+    };
+    let n = formats.len();
+    let next = if forward { (current + 1) % n } else { (current + n - 1) % n };
+    let name = self.columns[i].split('.').next().unwrap_or_default();
+    self.columns[i] = if next == 0 {
+      name.to_string()
+    } else {
+      format!("{}.{}", name, formats[next])
+    };
+  }
+
+  /// Gives the selected column the typed label; commas are dropped since labels are separated by them
+  pub fn rename_selected(&mut self) {
+    if let Some(i) = self.selected() {
+      self.labels[i] = self.label.replace(',', "").trim().to_string();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_column_editor_edits() {
+    let udas = [Uda {
+      name: "estimate".to_string(),
+      label: "Est.".to_string(),
+      kind: "numeric".to_string(),
+      values: None,
+      default: None,
+      urgency: None,
+    }];
+    let mut editor = ColumnEditorState::new();
+    editor.open(
+      &["id".to_string(), "due.relative".to_string()],
+      &["ID".to_string(), "Due".to_string()],
+      &udas,
+    );
+
+    editor.next(true);
+    editor.cycle_format(true);
+    assert_eq!(editor.columns[1], "due.remaining");
+    editor.cycle_format(false);
+    editor.cycle_format(false);
+    assert_eq!(editor.columns[1], "due.age");
+    editor.move_selected(false);
+    assert_eq!(editor.columns, vec!["due.age", "id"]);
+    assert_eq!(editor.selected(), Some(0));
+
+    editor.available_state.select(editor.available.iter().position(|c| c.name == "estimate"));
+    editor.add_picked();
+    assert_eq!(editor.columns, vec!["due.age", "estimate", "id"]);
+    assert_eq!(editor.labels, vec!["Due", "Est.", "ID"]);
+
+    editor.label = "Left, over ".to_string();
+    editor.rename_selected();
+    assert_eq!(editor.labels[1], "Left over");
+
+    editor.next(true);
+    editor.remove_selected();
+    assert_eq!(editor.columns, vec!["due.age", "estimate"]);
+    assert_eq!(editor.selected(), Some(1));
+  }
+}
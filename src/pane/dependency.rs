@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use task_hookrs::{status::TaskStatus, task::Task};
+use uuid::Uuid;
+
+use crate::{app::TaskwarriorTui, command::Command, pane::Pane};
+
+/// One line of the dependency tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyRow {
+  pub uuid: Uuid,
+  /// Tree lines drawn in front of the task, e.g. `│  └─ `
+  pub prefix: String,
+  /// The id of the task, or the start of its uuid once it has none
+  pub label: String,
+  /// `None` for a dependency that is not part of the export
+  pub description: Option<String>,
+  /// Whether the task is part of the current report and can be selected there
+  pub in_report: bool,
+  /// Whether the task still waits on a dependency that is not done
+  pub blocked: bool,
+  pub finished: bool,
+  /// The task was already shown higher up, so its dependencies are not repeated
+  pub repeated: bool,
+}
+
+impl DependencyRow {
+  /// The label followed by the description, which goes through `mask` so it can be hidden while obfuscating
+  pub fn text(&self, mask: impl Fn(&str) -> String) -> String {
+    match &self.description {
+      Some(description) => format!("{} {}", self.label, mask(description)),
+      None => format!("{} (not in report)", self.label),
+    }
+  }
+}
+
+/// The tasks of the current report that take part in a dependency, drawn as a tree.
+/// Each task is followed by the tasks it depends on, so the leaves are the ones that can be worked on first.
+pub struct DependencyState {
+  pub rows: Vec<DependencyRow>,
+  pub current_selection: usize,
+  pub scroll: u16,
+}
+
+impl DependencyState {
+  pub(crate) fn new() -> Self {
+    Self {
+      rows: vec![],
+      current_selection: 0,
+      scroll: 0,
+    }
+  }
+
+  /// Rebuilds the tree from the report's tasks. `all_tasks` is used to describe dependencies outside the report.
+  /// The selection stays on `current` if it is still shown.
+  pub fn update_data(&mut self, tasks: &[Task], all_tasks: &[Task], current: Option<Uuid>) {
+    let mut lookup: HashMap<Uuid, &Task> = all_tasks.iter().map(|t| (*t.uuid(), t)).collect();
+    lookup.extend(tasks.iter().map(|t| (*t.uuid(), t)));
+    let in_report: HashSet<Uuid> = tasks.iter().map(|t| *t.uuid()).collect();
+    let depended_on: HashSet<Uuid> = tasks.iter().flat_map(|t| t.depends().into_iter().flatten().copied()).collect();
+
+    let mut builder = TreeBuilder {
+      lookup,
+      in_report,
+      order: tasks.iter().enumerate().map(|(i, t)| (*t.uuid(), i)).collect(),
+      shown: HashSet::new(),
+      rows: vec![],
+    };
+    let involved: Vec<Uuid> = tasks
+      .iter()
+      .filter(|t| t.depends().is_some_and(|d| !d.is_empty()) || depended_on.contains(t.uuid()))
+      .map(|t| *t.uuid())
+      .collect();
+    for uuid in involved.iter().filter(|u| !depended_on.contains(u)) {
+      builder.add(*uuid, "", None);
+    }
+    // Tasks on a cycle are all depended on, so they only show up here
+    for uuid in &involved {
+      if !builder.shown.contains(uuid) {
+        builder.add(*uuid, "", None);
+      }
+    }
+
+    self.rows = builder.rows;
+    if let Some(i) = current.and_then(|uuid| self.rows.iter().position(|r| r.uuid == uuid)) {
+      self.current_selection = i;
+    }
+    self.current_selection = self.current_selection.min(self.rows.len().saturating_sub(1));
+  }
+
+  pub fn selected(&self) -> Option<&DependencyRow> {
+    self.rows.get(self.current_selection)
+  }
+
+  /// Keeps the selection within a view of `height` lines
+  pub fn scroll_to_selection(&mut self, height: u16) {
+    let selection = self.current_selection as u16;
+    if selection < self.scroll {
+      self.scroll = selection;
+    } else if height > 0 && selection >= self.scroll + height {
+      self.scroll = selection + 1 - height;
+    }
+  }
+}
+
+struct TreeBuilder<'a> {
+  lookup: HashMap<Uuid, &'a Task>,
+  in_report: HashSet<Uuid>,
+  /// Position in the report, so dependencies are listed in report order
+  order: HashMap<Uuid, usize>,
+  shown: HashSet<Uuid>,
+  rows: Vec<DependencyRow>,
+}
+
+impl TreeBuilder<'_> {
+  fn is_unfinished(&self, uuid: &Uuid) -> bool {
+    self
+      .lookup
+      .get(uuid)
+      .is_some_and(|t| matches!(t.status(), TaskStatus::Pending | TaskStatus::Waiting | TaskStatus::Recurring))
+  }
+
+  /// Adds `uuid` and, the first time it is seen, everything it depends on.
+  /// `indent` is the tree drawing of the parent levels and `last` whether this is the parent's last dependency.
+  fn add(&mut self, uuid: Uuid, indent: &str, last: Option<bool>) {
+    let task = self.lookup.get(&uuid).copied();
+    let prefix = match last {
+      None => String::new(),
+      Some(true) => format!("{}└─ ", indent),
+      Some(false) => format!("{}├─ ", indent),
+    };
+    let mut depends: Vec<Uuid> = task.and_then(Task::depends).cloned().unwrap_or_default();
+    depends.sort_by_key(|d| (self.order.get(d).copied().unwrap_or(usize::MAX), self.lookup.get(d).and_then(|t| t.id())));
+    let label = match task.and_then(Task::id).filter(|id| *id > 0) {
+      Some(id) => id.to_string(),
+      None => uuid.to_string()[..8].to_string(),
+    };
+    let repeated = !self.shown.insert(uuid);
+    self.rows.push(DependencyRow {
+      uuid,
+      prefix,
+      label,
+      description: task.map(|t| t.description().clone()),
+      in_report: self.in_report.contains(&uuid),
+      blocked: depends.iter().any(|d| self.is_unfinished(d)),
+      finished: task.is_some_and(|t| matches!(t.status(), TaskStatus::Completed | TaskStatus::Deleted)),
+      repeated,
+    });
+    if repeated {
+      return;
+    }
+    let indent = match last {
+      None => String::new(),
+      Some(true) => format!("{}   ", indent),
+      Some(false) => format!("{}│  ", indent),
+    };
+    let n = depends.len();
+    for (i, dependency) in depends.into_iter().enumerate() {
+      self.add(dependency, &indent, Some(i + 1 == n));
+    }
+  }
+}
+
+impl Pane for DependencyState {
+  fn handle_command(app: &mut TaskwarriorTui, command: Command) -> Result<()> {
+    let len = app.dependencies.rows.len();
+    let page = app.terminal_height.saturating_sub(3) as usize;
+    let dependencies = &mut app.dependencies;
+    match command {
+      Command::Quit => app.should_quit = true,
+      Command::NextTab => app.next_tab(),
+      Command::PreviousTab => app.previous_tab(),
+      Command::Down => dependencies.current_selection = (dependencies.current_selection + 1).min(len.saturating_sub(1)),
+      Command::Up => dependencies.current_selection = dependencies.current_selection.saturating_sub(1),
+      Command::PageDown => dependencies.current_selection = (dependencies.current_selection + page).min(len.saturating_sub(1)),
+      Command::PageUp => dependencies.current_selection = dependencies.current_selection.saturating_sub(page),
+      Command::GoToTop => dependencies.current_selection = 0,
+      Command::GoToBottom => dependencies.current_selection = len.saturating_sub(1),
+      Command::Select if select_in_report(app) => {
+        app.task_table_state.multiple_selection();
+        app.toggle_mark();
+      }
+      _ => {}
+    }
+    select_in_report(app);
+    Ok(())
+  }
+}
+
+/// Moves the task report's selection to the task under the cursor, if it is part of the report
+fn select_in_report(app: &mut TaskwarriorTui) -> bool {
+  let Some(row) = app.dependencies.selected().filter(|r| r.in_report) else {
+    return false;
+  };
+  let uuid = row.uuid;
+  match app.tasks.iter().position(|t| *t.uuid() == uuid) {
+    Some(i) => {
+      app.task_report_select(i);
+      true
+    }
+    None => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  fn tasks() -> Vec<Task> {
+    // 1 depends on 2 and 3, 2 depends on 3, 4 has no dependencies, 5 depends on 6 which was not exported
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"release","entry":"20260101T000000Z","status":"pending",
+       "depends":["20000000-0000-0000-0000-000000000000","30000000-0000-0000-0000-000000000000"]},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"test","entry":"20260101T000000Z","status":"pending",
+       "depends":["30000000-0000-0000-0000-000000000000"]},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"build","entry":"20260101T000000Z","status":"pending"},
+      {"id":4,"uuid":"40000000-0000-0000-0000-000000000000","description":"unrelated","entry":"20260101T000000Z","status":"pending"},
+      {"id":5,"uuid":"50000000-0000-0000-0000-000000000000","description":"deploy","entry":"20260101T000000Z","status":"pending",
+       "depends":["60000000-0000-0000-0000-000000000000"]}
+    ]"#;
+    import(data.as_bytes()).unwrap()
+  }
+
+  fn line(row: &DependencyRow) -> String {
+    format!("{}{}", row.prefix, row.text(str::to_string))
+  }
+
+  #[test]
+  fn test_dependency_tree() {
+    let mut state = DependencyState::new();
+    state.update_data(&tasks(), &[], None);
+    let lines: Vec<String> = state.rows.iter().map(line).collect();
+    assert_eq!(
+      lines,
+      vec![
+        "1 release",
+        "├─ 2 test",
+        "│  └─ 3 build",
+        "└─ 3 build",
+        "5 deploy",
+        "└─ 60000000 (not in report)",
+      ]
+    );
+    assert!(state.rows[0].blocked);
+    assert!(!state.rows[2].blocked);
+    assert!(state.rows[3].repeated);
+    assert!(!state.rows[5].in_report);
+    // The dependency is unknown, so it is not counted as blocking
+    assert!(!state.rows[4].blocked);
+    assert_eq!(state.rows[1].text(crate::utils::obfuscate), "2 xxxx");
+    assert_eq!(state.rows[5].text(crate::utils::obfuscate), "60000000 (not in report)");
+
+    state.update_data(&tasks(), &[], Some("30000000-0000-0000-0000-000000000000".parse().unwrap()));
+    assert_eq!(state.current_selection, 2);
+  }
+
+  #[test]
+  fn test_dependency_cycle() {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending",
+       "depends":["20000000-0000-0000-0000-000000000000"]},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending",
+       "depends":["10000000-0000-0000-0000-000000000000"]}
+    ]"#;
+    let mut state = DependencyState::new();
+    state.update_data(&import(data.as_bytes()).unwrap(), &[], None);
+    let lines: Vec<String> = state.rows.iter().map(line).collect();
+    assert_eq!(lines, vec!["1 a", "└─ 2 b", "   └─ 1 a"]);
+  }
+}
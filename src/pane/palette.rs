@@ -0,0 +1,129 @@
+use crate::{
+  command::{Command, sequence_label},
+  keyconfig::{KeyConfig, KeyScope},
+  table::TaskwarriorTuiTableState,
+};
+
+/// What running a palette entry does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteTarget {
+  Command(Command),
+  Report(String),
+  Context(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+  pub title: String,
+  pub key: String,
+  pub target: PaletteTarget,
+}
+
+pub struct CommandPaletteState {
+  pub table_state: TaskwarriorTuiTableState,
+  pub rows: Vec<PaletteEntry>,
+  /// Current search query typed by the user inside the popup.
+  pub search: String,
+}
+
+impl CommandPaletteState {
+  pub(crate) fn new() -> Self {
+    Self {
+      table_state: TaskwarriorTuiTableState::default(),
+      rows: vec![],
+      search: String::new(),
+    }
+  }
+
+  /// Lists every command from the registry followed by one entry per report and context.
+  pub fn update_data(&mut self, keyconfig: &KeyConfig, reports: &[String], contexts: &[String]) {
+    self.rows = Command::ALL
+      .into_iter()
+      .filter(|c| *c != Command::Palette && !Command::PANE_ONLY.contains(c))
+      .map(|c| PaletteEntry {
+        title: c.title().to_string(),
+        key: sequence_label(&keyconfig.sequence(KeyScope::Tasks, c)),
+        target: PaletteTarget::Command(c),
+      })
+      .collect();
+    self.rows.extend(reports.iter().map(|r| PaletteEntry {
+      title: format!("Switch to report {}", r),
+      key: String::new(),
+      target: PaletteTarget::Report(r.clone()),
+    }));
+    self.rows.extend(contexts.iter().map(|c| PaletteEntry {
+      title: format!("Switch to context {}", c),
+      key: String::new(),
+      target: PaletteTarget::Context(c.clone()),
+    }));
+  }
+
+  /// Returns the indices into `self.rows` that fuzzy match the current search query,
+  /// best match first. An empty query matches everything in the original order.
+  pub fn filtered_indices(&self) -> Vec<usize> {
+    let mut scored: Vec<(usize, usize)> = self
+      .rows
+      .iter()
+      .enumerate()
+      .filter_map(|(i, r)| fuzzy_score(&self.search, &r.title).map(|s| (i, s)))
+      .collect();
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    scored.into_iter().map(|(i, _)| i).collect()
+  }
+
+  pub fn selected(&self) -> Option<&PaletteEntry> {
+    let fi = self.table_state.current_selection()?;
+    self.filtered_indices().get(fi).map(|&ri| &self.rows[ri])
+  }
+}
+
+/// Case-insensitive subsequence match of `query` in `text`.
+/// Consecutive characters and characters at the start of a word score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+  let text: Vec<char> = text.to_lowercase().chars().collect();
+  let mut score = 0;
+  let mut position = 0;
+  let mut previous: Option<usize> = None;
+  for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+    let found = position + text[position..].iter().position(|&c| c == q)?;
+    score += 1;
+    if previous.is_some_and(|p| p + 1 == found) {
+      score += 2;
+    }
+    if found == 0 || text[found - 1] == ' ' {
+      score += 3;
+    }
+    previous = Some(found);
+    position = found + 1;
+  }
+  Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_fuzzy_score() {
+    assert_eq!(fuzzy_score("", "Add task"), Some(0));
+    assert!(fuzzy_score("adt", "Add task").is_some());
+    assert!(fuzzy_score("xyz", "Add task").is_none());
+    assert!(fuzzy_score("task", "Add task") > fuzzy_score("task", "Toggle task info location"));
+  }
+
+  #[test]
+  fn test_palette_lists_commands_reports_and_contexts() {
+    let mut palette = CommandPaletteState::new();
+    palette.update_data(&KeyConfig::default(), &["next".to_string()], &["work".to_string()]);
+    assert_eq!(palette.rows.len(), Command::ALL.len() - 1 - Command::PANE_ONLY.len() + 2);
+
+    palette.search = "info loc".to_string();
+    let first = palette.filtered_indices()[0];
+    assert_eq!(palette.rows[first].target, PaletteTarget::Command(Command::Transpose));
+    assert_eq!(palette.rows[first].key, "\\");
+
+    palette.search = "report next".to_string();
+    let first = palette.filtered_indices()[0];
+    assert_eq!(palette.rows[first].target, PaletteTarget::Report("next".to_string()));
+  }
+}
@@ -1,74 +1,103 @@
-use std::fmt;
+use std::{
+  cmp::Ordering,
+  collections::{BTreeMap, HashSet},
+};
 
-use anyhow::{Context as AnyhowContext, Result, anyhow};
+use anyhow::Result;
+use chrono::{Duration, NaiveDateTime};
+use task_hookrs::{project::Project, status::TaskStatus, task::Task};
 
-const COL_WIDTH: usize = 21;
-const PROJECT_HEADER: &str = "Name";
-const REMAINING_TASK_HEADER: &str = "Remaining";
-const AVG_AGE_HEADER: &str = "Avg age";
-const COMPLETE_HEADER: &str = "Complete";
+use crate::{app::TaskwarriorTui, command::Command, pane::Pane, table::TaskwarriorTuiTableState, utils::Changeset};
 
-use std::{
-  cmp::min,
-  collections::{HashMap, HashSet},
-  error::Error,
-  process::{Command, Output},
-};
+/// The name shown for tasks without a project, as in `task summary`
+pub const NO_PROJECT: &str = "(none)";
 
-use chrono::{Datelike, Duration, Local, Month, NaiveDate, NaiveDateTime, TimeZone};
-use itertools::Itertools;
-use ratatui::{
-  buffer::Buffer,
-  layout::Rect,
-  style::{Color, Modifier, Style},
-  symbols,
-  widgets::{Block, Widget},
-};
-use task_hookrs::project::Project;
-use uuid::Uuid;
-
-use crate::{
-  action::Action,
-  app::{Mode, TaskwarriorTui},
-  event::KeyCode,
-  pane::Pane,
-  table::TaskwarriorTuiTableState,
-  utils::Changeset,
-};
+/// A column of the Projects pane, which the projects can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectColumn {
+  Name,
+  Remaining,
+  AvgAge,
+  Complete,
+}
+
+impl ProjectColumn {
+  pub const ALL: [ProjectColumn; 4] = [
+    ProjectColumn::Name,
+    ProjectColumn::Remaining,
+    ProjectColumn::AvgAge,
+    ProjectColumn::Complete,
+  ];
+
+  pub fn header(self) -> &'static str {
+    match self {
+      ProjectColumn::Name => "Name",
+      ProjectColumn::Remaining => "Remaining",
+      ProjectColumn::AvgAge => "Avg age",
+      ProjectColumn::Complete => "Complete",
+    }
+  }
+}
+
+/// A project and everything below it, counted the way `task summary` does
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectDetails {
+  /// The full dotted name, e.g. `work.clients`
+  pub name: Project,
+  /// The last part of the name, e.g. `clients`
+  pub label: String,
+  pub depth: usize,
+  pub has_children: bool,
+  pub remaining: usize,
+  pub completed: usize,
+  /// The average age of the remaining tasks
+  pub avg_age: Duration,
+}
+
+impl ProjectDetails {
+  /// The share of tasks that are completed, in percent
+  pub fn complete(&self) -> usize {
+    (self.completed * 100).checked_div(self.remaining + self.completed).unwrap_or(0)
+  }
+
+  fn compare(&self, other: &Self, column: ProjectColumn) -> Ordering {
+    match column {
+      ProjectColumn::Name => Ordering::Equal,
+      ProjectColumn::Remaining => self.remaining.cmp(&other.remaining),
+      ProjectColumn::AvgAge => self.avg_age.cmp(&other.avg_age),
+      ProjectColumn::Complete => self.complete().cmp(&other.complete()),
+    }
+  }
+}
 
+/// The projects of all remaining tasks, as a tree of their dotted names
 pub struct ProjectsState {
-  pub(crate) list: Vec<Project>,
   pub table_state: TaskwarriorTuiTableState,
   pub current_selection: usize,
   pub marked: HashSet<Project>,
-  pub columns: Vec<String>,
+  /// The projects that are shown, in tree order
   pub rows: Vec<ProjectDetails>,
-  pub data: String,
-}
-
-#[derive(Debug, Clone, Default)]
-pub struct ProjectDetails {
-  name: Project,
-  remaining: usize,
-  avg_age: String,
-  complete: String,
+  /// Projects whose subprojects are hidden
+  pub collapsed: HashSet<Project>,
+  /// The column that `sort` applies to
+  pub focused_column: ProjectColumn,
+  /// The column the projects are sorted by and whether it is ascending; by name if `None`
+  pub sort: Option<(ProjectColumn, bool)>,
+  /// Every project, before folding and sorting
+  projects: Vec<ProjectDetails>,
 }
 
 impl ProjectsState {
   pub(crate) fn new() -> Self {
     Self {
-      list: Vec::default(),
       table_state: TaskwarriorTuiTableState::default(),
       current_selection: 0,
       marked: HashSet::default(),
-      columns: vec![
-        PROJECT_HEADER.to_string(),
-        REMAINING_TASK_HEADER.to_string(),
-        AVG_AGE_HEADER.to_string(),
-        COMPLETE_HEADER.to_string(),
-      ],
-      data: Default::default(),
       rows: vec![],
+      collapsed: HashSet::default(),
+      focused_column: ProjectColumn::Name,
+      sort: None,
+      projects: vec![],
     }
   }
 
@@ -77,7 +106,7 @@ impl ProjectsState {
     if !app.projects.marked.is_empty() {
       for (idx, project) in app.projects.marked.clone().iter().enumerate() {
         let mut input: String = String::from(project);
-        if input.as_str() == "(none)" {
+        if input.as_str() == NO_PROJECT {
           input = " ".to_string();
         }
         if idx == 0 {
@@ -92,40 +121,161 @@ impl ProjectsState {
   }
 
   pub fn toggle_mark(&mut self) {
-    if !self.list.is_empty() {
-      let selected = self.current_selection;
-      if !self.marked.insert(self.list[selected].clone()) {
-        self.marked.remove(self.list[selected].as_str());
-      }
+    if let Some(project) = self.selected().map(|p| p.name.clone())
+      && !self.marked.insert(project.clone())
+    {
+      self.marked.remove(&project);
     }
   }
 
-  pub fn simplified_view(&mut self) -> (Vec<Vec<String>>, Vec<String>) {
-    let rows = self
-      .rows
+  pub fn selected(&self) -> Option<&ProjectDetails> {
+    self.rows.get(self.current_selection)
+  }
+
+  /// Counts the pending and completed tasks of every project, adding each task to the projects above it too.
+  /// Like `task summary`, only projects with remaining tasks are listed.
+  pub fn update_data(&mut self, tasks: &[Task], now: NaiveDateTime) {
+    #[derive(Default)]
+    struct Counts {
+      remaining: usize,
+      completed: usize,
+      age: Duration,
+    }
+    let mut counts: BTreeMap<String, Counts> = BTreeMap::new();
+    for task in tasks {
+      let pending = matches!(task.status(), TaskStatus::Pending | TaskStatus::Waiting);
+      if !pending && *task.status() != TaskStatus::Completed {
+        continue;
+      }
+      let project = task.project().map_or(NO_PROJECT, String::as_str);
+      let mut names = vec![];
+      for (i, c) in project.char_indices() {
+        if c == '.' {
+          names.push(&project[..i]);
+        }
+      }
+      names.push(project);
+      for name in names {
+        let count = counts.entry(name.to_string()).or_default();
+        if pending {
+          count.remaining += 1;
+          count.age += now - **task.entry();
+        } else {
+          count.completed += 1;
+        }
+      }
+    }
+
+    self.projects = counts
       .iter()
-      .map(|c| vec![c.name.clone(), c.remaining.to_string(), c.avg_age.to_string(), c.complete.clone()])
+      .filter(|(_, c)| c.remaining > 0)
+      .map(|(name, c)| {
+        let depth = if name == NO_PROJECT { 0 } else { name.matches('.').count() };
+        ProjectDetails {
+          name: name.clone(),
+          label: if depth == 0 {
+            name.clone()
+          } else {
+            name.rsplit('.').next().unwrap_or(name).to_string()
+          },
+          depth,
+          has_children: false,
+          remaining: c.remaining,
+          completed: c.completed,
+          avg_age: c.age / c.remaining as i32,
+        }
+      })
       .collect();
-    let headers = self.columns.clone();
-    (rows, headers)
+    let names: HashSet<String> = self.projects.iter().map(|p| p.name.clone()).collect();
+    for project in &mut self.projects {
+      project.has_children = names.iter().any(|n| n.starts_with(&format!("{}.", project.name)));
+    }
+    self.refresh_rows();
   }
 
-  pub fn last_line(&self, line: &str) -> bool {
-    let words = line.trim().split(' ').map(|s| s.trim()).collect::<Vec<&str>>();
-    words.len() == 2 && words[0].chars().all(|c| c.is_numeric()) && (words[1] == "project" || words[1] == "projects")
+  /// Lays out the projects in tree order, with each level sorted by `sort`, leaving out folded subtrees.
+  /// The selection stays on the same project if it is still shown.
+  pub fn refresh_rows(&mut self) {
+    let current = self.selected().map(|p| p.name.clone());
+    let parent = |name: &str| {
+      if name == NO_PROJECT {
+        None
+      } else {
+        name.rsplit_once('.').map(|(parent, _)| parent.to_string())
+      }
+    };
+    let children = |of: Option<&str>| {
+      let mut children: Vec<&ProjectDetails> = self.projects.iter().filter(|p| parent(&p.name).as_deref() == of).collect();
+      children.sort_by(|a, b| {
+        let ordering = match self.sort {
+          Some((column, ascending)) => {
+            let ordering = a.compare(b, column);
+            if ascending { ordering } else { ordering.reverse() }
+          }
+          None => Ordering::Equal,
+        };
+        ordering.then_with(|| a.label.cmp(&b.label))
+      });
+      children
+    };
+
+    let mut rows = vec![];
+    let mut stack: Vec<&ProjectDetails> = children(None).into_iter().rev().collect();
+    while let Some(project) = stack.pop() {
+      rows.push(project.clone());
+      if !self.collapsed.contains(&project.name) {
+        stack.extend(children(Some(&project.name)).into_iter().rev());
+      }
+    }
+    self.rows = rows;
+
+    if let Some(i) = current.and_then(|name| self.rows.iter().position(|p| p.name == name)) {
+      self.current_selection = i;
+    }
+    self.current_selection = self.current_selection.min(self.rows.len().saturating_sub(1));
+    self.update_table_state();
   }
 
-  pub fn update_data(&mut self, task_exe: &str) -> Result<()> {
-    self.list.clear();
-    self.rows.clear();
-    let output = Command::new(task_exe)
-      .arg("summary")
-      .output()
-      .context("Unable to run `task summary`")
-      .unwrap();
-    let data = String::from_utf8_lossy(&output.stdout);
-    self.data = data.into();
-    Ok(())
+  /// The name column of `project`, indented by its depth and with a marker for folded and unfolded projects
+  pub fn name_cell(&self, project: &ProjectDetails) -> String {
+    let marker = if !project.has_children {
+      "  "
+    } else if self.collapsed.contains(&project.name) {
+      "▸ "
+    } else {
+      "▾ "
+    };
+    format!("{}{}{}", "  ".repeat(project.depth), marker, project.label)
+  }
+
+  pub fn toggle_fold(&mut self) {
+    let Some(project) = self.selected().filter(|p| p.has_children).map(|p| p.name.clone()) else {
+      return;
+    };
+    if !self.collapsed.remove(&project) {
+      self.collapsed.insert(project);
+    }
+    self.refresh_rows();
+  }
+
+  /// Sorts by the focused column: ascending, then descending, then back to the order of the names
+  pub fn cycle_sort(&mut self) {
+    self.sort = match self.sort {
+      Some((column, true)) if column == self.focused_column => Some((column, false)),
+      Some((column, false)) if column == self.focused_column => None,
+      _ => Some((self.focused_column, true)),
+    };
+    self.refresh_rows();
+  }
+
+  pub fn move_column(&mut self, forward: bool) {
+    let i = ProjectColumn::ALL.iter().position(|c| *c == self.focused_column).unwrap_or_default();
+    let i = if forward {
+      (i + 1).min(ProjectColumn::ALL.len() - 1)
+    } else {
+      i.saturating_sub(1)
+    };
+    self.focused_column = ProjectColumn::ALL[i];
   }
 
   fn update_table_state(&mut self) {
@@ -136,7 +286,7 @@ impl ProjectsState {
       self.table_state.multiple_selection();
       self.table_state.clear();
       for project in &self.marked {
-        let index = self.list.iter().position(|x| x == project);
+        let index = self.rows.iter().position(|x| x.name == *project);
         self.table_state.mark(index);
       }
     }
@@ -144,39 +294,32 @@ impl ProjectsState {
 }
 
 impl Pane for ProjectsState {
-  fn handle_input(app: &mut TaskwarriorTui, input: KeyCode) -> Result<()> {
-    if input == app.keyconfig.quit || input == KeyCode::Ctrl('c') {
-      app.should_quit = true;
-    } else if input == app.keyconfig.next_tab {
-      Self::change_focus_to_right_pane(app);
-    } else if input == app.keyconfig.previous_tab {
-      Self::change_focus_to_left_pane(app);
-    } else if input == KeyCode::Down || input == app.keyconfig.down {
-      self::focus_on_next_project(app);
-    } else if input == KeyCode::Up || input == app.keyconfig.up {
-      self::focus_on_previous_project(app);
-    } else if input == app.keyconfig.select {
-      self::update_task_filter_by_selection(app)?;
+  fn handle_command(app: &mut TaskwarriorTui, command: Command) -> Result<()> {
+    let last = app.projects.rows.len().saturating_sub(1);
+    let page = app.terminal_height.saturating_sub(3) as usize;
+    let projects = &mut app.projects;
+    match command {
+      Command::Quit => app.should_quit = true,
+      Command::NextTab => app.next_tab(),
+      Command::Obfuscate => app.toggle_obfuscate(),
+      Command::PreviousTab => app.previous_tab(),
+      Command::Down => projects.current_selection = (projects.current_selection + 1).min(last),
+      Command::Up => projects.current_selection = projects.current_selection.saturating_sub(1),
+      Command::PageDown => projects.current_selection = (projects.current_selection + page).min(last),
+      Command::PageUp => projects.current_selection = projects.current_selection.saturating_sub(page),
+      Command::GoToTop => projects.current_selection = 0,
+      Command::GoToBottom => projects.current_selection = last,
+      Command::Fold => projects.toggle_fold(),
+      Command::ColumnLeft | Command::ColumnRight => projects.move_column(command == Command::ColumnRight),
+      Command::Sort => projects.cycle_sort(),
+      Command::Select => self::update_task_filter_by_selection(app)?,
+      _ => {}
     }
     app.projects.update_table_state();
     Ok(())
   }
 }
 
-fn focus_on_next_project(app: &mut TaskwarriorTui) {
-  if app.projects.current_selection < app.projects.list.len().saturating_sub(1) {
-    app.projects.current_selection += 1;
-    app.projects.table_state.select(Some(app.projects.current_selection));
-  }
-}
-
-fn focus_on_previous_project(app: &mut TaskwarriorTui) {
-  if app.projects.current_selection >= 1 {
-    app.projects.current_selection -= 1;
-    app.projects.table_state.select(Some(app.projects.current_selection));
-  }
-}
-
 fn update_task_filter_by_selection(app: &mut TaskwarriorTui) -> Result<()> {
   app.projects.table_state.multiple_selection();
   let last_project_pattern = ProjectsState::pattern_by_marked(app);
@@ -188,5 +331,94 @@ fn update_task_filter_by_selection(app: &mut TaskwarriorTui) -> Result<()> {
   let mut filter = current_filter.replace(&last_project_pattern, "");
   filter = format!("{}{}", filter, new_project_pattern);
   app.filter.update(filter.as_str(), filter.len(), &mut Changeset::default());
+  app.dirty = true;
   Ok(())
 }
+
+/// Filters the task report to the selected project and its subprojects, in place of the marked projects
+/// and of any `project:` in the filter
+pub fn filter_by_selected_project(app: &mut TaskwarriorTui) {
+  let Some(project) = app.projects.selected().map(|p| p.name.clone()) else {
+    return;
+  };
+  let marked_pattern = ProjectsState::pattern_by_marked(app);
+  let current_filter = app.filter.as_str().to_string();
+  app.filter_history.add(&current_filter);
+  let mut words: Vec<&str> = current_filter
+    .strip_suffix(&marked_pattern)
+    .unwrap_or(&current_filter)
+    .split_whitespace()
+    .filter(|w| !w.starts_with("project:"))
+    .collect();
+  let project = if project == NO_PROJECT {
+    "project:".to_string()
+  } else {
+    format!("project:{}", project)
+  };
+  words.push(&project);
+  let filter = words.join(" ");
+  app.filter.update(&filter, filter.len(), &mut Changeset::default());
+  app.projects.marked.clear();
+  app.dirty = true;
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  fn state() -> ProjectsState {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260301T000000Z","status":"pending","project":"work"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260305T000000Z","status":"pending","project":"work.clients"},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260307T000000Z","status":"waiting","project":"work.clients"},
+      {"id":0,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"20260101T000000Z","status":"completed","project":"work.clients"},
+      {"id":0,"uuid":"50000000-0000-0000-0000-000000000000","description":"e","entry":"20260101T000000Z","status":"completed","project":"work.old"},
+      {"id":4,"uuid":"60000000-0000-0000-0000-000000000000","description":"f","entry":"20260309T000000Z","status":"pending","project":"home"},
+      {"id":0,"uuid":"70000000-0000-0000-0000-000000000000","description":"g","entry":"20260101T000000Z","status":"deleted","project":"home"},
+      {"id":5,"uuid":"80000000-0000-0000-0000-000000000000","description":"h","entry":"20260310T000000Z","status":"pending"}
+    ]"#;
+    let tasks = import(data.as_bytes()).unwrap();
+    let mut state = ProjectsState::new();
+    state.update_data(&tasks, NaiveDate::from_ymd_opt(2026, 3, 11).unwrap().and_hms_opt(0, 0, 0).unwrap());
+    state
+  }
+
+  fn names(state: &ProjectsState) -> Vec<String> {
+    state.rows.iter().map(|p| state.name_cell(p)).collect()
+  }
+
+  #[test]
+  fn test_projects_tree_counts() {
+    let state = state();
+    assert_eq!(names(&state), vec!["  (none)", "  home", "▾ work", "    clients"]);
+
+    let work = &state.rows[2];
+    assert_eq!((work.remaining, work.completed, work.complete()), (3, 2, 40));
+    assert_eq!(work.avg_age, Duration::days((10 + 6 + 4) / 3) + Duration::hours(16));
+    let clients = &state.rows[3];
+    assert_eq!((clients.depth, clients.remaining, clients.completed, clients.complete()), (1, 2, 1, 33));
+  }
+
+  #[test]
+  fn test_projects_fold_and_sort() {
+    let mut state = state();
+    state.current_selection = 3;
+    state.focused_column = ProjectColumn::Remaining;
+    state.cycle_sort();
+    assert_eq!(names(&state), vec!["  (none)", "  home", "▾ work", "    clients"]);
+    state.cycle_sort();
+    assert_eq!(names(&state), vec!["▾ work", "    clients", "  (none)", "  home"]);
+    assert_eq!(state.selected().unwrap().name, "work.clients");
+
+    state.current_selection = 0;
+    state.toggle_fold();
+    assert_eq!(names(&state), vec!["▸ work", "  (none)", "  home"]);
+    state.cycle_sort();
+    assert_eq!(state.sort, None);
+    assert_eq!(names(&state), vec!["  (none)", "  home", "▸ work"]);
+    assert_eq!(state.selected().unwrap().name, "work");
+  }
+}
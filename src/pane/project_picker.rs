@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use task_hookrs::{status::TaskStatus, task::Task};
+use uuid::Uuid;
+
+use crate::{pane::project::NO_PROJECT, table::TaskwarriorTuiTableState};
+
+/// What the picked project is used for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectPickerTarget {
+  /// Renames a project and its subprojects to the picked name; an existing name merges them into it
+  Rename(String),
+  /// Like `Rename`, but only into a project that already exists
+  Merge(String),
+  /// Moves these tasks to the picked project
+  Move(Vec<Uuid>),
+}
+
+/// A task whose project changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectChange {
+  pub uuid: Uuid,
+  pub id: Option<u64>,
+  pub description: String,
+  /// Empty for no project
+  pub from: String,
+  pub to: String,
+}
+
+/// A popup to pick a project by typing its name, followed by a preview of the tasks that will change
+pub struct ProjectPickerState {
+  pub table_state: TaskwarriorTuiTableState,
+  pub search: String,
+  pub target: ProjectPickerTarget,
+  /// Every project of a task that is not deleted, and the projects above them
+  pub projects: Vec<String>,
+  /// The tasks that change once a project is picked
+  pub preview: Vec<ProjectChange>,
+}
+
+impl ProjectPickerState {
+  pub(crate) fn new() -> Self {
+    Self {
+      table_state: TaskwarriorTuiTableState::default(),
+      search: String::new(),
+      target: ProjectPickerTarget::Move(vec![]),
+      projects: vec![],
+      preview: vec![],
+    }
+  }
+
+  pub fn open(&mut self, target: ProjectPickerTarget, tasks: &[Task], search: String) {
+    let mut projects = BTreeSet::new();
+    for project in tasks.iter().filter(|t| *t.status() != TaskStatus::Deleted).filter_map(|t| t.project()) {
+      for (i, c) in project.char_indices() {
+        if c == '.' {
+          projects.insert(project[..i].to_string());
+        }
+      }
+      projects.insert(project.clone());
+    }
+    self.projects = projects.into_iter().collect();
+    self.target = target;
+    self.search = search;
+    self.preview = vec![];
+    self.table_state.select(Some(0));
+  }
+
+  /// The projects that can be picked for the search, with whether they are new. A name that is typed out
+  /// comes first unless it exists; a project cannot be renamed or merged into itself or its subprojects.
+  pub fn choices(&self) -> Vec<(String, bool)> {
+    let search = self.search.trim();
+    let source = match &self.target {
+      ProjectPickerTarget::Rename(from) | ProjectPickerTarget::Merge(from) => Some(from.as_str()),
+      ProjectPickerTarget::Move(_) => None,
+    };
+    let allowed = |name: &str| source.is_none_or(|from| renamed(Some(name), from, "").is_none());
+    let mut choices = vec![];
+    if !matches!(self.target, ProjectPickerTarget::Merge(_))
+      && !search.is_empty()
+      && !search.contains(char::is_whitespace)
+      && !self.projects.iter().any(|p| p == search)
+      && allowed(search)
+    {
+      choices.push((search.to_string(), true));
+    }
+    let lowercase = search.to_lowercase();
+    choices.extend(
+      self
+        .projects
+        .iter()
+        .filter(|p| p.to_lowercase().contains(&lowercase) && allowed(p))
+        .map(|p| (p.clone(), false)),
+    );
+    choices
+  }
+
+  pub fn selected(&self) -> Option<String> {
+    let i = self.table_state.current_selection()?;
+    self.choices().into_iter().nth(i).map(|(name, _)| name)
+  }
+
+  /// Fills the preview with the tasks that move to `to`
+  pub fn update_preview(&mut self, to: &str, tasks: &[Task]) {
+    let change = |task: &Task, to: String| ProjectChange {
+      uuid: *task.uuid(),
+      id: task.id().filter(|id| *id > 0),
+      description: task.description().clone(),
+      from: task.project().cloned().unwrap_or_default(),
+      to,
+    };
+    let tasks = tasks.iter().filter(|t| *t.status() != TaskStatus::Deleted);
+    self.preview = match &self.target {
+      ProjectPickerTarget::Rename(from) | ProjectPickerTarget::Merge(from) => tasks
+        .filter_map(|t| renamed(t.project().map(String::as_str), from, to).map(|p| change(t, p)))
+        .collect(),
+      ProjectPickerTarget::Move(uuids) => tasks
+        .filter(|t| uuids.contains(t.uuid()) && t.project().map(String::as_str) != Some(to))
+        .map(|t| change(t, to.to_string()))
+        .collect(),
+    };
+  }
+
+  /// The uuids of the previewed tasks, grouped by their new project
+  pub fn changes_by_project(&self) -> BTreeMap<&str, Vec<Uuid>> {
+    let mut changes: BTreeMap<&str, Vec<Uuid>> = BTreeMap::new();
+    for change in &self.preview {
+      changes.entry(&change.to).or_default().push(change.uuid);
+    }
+    changes
+  }
+}
+
+/// The new name of `project` when `from` is renamed to `to`, if it is `from` or one of its subprojects.
+/// `from` is [`NO_PROJECT`] for tasks without a project.
+pub fn renamed(project: Option<&str>, from: &str, to: &str) -> Option<String> {
+  let Some(project) = project else {
+    return (from == NO_PROJECT).then(|| to.to_string());
+  };
+  if project == from {
+    Some(to.to_string())
+  } else {
+    project
+      .strip_prefix(from)
+      .filter(|rest| rest.starts_with('.'))
+      .map(|rest| format!("{}{}", to, rest))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  fn tasks() -> Vec<Task> {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","project":"work.old"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","project":"work.old.x"},
+      {"id":0,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"completed","project":"work.old"},
+      {"id":0,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"20260101T000000Z","status":"deleted","project":"work.old"},
+      {"id":3,"uuid":"50000000-0000-0000-0000-000000000000","description":"e","entry":"20260101T000000Z","status":"pending","project":"work.older"},
+      {"id":4,"uuid":"60000000-0000-0000-0000-000000000000","description":"f","entry":"20260101T000000Z","status":"pending","project":"home"},
+      {"id":5,"uuid":"70000000-0000-0000-0000-000000000000","description":"g","entry":"20260101T000000Z","status":"pending"}
+    ]"#;
+    import(data.as_bytes()).unwrap()
+  }
+
+  #[test]
+  fn test_rename_moves_the_subtree() {
+    assert_eq!(
+      renamed(Some("work.old"), "work.old", "work.archive.old").as_deref(),
+      Some("work.archive.old")
+    );
+    assert_eq!(
+      renamed(Some("work.old.x"), "work.old", "work.archive.old").as_deref(),
+      Some("work.archive.old.x")
+    );
+    assert_eq!(renamed(Some("work.older"), "work.old", "work.archive.old"), None);
+    assert_eq!(renamed(None, NO_PROJECT, "inbox").as_deref(), Some("inbox"));
+    assert_eq!(renamed(None, "work", "inbox"), None);
+
+    let tasks = tasks();
+    let mut picker = ProjectPickerState::new();
+    picker.open(
+      ProjectPickerTarget::Rename("work.old".to_string()),
+      &tasks,
+      "work.archive.old".to_string(),
+    );
+    assert_eq!(picker.selected().as_deref(), Some("work.archive.old"));
+    picker.update_preview("work.archive.old", &tasks);
+    let preview: Vec<(&str, &str)> = picker.preview.iter().map(|c| (c.description.as_str(), c.to.as_str())).collect();
+    assert_eq!(
+      preview,
+      vec![("a", "work.archive.old"), ("b", "work.archive.old.x"), ("c", "work.archive.old")]
+    );
+    assert_eq!(picker.changes_by_project().len(), 2);
+  }
+
+  #[test]
+  fn test_picker_choices() {
+    let tasks = tasks();
+    let mut picker = ProjectPickerState::new();
+    picker.open(ProjectPickerTarget::Merge("work.old".to_string()), &tasks, String::new());
+    let names: Vec<String> = picker.choices().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["home", "work", "work.older"]);
+    picker.search = "new".to_string();
+    assert!(picker.choices().is_empty());
+
+    picker.open(
+      ProjectPickerTarget::Move(vec!["60000000-0000-0000-0000-000000000000".parse().unwrap(), *tasks[6].uuid()]),
+      &tasks,
+      "ho".to_string(),
+    );
+    assert_eq!(picker.choices(), vec![("ho".to_string(), true), ("home".to_string(), false)]);
+    picker.update_preview("home", &tasks);
+    assert_eq!(picker.preview.len(), 1);
+    assert_eq!((picker.preview[0].from.as_str(), picker.preview[0].to.as_str()), ("", "home"));
+  }
+}
@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveDateTime};
+use task_hookrs::{status::TaskStatus, task::Task};
+use uuid::Uuid;
+
+use crate::{app::TaskwarriorTui, command::Command, datetime, pane::Pane, table::TaskwarriorTuiTableState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimesheetAction {
+  Added,
+  Started,
+  Completed,
+}
+
+impl TimesheetAction {
+  pub fn name(self) -> &'static str {
+    match self {
+      TimesheetAction::Added => "Added",
+      TimesheetAction::Started => "Started",
+      TimesheetAction::Completed => "Completed",
+    }
+  }
+}
+
+/// Something that happened to a task during the period, at a local time
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimesheetRow {
+  pub uuid: Uuid,
+  pub time: NaiveDateTime,
+  pub action: TimesheetAction,
+  pub id: Option<u64>,
+  pub project: String,
+  pub description: String,
+}
+
+/// How many tasks of a project were added, started and completed during the period
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimesheetTotal {
+  /// Empty for tasks without a project
+  pub project: String,
+  pub added: usize,
+  pub started: usize,
+  pub completed: usize,
+}
+
+/// The tasks added, started or completed in a week or a day, read from their `entry`, `start` and `end`
+pub struct TimesheetState {
+  /// A day in the period shown
+  pub day: NaiveDate,
+  /// Show the whole week of `day` instead of only `day`
+  pub week: bool,
+  pub rows: Vec<TimesheetRow>,
+  pub totals: Vec<TimesheetTotal>,
+  pub current_selection: usize,
+  /// Scroll position and marks of the drawn table, which follow `current_selection` and the report's marks
+  pub table_state: TaskwarriorTuiTableState,
+}
+
+impl TimesheetState {
+  pub(crate) fn new() -> Self {
+    Self {
+      day: Local::now().date_naive(),
+      week: true,
+      rows: vec![],
+      totals: vec![],
+      current_selection: 0,
+      table_state: TaskwarriorTuiTableState::default(),
+    }
+  }
+
+  /// The first and last day of the period shown
+  pub fn period(&self, start_on_monday: bool) -> (NaiveDate, NaiveDate) {
+    if !self.week {
+      return (self.day, self.day);
+    }
+    let offset = if start_on_monday {
+      self.day.weekday().num_days_from_monday()
+    } else {
+      self.day.weekday().num_days_from_sunday()
+    };
+    let first = self.day - Days::new(u64::from(offset));
+    (first, first + Days::new(6))
+  }
+
+  pub fn period_label(&self, start_on_monday: bool) -> String {
+    let (first, last) = self.period(start_on_monday);
+    if self.week {
+      format!(
+        "Week {} ({} to {})",
+        first.iso_week().week(),
+        first.format("%Y-%m-%d"),
+        last.format("%Y-%m-%d")
+      )
+    } else {
+      first.format("%A %Y-%m-%d").to_string()
+    }
+  }
+
+  /// Goes to the previous or next week or day
+  pub fn move_period(&mut self, forward: bool) {
+    let days = Days::new(if self.week { 7 } else { 1 });
+    let day = if forward {
+      self.day.checked_add_days(days)
+    } else {
+      self.day.checked_sub_days(days)
+    };
+    if let Some(day) = day {
+      self.day = day;
+    }
+  }
+
+  /// Rebuilds the rows and totals of the period from `tasks`, keeping the selection on `current` if it is still shown
+  pub fn update_data(&mut self, tasks: &[Task], start_on_monday: bool, current: Option<Uuid>) {
+    let (first, last) = self.period(start_on_monday);
+    let in_period = |date: Option<&task_hookrs::date::Date>| {
+      date
+        .map(|d| datetime::local_from_utc(d).naive_local())
+        .filter(|t| (first..=last).contains(&t.date()))
+    };
+    self.rows = vec![];
+    for task in tasks.iter().filter(|t| *t.status() != TaskStatus::Deleted) {
+      let end = if *task.status() == TaskStatus::Completed { task.end() } else { None };
+      for (action, date) in [
+        (TimesheetAction::Added, Some(task.entry())),
+        (TimesheetAction::Started, task.start()),
+        (TimesheetAction::Completed, end),
+      ] {
+        if let Some(time) = in_period(date) {
+          self.rows.push(TimesheetRow {
+            uuid: *task.uuid(),
+            time,
+            action,
+            id: task.id().filter(|id| *id > 0),
+            project: task.project().cloned().unwrap_or_default(),
+            description: task.description().clone(),
+          });
+        }
+      }
+    }
+    self.rows.sort_by_key(|r| (r.time, r.action));
+
+    let mut totals: BTreeMap<&str, TimesheetTotal> = BTreeMap::new();
+    for row in &self.rows {
+      let total = totals.entry(&row.project).or_insert_with(|| TimesheetTotal {
+        project: row.project.clone(),
+        ..TimesheetTotal::default()
+      });
+      match row.action {
+        TimesheetAction::Added => total.added += 1,
+        TimesheetAction::Started => total.started += 1,
+        TimesheetAction::Completed => total.completed += 1,
+      }
+    }
+    self.totals = totals.into_values().collect();
+
+    if let Some(i) = current.and_then(|uuid| self.rows.iter().position(|r| r.uuid == uuid)) {
+      self.current_selection = i;
+    }
+    self.current_selection = self.current_selection.min(self.rows.len().saturating_sub(1));
+  }
+
+  pub fn selected(&self) -> Option<&TimesheetRow> {
+    self.rows.get(self.current_selection)
+  }
+}
+
+impl Pane for TimesheetState {
+  fn handle_command(app: &mut TaskwarriorTui, command: Command) -> Result<()> {
+    let len = app.timesheet.rows.len();
+    let page = app.terminal_height.saturating_sub(3) as usize;
+    let timesheet = &mut app.timesheet;
+    match command {
+      Command::Quit => app.should_quit = true,
+      Command::NextTab => app.next_tab(),
+      Command::Obfuscate => app.toggle_obfuscate(),
+      Command::PreviousTab => app.previous_tab(),
+      Command::Down => timesheet.current_selection = (timesheet.current_selection + 1).min(len.saturating_sub(1)),
+      Command::Up => timesheet.current_selection = timesheet.current_selection.saturating_sub(1),
+      Command::PageDown => timesheet.current_selection = (timesheet.current_selection + page).min(len.saturating_sub(1)),
+      Command::PageUp => timesheet.current_selection = timesheet.current_selection.saturating_sub(page),
+      Command::GoToTop => timesheet.current_selection = 0,
+      Command::GoToBottom => timesheet.current_selection = len.saturating_sub(1),
+      Command::PreviousPeriod | Command::NextPeriod | Command::WeekView => {
+        if command == Command::WeekView {
+          timesheet.week = !timesheet.week;
+        } else {
+          timesheet.move_period(command == Command::NextPeriod);
+        }
+        timesheet.current_selection = 0;
+        timesheet.update_data(&app.all_tasks, app.config.weekstart, None);
+      }
+      Command::Select if select_in_report(app) => {
+        app.task_table_state.multiple_selection();
+        app.toggle_mark();
+      }
+      _ => {}
+    }
+    select_in_report(app);
+    Ok(())
+  }
+}
+
+/// Moves the task report's selection to the task under the cursor, if it is part of the report
+pub fn select_in_report(app: &mut TaskwarriorTui) -> bool {
+  let Some(uuid) = app.timesheet.selected().map(|r| r.uuid) else {
+    return false;
+  };
+  match app.tasks.iter().position(|t| *t.uuid() == uuid) {
+    Some(i) => {
+      app.task_report_select(i);
+      true
+    }
+    None => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::TimeZone;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  /// A local time as it is stored in a task
+  fn utc(day: NaiveDate, hour: u32) -> String {
+    let local = Local.from_local_datetime(&day.and_hms_opt(hour, 0, 0).unwrap()).earliest().unwrap();
+    local.naive_utc().format("%Y%m%dT%H%M%SZ").to_string()
+  }
+
+  fn tasks(monday: NaiveDate) -> Vec<Task> {
+    let before = monday - Days::new(3);
+    let tuesday = monday + Days::new(1);
+    let data = format!(
+      r#"[
+      {{"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"{}","status":"pending","project":"work","start":"{}"}},
+      {{"id":0,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"{}","status":"completed","project":"work","end":"{}"}},
+      {{"id":2,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"{}","status":"pending"}},
+      {{"id":0,"uuid":"40000000-0000-0000-0000-000000000000","description":"d","entry":"{}","status":"deleted","end":"{}"}}
+    ]"#,
+      utc(before, 9),
+      utc(monday, 10),
+      utc(before, 9),
+      utc(tuesday, 11),
+      utc(tuesday, 8),
+      utc(monday, 9),
+      utc(monday, 12),
+    );
+    import(data.as_bytes()).unwrap()
+  }
+
+  #[test]
+  fn test_timesheet_rows_and_totals() {
+    let monday = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+    let tasks = tasks(monday);
+    let mut state = TimesheetState::new();
+    state.day = monday + Days::new(2);
+    state.update_data(&tasks, true, None);
+
+    assert_eq!(state.period(true), (monday, monday + Days::new(6)));
+    let rows: Vec<(&str, &str)> = state.rows.iter().map(|r| (r.description.as_str(), r.action.name())).collect();
+    assert_eq!(rows, vec![("a", "Started"), ("c", "Added"), ("b", "Completed")]);
+    assert_eq!(state.rows[2].id, None);
+    assert_eq!(
+      state.totals,
+      vec![
+        TimesheetTotal {
+          project: String::new(),
+          added: 1,
+          ..TimesheetTotal::default()
+        },
+        TimesheetTotal {
+          project: "work".to_string(),
+          started: 1,
+          completed: 1,
+          ..TimesheetTotal::default()
+        },
+      ]
+    );
+
+    // On Sunday the week is the one before unless weeks start on Sunday
+    state.day = monday - Days::new(1);
+    state.update_data(&tasks, false, None);
+    assert_eq!(state.period(false).0, state.day);
+    assert_eq!(state.rows.len(), 3);
+    state.update_data(&tasks, true, None);
+    assert_eq!(state.rows.len(), 2);
+  }
+
+  #[test]
+  fn test_timesheet_periods() {
+    let monday = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+    let tasks = tasks(monday);
+    let mut state = TimesheetState::new();
+    state.day = monday;
+    state.week = false;
+    state.update_data(&tasks, true, None);
+    assert_eq!(state.rows.len(), 1);
+    assert_eq!(state.period_label(true), "Monday 2026-03-09");
+
+    state.move_period(true);
+    state.update_data(&tasks, true, Some("20000000-0000-0000-0000-000000000000".parse().unwrap()));
+    assert_eq!(state.day, monday + Days::new(1));
+    assert_eq!(state.selected().unwrap().description, "b");
+
+    state.week = true;
+    state.move_period(false);
+    assert_eq!(state.period_label(true), "Week 10 (2026-03-02 to 2026-03-08)");
+    state.update_data(&tasks, true, None);
+    assert_eq!(state.rows.len(), 2);
+    assert_eq!(state.current_selection, 1);
+  }
+}
@@ -0,0 +1,106 @@
+use chrono::{Duration, NaiveDateTime};
+use task_hookrs::{task::Task, uda::UDAValue};
+use uuid::Uuid;
+
+/// The text of the annotation added when a pomodoro is completed, followed by its number
+pub const ANNOTATION: &str = "pomodoro #";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+  Work,
+  Break,
+}
+
+/// A focus interval on a task, and the break that follows it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pomodoro {
+  pub uuid: Uuid,
+  pub description: String,
+  pub phase: Phase,
+  /// When the current phase is over, in UTC
+  pub ends: NaiveDateTime,
+  /// The number of this pomodoro on the task, counting the ones completed before it
+  pub number: u64,
+}
+
+impl Pomodoro {
+  /// Starts working on `task` for `length`, after the pomodoros already completed on it
+  pub fn start(task: &Task, now: NaiveDateTime, length: Duration, uda: &str) -> Self {
+    Self {
+      uuid: *task.uuid(),
+      description: task.description().clone(),
+      phase: Phase::Work,
+      ends: now + length,
+      number: completed(task, uda) + 1,
+    }
+  }
+
+  pub fn remaining(&self, now: NaiveDateTime) -> Duration {
+    (self.ends - now).max(Duration::zero())
+  }
+
+  pub fn is_over(&self, now: NaiveDateTime) -> bool {
+    now >= self.ends
+  }
+
+  pub fn take_break(&mut self, now: NaiveDateTime, length: Duration) {
+    self.phase = Phase::Break;
+    self.ends = now + length;
+  }
+}
+
+/// How many pomodoros were completed on `task`, read from the numeric UDA `uda` if it is set,
+/// otherwise from the `pomodoro #<n>` annotations
+pub fn completed(task: &Task, uda: &str) -> u64 {
+  match task.uda().get(uda) {
+    Some(UDAValue::U64(n)) => *n,
+    Some(UDAValue::F64(n)) => *n as u64,
+    Some(UDAValue::Str(s)) => s.parse().unwrap_or_default(),
+    None => task
+      .annotations()
+      .into_iter()
+      .flatten()
+      .filter_map(|a| a.description().strip_prefix(ANNOTATION)?.parse::<u64>().ok())
+      .max()
+      .unwrap_or_default(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_pomodoro_phases_and_count() {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending",
+       "annotations":[
+         {"entry":"20260309T090000Z","description":"pomodoro #1"},
+         {"entry":"20260309T093000Z","description":"pomodoro #2"},
+         {"entry":"20260309T100000Z","description":"a note"}
+       ]},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","pomodoros":4},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"pending"}
+    ]"#;
+    let tasks = import(data.as_bytes()).unwrap();
+    assert_eq!(completed(&tasks[0], "pomodoros"), 2);
+    assert_eq!(completed(&tasks[1], "pomodoros"), 4);
+    assert_eq!(completed(&tasks[1], ""), 0);
+    assert_eq!(completed(&tasks[2], "pomodoros"), 0);
+
+    let now = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    let mut pomodoro = Pomodoro::start(&tasks[0], now, Duration::minutes(25), "pomodoros");
+    assert_eq!(pomodoro.number, 3);
+    assert_eq!(pomodoro.remaining(now + Duration::minutes(10)), Duration::minutes(15));
+    assert!(!pomodoro.is_over(now + Duration::minutes(10)));
+    assert!(pomodoro.is_over(now + Duration::minutes(25)));
+    assert_eq!(pomodoro.remaining(now + Duration::minutes(30)), Duration::zero());
+
+    pomodoro.take_break(now + Duration::minutes(25), Duration::minutes(5));
+    assert_eq!(pomodoro.phase, Phase::Break);
+    assert_eq!(pomodoro.remaining(now + Duration::minutes(26)), Duration::minutes(4));
+  }
+}
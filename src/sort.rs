@@ -0,0 +1,225 @@
+use std::cmp::Ordering;
+
+use chrono::NaiveDateTime;
+use task_hookrs::{task::Task, uda::UDAValue};
+
+/// One column of a `report.<name>.sort` setting, e.g. `due+`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+  pub column: String,
+  pub ascending: bool,
+}
+
+/// Parses a sort setting such as `urgency-,due+,project+/`. The `/` break marker is ignored, and `none` means unsorted.
+pub fn parse_sort(spec: &str) -> Vec<SortKey> {
+  spec
+    .split(',')
+    .map(|s| s.trim().trim_end_matches('/'))
+    .filter(|s| !s.is_empty() && *s != "none")
+    .map(|s| {
+      if let Some(column) = s.strip_suffix('-') {
+        SortKey {
+          column: column.to_string(),
+          ascending: false,
+        }
+      } else {
+        SortKey {
+          column: s.strip_suffix('+').unwrap_or(s).to_string(),
+          ascending: true,
+        }
+      }
+    })
+    .collect()
+}
+
+/// The inverse of `parse_sort`, e.g. for `task config report.<name>.sort`
+pub fn format_sort(keys: &[SortKey]) -> String {
+  if keys.is_empty() {
+    return "none".to_string();
+  }
+  keys
+    .iter()
+    .map(|key| format!("{}{}", key.column, if key.ascending { '+' } else { '-' }))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+/// Cycles `column` through ascending, descending and unsorted. A column that is not sorted by yet
+/// becomes the first key, so the keys before it break ties.
+pub fn cycle_sort(keys: &[SortKey], column: &str) -> Vec<SortKey> {
+  let mut keys = keys.to_vec();
+  match keys.iter().position(|key| key.column == column) {
+    Some(i) if keys[i].ascending => keys[i].ascending = false,
+    Some(i) => {
+      keys.remove(i);
+    }
+    None => keys.insert(
+      0,
+      SortKey {
+        column: column.to_string(),
+        ascending: true,
+      },
+    ),
+  }
+  keys
+}
+
+fn date_column(task: &Task, column: &str) -> Option<Option<NaiveDateTime>> {
+  let date = match column {
+    "due" => task.due(),
+    "scheduled" => task.scheduled(),
+    "wait" => task.wait(),
+    "until" => task.until(),
+    "entry" => Some(task.entry()),
+    "modified" => task.modified(),
+    "start" => task.start(),
+    "end" => task.end(),
+    _ => return None,
+  };
+  Some(date.map(|d| **d))
+}
+
+/// Whether `compare` knows how to order tasks by `column` the way taskwarrior does
+pub fn is_supported(column: &str, udas: &[String]) -> bool {
+  matches!(
+    column,
+    "id" | "urgency" | "due" | "scheduled" | "wait" | "until" | "entry" | "modified" | "start" | "end" | "project" | "description" | "priority"
+  ) || udas.iter().any(|u| u == column)
+}
+
+/// Compares two optional values so that missing values always sort last
+fn compare_present<T>(a: Option<T>, b: Option<T>, ascending: bool, cmp: impl Fn(&T, &T) -> Ordering) -> Ordering {
+  match (a, b) {
+    (Some(a), Some(b)) => {
+      let ordering = cmp(&a, &b);
+      if ascending { ordering } else { ordering.reverse() }
+    }
+    (Some(_), None) => Ordering::Less,
+    (None, Some(_)) => Ordering::Greater,
+    (None, None) => Ordering::Equal,
+  }
+}
+
+fn compare_uda(a: Option<&UDAValue>, b: Option<&UDAValue>, ascending: bool) -> Ordering {
+  let number = |v: &UDAValue| match v {
+    UDAValue::U64(n) => Some(*n as f64),
+    UDAValue::F64(n) => Some(*n),
+    UDAValue::Str(_) => None,
+  };
+  let text = |v: &UDAValue| match v {
+    UDAValue::Str(s) => s.clone(),
+    UDAValue::U64(n) => n.to_string(),
+    UDAValue::F64(n) => n.to_string(),
+  };
+  compare_present(a, b, ascending, |a, b| match (number(a), number(b)) {
+    (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+    _ => text(a).cmp(&text(b)),
+  })
+}
+
+/// Orders two tasks by `keys`. `priority_values` lists priorities from highest to lowest,
+/// as in `uda.priority.values`.
+pub fn compare(a: &Task, b: &Task, keys: &[SortKey], priority_values: &[String]) -> Ordering {
+  for key in keys {
+    let ascending = key.ascending;
+    let ordering = match key.column.as_str() {
+      "id" => compare_present(a.id().filter(|i| *i > 0), b.id().filter(|i| *i > 0), ascending, Ord::cmp),
+      "urgency" => compare_present(a.urgency(), b.urgency(), ascending, |x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal)),
+      "project" => compare_present(a.project(), b.project(), ascending, Ord::cmp),
+      "description" => compare_present(Some(a.description()), Some(b.description()), ascending, Ord::cmp),
+      "priority" => {
+        // Earlier values are more important, so they sort last in ascending order
+        let rank = |t: &Task| {
+          let value = t.priority().map(String::as_str).unwrap_or_default();
+          priority_values.iter().position(|p| p == value).map(|i| priority_values.len() - i)
+        };
+        compare_present(rank(a), rank(b), ascending, Ord::cmp)
+      }
+      column => match (date_column(a, column), date_column(b, column)) {
+        (Some(x), Some(y)) => compare_present(x, y, ascending, Ord::cmp),
+        _ => compare_uda(a.uda().get(column), b.uda().get(column), ascending),
+      },
+    };
+    if ordering != Ordering::Equal {
+      return ordering;
+    }
+  }
+  Ordering::Equal
+}
+
+pub fn sort_tasks(tasks: &mut [Task], keys: &[SortKey], priority_values: &[String]) {
+  tasks.sort_by(|a, b| compare(a, b, keys, priority_values));
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  fn tasks() -> Vec<Task> {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending","urgency":2.5,"priority":"L"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending","urgency":7.0,"due":"20260301T000000Z","priority":"H"},
+      {"id":3,"uuid":"30000000-0000-0000-0000-000000000000","description":"c","entry":"20260101T000000Z","status":"pending","urgency":2.5,"due":"20260201T000000Z"}
+    ]"#;
+    import(data.as_bytes()).unwrap()
+  }
+
+  fn ids(tasks: &[Task]) -> Vec<u64> {
+    tasks.iter().filter_map(Task::id).collect()
+  }
+
+  #[test]
+  fn test_parse_sort() {
+    assert_eq!(
+      parse_sort("urgency-,project+/,description"),
+      vec![
+        SortKey {
+          column: "urgency".to_string(),
+          ascending: false
+        },
+        SortKey {
+          column: "project".to_string(),
+          ascending: true
+        },
+        SortKey {
+          column: "description".to_string(),
+          ascending: true
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn test_cycle_sort() {
+    let keys = parse_sort("urgency-");
+    let keys = cycle_sort(&keys, "due");
+    assert_eq!(format_sort(&keys), "due+,urgency-");
+    let keys = cycle_sort(&keys, "due");
+    assert_eq!(format_sort(&keys), "due-,urgency-");
+    let keys = cycle_sort(&keys, "due");
+    assert_eq!(format_sort(&keys), "urgency-");
+    let keys = cycle_sort(&keys, "urgency");
+    assert_eq!(format_sort(&keys), "none");
+    assert!(parse_sort("none").is_empty());
+  }
+
+  #[test]
+  fn test_sort_tasks() {
+    let priorities = ["H", "M", "L", ""].map(String::from);
+    let mut tasks = tasks();
+
+    sort_tasks(&mut tasks, &parse_sort("urgency-,description+"), &priorities);
+    assert_eq!(ids(&tasks), vec![2, 1, 3]);
+
+    // Tasks without a due date come last either way
+    sort_tasks(&mut tasks, &parse_sort("due+"), &priorities);
+    assert_eq!(ids(&tasks), vec![3, 2, 1]);
+    sort_tasks(&mut tasks, &parse_sort("due-"), &priorities);
+    assert_eq!(ids(&tasks), vec![2, 3, 1]);
+
+    sort_tasks(&mut tasks, &parse_sort("priority-,id+"), &priorities);
+    assert_eq!(ids(&tasks), vec![2, 1, 3]);
+  }
+}
@@ -63,6 +63,10 @@ impl TaskwarriorTuiTableState {
     self.current_selection
   }
 
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+
   pub fn select(&mut self, index: Option<usize>) {
     self.current_selection = index;
     if index.is_none() {
@@ -108,6 +112,8 @@ where
 {
   Data(D),
   StyledData(D, Style),
+  /// A line across all columns that is not a task, such as a group header
+  Header(String, Style),
 }
 
 /// A widget to display data in formatted columns
@@ -144,6 +150,8 @@ pub struct Table<'a, H, R> {
   header: H,
   /// Style for the header
   header_style: Style,
+  /// Column whose header is drawn with an extra style on top of `header_style`
+  focused_header: Option<(usize, Style)>,
   /// Width constraints for each column
   widths: &'a [Constraint],
   /// Space between each column
@@ -162,6 +170,8 @@ pub struct Table<'a, H, R> {
   mark_highlight_symbol: Option<&'a str>,
   /// Symbol in front of the unmarked and selected row
   unmark_highlight_symbol: Option<&'a str>,
+  /// Number of lines taken up by each row, one for rows not listed
+  heights: &'a [usize],
   /// Data to display in each row
   rows: R,
 }
@@ -177,6 +187,7 @@ where
       style: Style::default(),
       header: H::default(),
       header_style: Style::default(),
+      focused_header: None,
       widths: &[],
       column_spacing: 1,
       header_gap: 1,
@@ -186,6 +197,7 @@ where
       unmark_symbol: None,
       mark_highlight_symbol: None,
       unmark_highlight_symbol: None,
+      heights: &[],
       rows: R::default(),
     }
   }
@@ -203,6 +215,7 @@ where
       style: Style::default(),
       header,
       header_style: Style::default(),
+      focused_header: None,
       widths: &[],
       column_spacing: 1,
       header_gap: 1,
@@ -212,6 +225,7 @@ where
       unmark_symbol: None,
       mark_highlight_symbol: None,
       unmark_highlight_symbol: None,
+      heights: &[],
       rows,
     }
   }
@@ -233,6 +247,11 @@ where
     self
   }
 
+  pub fn focused_header(mut self, index: usize, style: Style) -> Table<'a, H, R> {
+    self.focused_header = Some((index, style));
+    self
+  }
+
   pub fn widths(mut self, widths: &'a [Constraint]) -> Table<'a, H, R> {
     let between_0_and_100 = |&w| match w {
       Constraint::Percentage(p) => p <= 100,
@@ -254,6 +273,12 @@ where
     self
   }
 
+  /// Rows take up this many lines each; the lines of a cell are separated by `\n`
+  pub fn heights(mut self, heights: &'a [usize]) -> Table<'a, H, R> {
+    self.heights = heights;
+    self
+  }
+
   pub fn style(mut self, style: Style) -> Table<'a, H, R> {
     self.style = style;
     self
@@ -366,12 +391,16 @@ where
     let mut index = 0;
     if y < table_area.bottom() {
       for (w, t) in solved_widths.iter().zip(self.header.by_ref()) {
+        let header_style = match self.focused_header {
+          Some((focused, style)) if focused == index => self.header_style.patch(style),
+          _ => self.header_style,
+        };
         buf.set_stringn(
           x,
           y,
           format!("{symbol:>width$}", symbol = " ", width = *w as usize),
           *w as usize,
-          self.header_style,
+          header_style,
         );
         if t.to_string() == "ID" {
           buf.set_stringn(
@@ -379,11 +408,11 @@ where
             y,
             format!("{symbol:>width$}", symbol = t, width = *w as usize),
             *w as usize,
-            self.header_style,
+            header_style,
           );
           header_index = index;
         } else {
-          buf.set_stringn(x, y, format!("{}", t), *w as usize, self.header_style);
+          buf.set_stringn(x, y, format!("{}", t), *w as usize, header_style);
         }
         x += *w + self.column_spacing;
         index += 1;
@@ -437,18 +466,41 @@ where
     if y < table_area.bottom() {
       let remaining = (table_area.bottom() - y) as usize;
 
-      // Make sure the table shows the selected item
+      let height = |i: usize| self.heights.get(i).copied().unwrap_or(1).max(1);
+
+      // Make sure the table shows the selected item, scrolling as little as possible
       state.offset = selected.map_or(0, |s| {
-        if s >= remaining + state.offset - 1 {
-          s + 1 - remaining
-        } else if s < state.offset {
-          s
-        } else {
-          state.offset
+        let mut offset = state.offset.min(s);
+        let mut lines: usize = (offset..=s).map(height).sum();
+        while offset < s && lines > remaining {
+          lines -= height(offset);
+          offset += 1;
         }
+        offset
       });
-      for (i, row) in self.rows.skip(state.offset).take(remaining).enumerate() {
+      let mut row_y = y;
+      for (i, row) in self.rows.skip(state.offset).enumerate() {
+        if row_y >= table_area.bottom() {
+          break;
+        }
+        let lines = (height(i + state.offset) as u16).min(table_area.bottom() - row_y);
         let (data, style, symbol) = match row {
+          Row::Header(text, style) => {
+            let (style, symbol) = if Some(i) == state.current_selection().map(|s| s - state.offset) {
+              (style.patch(highlight_style), &highlight_symbol)
+            } else {
+              (style, &blank_symbol)
+            };
+            buf.set_stringn(
+              table_area.left(),
+              row_y,
+              format!("{}{:<width$}", symbol, text, width = table_area.width as usize),
+              table_area.width as usize,
+              style,
+            );
+            row_y += lines;
+            continue;
+          }
           Row::Data(d) | Row::StyledData(d, _) if Some(i) == state.current_selection().map(|s| s - state.offset) => match state.mode {
             TableMode::MultipleSelection => {
               if state.marked.contains(&(i + state.offset)) {
@@ -474,51 +526,58 @@ where
             }
           }
         };
+        // The symbol is only drawn on the first line of a row
+        let indent = " ".repeat(symbol.width());
         x = table_area.left();
         for (c, (w, elt)) in solved_widths.iter().zip(data).enumerate() {
-          let s = if c == 0 {
-            buf.set_stringn(
-              x,
-              y + i as u16,
-              format!("{symbol:^width$}", symbol = "", width = area.width as usize),
-              *w as usize,
-              style,
-            );
-            if c == header_index {
-              let symbol = match state.mode {
-                TableMode::SingleSelection | TableMode::MultipleSelection => &symbol,
-              };
-              format!(
-                "{symbol}{elt:>width$}",
-                symbol = symbol,
-                elt = elt,
-                width = (*w as usize).saturating_sub(symbol.to_string().width())
-              )
+          let text = elt.to_string();
+          for line in 0..lines {
+            if c == 0 {
+              buf.set_stringn(
+                x,
+                row_y + line,
+                format!("{symbol:^width$}", symbol = "", width = area.width as usize),
+                *w as usize,
+                style,
+              );
             } else {
-              format!(
-                "{symbol}{elt:<width$}",
-                symbol = symbol,
-                elt = elt,
-                width = (*w as usize).saturating_sub(symbol.to_string().width())
-              )
+              buf.set_stringn(
+                x - 1,
+                row_y + line,
+                format!("{symbol:^width$}", symbol = "", width = area.width as usize),
+                *w as usize + 1,
+                style,
+              );
             }
-          } else {
-            buf.set_stringn(
-              x - 1,
-              y + i as u16,
-              format!("{symbol:^width$}", symbol = "", width = area.width as usize),
-              *w as usize + 1,
-              style,
-            );
-            if c == header_index {
+          }
+          for (line, elt) in text.split('\n').take(lines as usize).enumerate() {
+            let s = if c == 0 {
+              let symbol = if line == 0 { &symbol } else { &indent };
+              if c == header_index {
+                format!(
+                  "{symbol}{elt:>width$}",
+                  symbol = symbol,
+                  elt = elt,
+                  width = (*w as usize).saturating_sub(symbol.width())
+                )
+              } else {
+                format!(
+                  "{symbol}{elt:<width$}",
+                  symbol = symbol,
+                  elt = elt,
+                  width = (*w as usize).saturating_sub(symbol.width())
+                )
+              }
+            } else if c == header_index {
               format!("{elt:>width$}", elt = elt, width = *w as usize)
             } else {
               format!("{elt:<width$}", elt = elt, width = *w as usize)
-            }
-          };
-          buf.set_stringn(x, y + i as u16, s, *w as usize, style);
+            };
+            buf.set_stringn(x, row_y + line as u16, s, *w as usize, style);
+          }
           x += *w + self.column_spacing;
         }
+        row_y += lines;
       }
     }
   }
@@ -537,3 +596,48 @@ where
     StatefulWidget::render(self, area, buf, &mut state);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn render(state: &mut TaskwarriorTuiTableState) -> Vec<String> {
+    let area = Rect::new(0, 0, 12, 5);
+    let mut buf = Buffer::empty(area);
+    let rows = [vec!["a"], vec!["b1\nb2\nb3"], vec!["c1\nc2"]];
+    let table = Table::new(["Col"].into_iter(), rows.iter().map(|row| Row::Data(row.iter())))
+      .widths(&[Constraint::Length(8)])
+      .heights(&[1, 3, 2])
+      .highlight_symbol("> ");
+    StatefulWidget::render(table, area, &mut buf, state);
+    (0..area.height)
+      .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+      .collect()
+  }
+
+  #[test]
+  fn test_table_rows_of_several_lines() {
+    // Three lines are left below the header, so the second row is cut off after two
+    let mut state = TaskwarriorTuiTableState::default();
+    assert_eq!(
+      render(&mut state),
+      vec!["Col         ", "            ", "> a         ", "  b1        ", "  b2        "]
+    );
+    assert_eq!(state.offset(), 0);
+
+    // Selecting the tall row scrolls until all of it is shown, with the symbol on its first line only
+    state.select(Some(1));
+    assert_eq!(
+      render(&mut state),
+      vec!["Col         ", "            ", "> b1        ", "  b2        ", "  b3        "]
+    );
+    assert_eq!(state.offset(), 1);
+
+    state.select(Some(2));
+    assert_eq!(
+      render(&mut state),
+      vec!["Col         ", "            ", "> c1        ", "  c2        ", "            "]
+    );
+    assert_eq!(state.offset(), 2);
+  }
+}
@@ -0,0 +1,522 @@
+use std::sync::LazyLock;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use regex::Regex;
+use rustyline::line_buffer::LineBuffer;
+use task_hookrs::{task::Task, uda::UDAValue};
+use uuid::Uuid;
+
+use crate::{config::Uda, datetime, utils};
+
+const MAX_LINE: usize = 4096;
+
+/// Format used to show dates in the editor, taskwarrior reads it back as local time
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Format taskwarrior uses for date UDAs in `task export`
+const EXPORT_DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+pub const DATE_SYNONYMS: [&str; 47] = [
+  "now",
+  "today",
+  "sod",
+  "eod",
+  "yesterday",
+  "tomorrow",
+  "later",
+  "someday",
+  "sow",
+  "eow",
+  "soww",
+  "eoww",
+  "socw",
+  "eocw",
+  "som",
+  "eom",
+  "socm",
+  "eocm",
+  "soq",
+  "eoq",
+  "socq",
+  "eocq",
+  "soy",
+  "eoy",
+  "socy",
+  "eocy",
+  "monday",
+  "tuesday",
+  "wednesday",
+  "thursday",
+  "friday",
+  "saturday",
+  "sunday",
+  "january",
+  "february",
+  "march",
+  "april",
+  "may",
+  "june",
+  "july",
+  "august",
+  "september",
+  "october",
+  "november",
+  "december",
+  "goodfriday",
+  "easter",
+];
+
+pub const RECURRENCE_SYNONYMS: [&str; 14] = [
+  "daily",
+  "weekdays",
+  "weekly",
+  "biweekly",
+  "fortnight",
+  "monthly",
+  "bimonthly",
+  "quarterly",
+  "semiannual",
+  "biannual",
+  "annual",
+  "yearly",
+  "biyearly",
+  "sennight",
+];
+
+static DURATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(
+    r"^(\d+(\.\d+)?)?\s*(s|secs?|seconds?|min|mins|minutes?|h|hrs?|hours?|d|days?|w|wks?|weeks?|mo|mos|mnths?|months?|q|qtrs?|quarters?|y|yrs?|years?)$",
+  )
+  .unwrap()
+});
+
+static ISO_DURATION_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^P(\d+Y)?(\d+M)?(\d+W)?(\d+D)?(T(\d+H)?(\d+M)?(\d+S)?)?$").unwrap());
+
+static ORDINAL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([1-9]|[12]\d|3[01])(st|nd|rd|th)$").unwrap());
+
+static ID_OR_UUID_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+|[0-9a-fA-F]{8}(-[0-9a-fA-F-]{4,})?)$").unwrap());
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+  Text,
+  Tags,
+  Date,
+  Duration,
+  Numeric,
+  Depends,
+  Choice(Vec<String>),
+}
+
+pub struct EditorField {
+  pub attribute: String,
+  pub label: String,
+  pub kind: FieldKind,
+  pub original: String,
+  pub buffer: LineBuffer,
+}
+
+impl EditorField {
+  fn new(attribute: &str, label: &str, kind: FieldKind, original: String) -> Self {
+    let mut buffer = LineBuffer::with_capacity(MAX_LINE);
+    buffer.update(&original, original.len(), &mut utils::Changeset::default());
+    Self {
+      attribute: attribute.to_string(),
+      label: label.to_string(),
+      kind,
+      original,
+      buffer,
+    }
+  }
+
+  pub fn value(&self) -> &str {
+    self.buffer.as_str().trim()
+  }
+
+  pub fn is_changed(&self) -> bool {
+    match self.kind {
+      FieldKind::Tags | FieldKind::Depends => split_list(self.value()) != split_list(&self.original),
+      _ => self.value() != self.original,
+    }
+  }
+
+  /// `date_format` is taskwarrior's `dateformat` as a chrono format, which dates may also be typed in
+  pub fn validate(&self, date_format: &str) -> Result<(), String> {
+    let value = self.value();
+    if self.attribute == "description" && value.is_empty() {
+      return Err("Description cannot be empty".to_string());
+    }
+    if value.is_empty() || !self.is_changed() {
+      return Ok(());
+    }
+    let valid = match &self.kind {
+      FieldKind::Text => !value.contains('\n'),
+      FieldKind::Tags => split_list(value).iter().all(|t| !t.contains(':')),
+      FieldKind::Date => is_date(value, date_format),
+      FieldKind::Duration => is_duration(value),
+      FieldKind::Numeric => value.parse::<f64>().is_ok(),
+      FieldKind::Depends => split_list(value).iter().all(|d| ID_OR_UUID_RE.is_match(d)),
+      FieldKind::Choice(values) => values.iter().any(|v| v == value),
+    };
+    if valid {
+      return Ok(());
+    }
+    match &self.kind {
+      FieldKind::Choice(values) => Err(format!("{}: `{}` is not one of {}", self.label, value, values.join(", "))),
+      FieldKind::Date => Err(format!("{}: `{}` is not a valid date", self.label, value)),
+      FieldKind::Duration => Err(format!("{}: `{}` is not a valid duration", self.label, value)),
+      FieldKind::Numeric => Err(format!("{}: `{}` is not a number", self.label, value)),
+      FieldKind::Depends => Err(format!("{}: `{}` is not a list of task ids or uuids", self.label, value)),
+      FieldKind::Tags => Err(format!("{}: tags cannot contain `:`", self.label)),
+      FieldKind::Text => Err(format!("{}: value cannot span multiple lines", self.label)),
+    }
+  }
+
+  /// Arguments for `task modify` that turn the original value into the edited one.
+  fn modifications(&self) -> Vec<String> {
+    if !self.is_changed() {
+      return vec![];
+    }
+    match self.kind {
+      FieldKind::Tags => {
+        let (added, removed) = list_difference(&self.original, self.value());
+        added
+          .into_iter()
+          .map(|t| format!("+{}", t))
+          .chain(removed.into_iter().map(|t| format!("-{}", t)))
+          .collect()
+      }
+      FieldKind::Depends => {
+        let (added, removed) = list_difference(&self.original, self.value());
+        let depends: Vec<String> = added
+          .into_iter()
+          .map(ToString::to_string)
+          .chain(removed.into_iter().map(|d| format!("-{}", d)))
+          .collect();
+        vec![format!("{}:{}", self.attribute, depends.join(","))]
+      }
+      _ => vec![format!("{}:{}", self.attribute, self.value())],
+    }
+  }
+}
+
+/// Form state for editing every attribute of a single task in place
+pub struct TaskEditor {
+  pub uuid: Uuid,
+  pub id: Option<u64>,
+  pub fields: Vec<EditorField>,
+  pub selected: usize,
+  /// Taskwarrior's `dateformat` as a chrono format, accepted in date fields besides the formats of `is_date`
+  pub date_format: String,
+}
+
+impl TaskEditor {
+  /// `dependency` renders a dependency, e.g. as its id while the task is still pending.
+  pub fn new(task: &Task, udas: &[Uda], priority_values: &[String], dependency: impl Fn(&Uuid) -> String) -> Self {
+    let date = |d: Option<&task_hookrs::date::Date>| d.map(|d| datetime::format_local_date(d, DATE_FORMAT)).unwrap_or_default();
+    let priorities: Vec<String> = priority_values.iter().filter(|p| !p.is_empty()).cloned().collect();
+
+    let mut fields = vec![
+      EditorField::new("description", "Description", FieldKind::Text, task.description().to_string()),
+      EditorField::new("project", "Project", FieldKind::Text, task.project().cloned().unwrap_or_default()),
+      EditorField::new("tags", "Tags", FieldKind::Tags, task.tags().map(|t| t.join(" ")).unwrap_or_default()),
+      EditorField::new("due", "Due", FieldKind::Date, date(task.due())),
+      EditorField::new("scheduled", "Scheduled", FieldKind::Date, date(task.scheduled())),
+      EditorField::new("wait", "Wait", FieldKind::Date, date(task.wait())),
+      EditorField::new("until", "Until", FieldKind::Date, date(task.until())),
+      EditorField::new(
+        "priority",
+        "Priority",
+        FieldKind::Choice(priorities),
+        task.priority().cloned().unwrap_or_default(),
+      ),
+      EditorField::new("recur", "Recur", FieldKind::Duration, task.recur().cloned().unwrap_or_default()),
+      EditorField::new(
+        "depends",
+        "Depends",
+        FieldKind::Depends,
+        task
+          .depends()
+          .map(|d| d.iter().map(&dependency).collect::<Vec<_>>().join(" "))
+          .unwrap_or_default(),
+      ),
+    ];
+
+    for uda in udas {
+      let kind = match (uda.kind.as_str(), &uda.values) {
+        (_, Some(values)) if !values.is_empty() => FieldKind::Choice(values.clone()),
+        ("numeric", _) => FieldKind::Numeric,
+        ("date", _) => FieldKind::Date,
+        ("duration", _) => FieldKind::Duration,
+        _ => FieldKind::Text,
+      };
+      let original = match task.uda().get(&uda.name) {
+        Some(UDAValue::Str(s)) if kind == FieldKind::Date => NaiveDateTime::parse_from_str(s, EXPORT_DATE_FORMAT)
+          .map(|d| datetime::format_local_date(&d, DATE_FORMAT))
+          .unwrap_or_else(|_| s.clone()),
+        Some(UDAValue::Str(s)) => s.clone(),
+        Some(UDAValue::U64(n)) => n.to_string(),
+        Some(UDAValue::F64(n)) => n.to_string(),
+        None => String::new(),
+      };
+      fields.push(EditorField::new(&uda.name, &uda.label, kind, original));
+    }
+
+    Self {
+      uuid: *task.uuid(),
+      id: task.id(),
+      fields,
+      selected: 0,
+      date_format: String::new(),
+    }
+  }
+
+  pub fn current(&self) -> &EditorField {
+    &self.fields[self.selected]
+  }
+
+  pub fn current_mut(&mut self) -> &mut EditorField {
+    &mut self.fields[self.selected]
+  }
+
+  pub fn next(&mut self) {
+    self.selected = (self.selected + 1) % self.fields.len();
+  }
+
+  pub fn previous(&mut self) {
+    self.selected = self.selected.checked_sub(1).unwrap_or(self.fields.len() - 1);
+  }
+
+  /// Returns the first validation error, if any
+  pub fn validate(&self) -> Result<(), String> {
+    self.fields.iter().try_for_each(|f| f.validate(&self.date_format))
+  }
+
+  /// Arguments for a single `task <uuid> modify` covering every changed field
+  pub fn modifications(&self) -> Vec<String> {
+    self.fields.iter().flat_map(EditorField::modifications).collect()
+  }
+}
+
+fn split_list(value: &str) -> Vec<&str> {
+  let mut items: Vec<&str> = value
+    .split(|c: char| c == ',' || c.is_whitespace())
+    .filter(|s| !s.is_empty())
+    .map(|s| s.trim_start_matches('+'))
+    .collect();
+  items.sort_unstable();
+  items.dedup();
+  items
+}
+
+fn list_difference<'a>(original: &'a str, edited: &'a str) -> (Vec<&'a str>, Vec<&'a str>) {
+  let original = split_list(original);
+  let edited = split_list(edited);
+  let added = edited.iter().filter(|i| !original.contains(i)).copied().collect();
+  let removed = original.iter().filter(|i| !edited.contains(i)).copied().collect();
+  (added, removed)
+}
+
+/// Returns true for the date formats taskwarrior understands: ISO dates, dates in `date_format`
+/// (taskwarrior's `dateformat` as a chrono format), named dates such as `eom` or `friday`, ordinals
+/// such as `15th`, durations relative to now, and any of these followed by `+` or `-` and a duration.
+pub fn is_date(value: &str, date_format: &str) -> bool {
+  let value = value.trim().trim_matches('\'');
+  let is_absolute_date = |value: &str| {
+    is_absolute_date(value)
+      || (!date_format.is_empty()
+        && (NaiveDate::parse_from_str(value, date_format).is_ok() || NaiveDateTime::parse_from_str(value, date_format).is_ok()))
+  };
+  if is_absolute_date(value) || is_duration(value) {
+    return true;
+  }
+  let lower = value.to_lowercase();
+  if DATE_SYNONYMS.contains(&lower.as_str()) || ORDINAL_RE.is_match(&lower) {
+    return true;
+  }
+  // `eom+1d`, `2024-01-01-2wk`: split at the last sign that starts a duration
+  lower
+    .char_indices()
+    .rev()
+    .filter(|(i, c)| *i > 0 && (*c == '+' || *c == '-'))
+    .any(|(i, _)| {
+      let (base, offset) = lower.split_at(i);
+      is_duration(&offset[1..]) && (DATE_SYNONYMS.contains(&base) || ORDINAL_RE.is_match(base) || is_absolute_date(base))
+    })
+}
+
+fn is_absolute_date(value: &str) -> bool {
+  DateTime::parse_from_rfc3339(value).is_ok()
+    || ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y%m%dT%H%M%SZ"]
+      .iter()
+      .any(|f| NaiveDateTime::parse_from_str(value, f).is_ok())
+    || NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+/// Returns true for taskwarrior durations such as `3d`, `2 weeks`, `monthly` or `P1DT2H`
+pub fn is_duration(value: &str) -> bool {
+  let lower = value.trim().to_lowercase();
+  let upper = value.trim().to_uppercase();
+  let iso = ISO_DURATION_RE.is_match(&upper) && upper.len() > 1 && !upper.ends_with('T');
+  !lower.is_empty() && (iso || DURATION_RE.is_match(&lower) || RECURRENCE_SYNONYMS.contains(&lower.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  fn task() -> Task {
+    let json = r#"[{
+      "id": 3,
+      "description": "write docs",
+      "entry": "20240101T100000Z",
+      "modified": "20240101T100000Z",
+      "status": "pending",
+      "uuid": "d3d1b0f6-6c1e-4a0c-9d2c-1e7e0b0f5a01",
+      "project": "home",
+      "tags": ["next", "docs"],
+      "priority": "M",
+      "depends": ["8b1d4bd5-0b4c-4a52-9d3c-2c3d2a1f6b02"],
+      "estimate": 2,
+      "size": "S"
+    }]"#;
+    import(json.as_bytes()).unwrap().remove(0)
+  }
+
+  fn udas() -> Vec<Uda> {
+    vec![
+      Uda {
+        name: "estimate".to_string(),
+        label: "Est".to_string(),
+        kind: "numeric".to_string(),
+        values: None,
+        default: None,
+        urgency: None,
+      },
+      Uda {
+        name: "size".to_string(),
+        label: "Size".to_string(),
+        kind: "string".to_string(),
+        values: Some(vec!["S".to_string(), "M".to_string(), "L".to_string()]),
+        default: None,
+        urgency: None,
+      },
+    ]
+  }
+
+  fn editor() -> TaskEditor {
+    TaskEditor::new(
+      &task(),
+      &udas(),
+      &["H".to_string(), "M".to_string(), "L".to_string(), String::new()],
+      |u| u.to_string()[..8].to_string(),
+    )
+  }
+
+  fn set(editor: &mut TaskEditor, attribute: &str, value: &str) {
+    let field = editor.fields.iter_mut().find(|f| f.attribute == attribute).unwrap();
+    field.buffer.update(value, value.len(), &mut utils::Changeset::default());
+  }
+
+  #[test]
+  fn test_task_editor_lists_attributes_and_udas() {
+    let editor = editor();
+    let attributes: Vec<&str> = editor.fields.iter().map(|f| f.attribute.as_str()).collect();
+    assert_eq!(
+      attributes,
+      vec![
+        "description",
+        "project",
+        "tags",
+        "due",
+        "scheduled",
+        "wait",
+        "until",
+        "priority",
+        "recur",
+        "depends",
+        "estimate",
+        "size"
+      ]
+    );
+    assert_eq!(editor.fields[2].original, "next docs");
+    assert_eq!(editor.fields[9].original, "8b1d4bd5");
+    assert_eq!(editor.fields[10].original, "2");
+    assert!(editor.modifications().is_empty());
+  }
+
+  #[test]
+  fn test_task_editor_only_sends_changed_fields() {
+    let mut editor = editor();
+    set(&mut editor, "description", "write more docs");
+    set(&mut editor, "tags", "docs +urgent");
+    set(&mut editor, "depends", "8b1d4bd5, 12");
+    set(&mut editor, "priority", "");
+    set(&mut editor, "size", "L");
+    assert_eq!(
+      editor.modifications(),
+      vec!["description:write more docs", "+urgent", "-next", "priority:", "depends:12", "size:L"]
+    );
+  }
+
+  #[test]
+  fn test_task_editor_validation() {
+    let mut editor = editor();
+    assert!(editor.validate().is_ok());
+
+    set(&mut editor, "due", "eom+1d");
+    set(&mut editor, "recur", "weekly");
+    set(&mut editor, "estimate", "1.5");
+    assert!(editor.validate().is_ok());
+
+    set(&mut editor, "due", "whenever");
+    assert_eq!(editor.validate(), Err("Due: `whenever` is not a valid date".to_string()));
+    set(&mut editor, "due", "");
+
+    set(&mut editor, "size", "XL");
+    assert_eq!(editor.validate(), Err("Size: `XL` is not one of S, M, L".to_string()));
+    set(&mut editor, "size", "S");
+
+    set(&mut editor, "estimate", "lots");
+    assert_eq!(editor.validate(), Err("Est: `lots` is not a number".to_string()));
+    set(&mut editor, "estimate", "2");
+
+    set(&mut editor, "description", " ");
+    assert_eq!(editor.validate(), Err("Description cannot be empty".to_string()));
+  }
+
+  #[test]
+  fn test_is_date() {
+    for value in [
+      "2024-01-31",
+      "2024-01-31T10:00:00",
+      "'2024-01-31T10:00:00+01:00'",
+      "tomorrow",
+      "Friday",
+      "15th",
+      "eom-2d",
+      "2024-01-31+1wk",
+      "3d",
+    ] {
+      assert!(is_date(value, ""), "{}", value);
+    }
+    for value in ["soon", "2024-13-01", "eom+", "tomorrow+later", "1/31/2024"] {
+      assert!(!is_date(value, ""), "{}", value);
+    }
+    // Dates in the user's `dateformat=m/d/Y`
+    assert!(is_date("1/31/2024", "%-m/%-d/%Y"));
+    assert!(is_date("1/31/2024+2d", "%-m/%-d/%Y"));
+    assert!(!is_date("31/1/2024", "%-m/%-d/%Y"));
+  }
+
+  #[test]
+  fn test_is_duration() {
+    for value in ["1d", "2 weeks", "3mo", "monthly", "weekdays", "P1DT2H", "1.5h"] {
+      assert!(is_duration(value), "{}", value);
+    }
+    for value in ["", "d1", "fortnightly", "P"] {
+      assert!(!is_duration(value), "{}", value);
+    }
+  }
+}
@@ -1,4 +1,4 @@
-use std::{error::Error, process::Command};
+use std::{collections::HashMap, error::Error, process::Command};
 
 use anyhow::Result;
 use chrono::{Datelike, Local, NaiveDateTime};
@@ -7,7 +7,11 @@ use task_hookrs::{task::Task, uda::UDAValue};
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
 
-use crate::{datetime, utils};
+use crate::{
+  datetime,
+  sort::{self, SortKey, parse_sort},
+  utils,
+};
 
 pub fn format_date_time(dt: NaiveDateTime) -> String {
   datetime::format_local_date_time(&dt)
@@ -79,7 +83,7 @@ pub fn vague_format_date_time(from_dt: NaiveDateTime, to_dt: NaiveDateTime, with
   format!("{}{}s", minus, seconds)
 }
 
-fn taskwarrior_to_chrono(fmt: &str) -> String {
+pub fn taskwarrior_to_chrono(fmt: &str) -> String {
   fmt
     .chars()
     .map(|c| match c {
@@ -109,6 +113,75 @@ fn taskwarrior_to_chrono(fmt: &str) -> String {
     .collect()
 }
 
+const DATE_FORMATS: &[&str] = &["formatted", "julian", "epoch", "iso", "age", "relative", "remaining", "countdown"];
+
+/// The columns taskwarrior can show in a report and their formats, as listed by `task columns`.
+/// The first format is the default, which is written without a suffix.
+pub const COLUMN_FORMATS: &[(&str, &[&str])] = &[
+  ("id", &["number"]),
+  ("uuid", &["long", "short"]),
+  ("description", &["combined", "desc", "oneline", "truncated", "count", "truncated_count"]),
+  ("project", &["full", "parent", "indented"]),
+  ("tags", &["list", "indicator", "count"]),
+  ("priority", &["default", "indicator"]),
+  ("status", &["long", "short"]),
+  ("urgency", &["real", "integer"]),
+  ("due", DATE_FORMATS),
+  ("scheduled", DATE_FORMATS),
+  ("wait", DATE_FORMATS),
+  ("until", DATE_FORMATS),
+  ("entry", DATE_FORMATS),
+  ("modified", DATE_FORMATS),
+  (
+    "start",
+    &[
+      "formatted",
+      "julian",
+      "epoch",
+      "iso",
+      "age",
+      "relative",
+      "remaining",
+      "countdown",
+      "active",
+    ],
+  ),
+  ("end", DATE_FORMATS),
+  ("depends", &["list", "count", "indicator"]),
+  ("recur", &["duration", "indicator"]),
+  ("parent", &["long", "short"]),
+  ("imask", &["number"]),
+  ("mask", &["default"]),
+];
+
+/// The formats of a UDA column by its type, the default first
+pub fn uda_formats(kind: &str) -> &'static [&'static str] {
+  match kind {
+    "date" => DATE_FORMATS,
+    "string" => &["default", "indicator"],
+    _ => &["default"],
+  }
+}
+
+/// The number of lines each row takes up, where the lines of a cell are separated by `\n`
+pub fn row_heights(rows: &[Vec<String>]) -> Vec<usize> {
+  rows
+    .iter()
+    .map(|row| row.iter().map(|cell| cell.split('\n').count()).max().unwrap_or(1))
+    .collect()
+}
+
+/// The label taskwarrior shows for a column without a label, e.g. `Due` for `due.relative`
+pub fn default_label(column: &str) -> String {
+  let label = column.split('.').next().unwrap_or_default();
+  let label = if label == "id" { "ID" } else { label };
+  let mut c = label.chars();
+  match c.next() {
+    None => String::new(),
+    Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+  }
+}
+
 pub struct TaskReportTable {
   pub labels: Vec<String>,
   pub columns: Vec<String>,
@@ -117,6 +190,20 @@ pub struct TaskReportTable {
   pub description_width: usize,
   pub date_time_vague_precise: bool,
   pub date_format: String,
+  /// The report's `sort` setting, used to re-order tasks without re-running the report
+  pub sort: Vec<SortKey>,
+  /// Order picked from inside the report, which replaces `sort` until another report is shown
+  pub custom_sort: Option<Vec<SortKey>>,
+  /// Index into `columns` of the column that sorting applies to, once one is focused
+  pub focused_column: Option<usize>,
+  /// Columns and labels picked in the column editor, which replace the report's until another report is shown
+  pub custom_columns: Option<(Vec<String>, Vec<String>)>,
+  /// Indices into `columns` of the columns kept by the last `simplify_table`
+  pub shown_columns: Vec<usize>,
+  /// The `*.indicator` and `uda.<name>.type` settings, from `task show`
+  pub settings: HashMap<String, String>,
+  /// Masks descriptions, projects and tags, see `utils::obfuscate`
+  pub obfuscate: bool,
 }
 
 impl TaskReportTable {
@@ -165,6 +252,13 @@ impl TaskReportTable {
       description_width: 100,
       date_time_vague_precise: false,
       date_format: "%Y-%m-%d".to_string(),
+      sort: vec![],
+      custom_sort: None,
+      focused_column: None,
+      custom_columns: None,
+      shown_columns: vec![],
+      settings: HashMap::new(),
+      obfuscate: false,
     };
     task_report_table.export_headers(Some(data), report, task_exe)?;
     Ok(task_report_table)
@@ -180,7 +274,7 @@ impl TaskReportTable {
       let output = Command::new(task_exe)
         .arg("show")
         .arg("rc.defaultwidth=0")
-        .arg(format!("report.{}.columns", report))
+        .arg(format!("report.{}.", report))
         .output()?;
       String::from_utf8_lossy(&output.stdout).into_owned()
     };
@@ -194,13 +288,6 @@ impl TaskReportTable {
       }
     }
 
-    let output = Command::new(task_exe)
-      .arg("show")
-      .arg("rc.defaultwidth=0")
-      .arg(format!("report.{}.labels", report))
-      .output()?;
-    let data = String::from_utf8_lossy(&output.stdout);
-
     for line in data.split('\n') {
       if line.starts_with(format!("report.{}.labels", report).as_str()) {
         let label_names = line.split_once(' ').unwrap().1;
@@ -210,13 +297,6 @@ impl TaskReportTable {
       }
     }
 
-    let output = Command::new(task_exe)
-      .arg("show")
-      .arg("rc.defaultwidth=0")
-      .arg(format!("report.{}.dateformat", report))
-      .output()?;
-    let data = String::from_utf8_lossy(&output.stdout);
-
     for line in data.split('\n') {
       if line.starts_with(format!("report.{}.dateformat", report).as_str()) {
         let taskwarrior_dateformat = line.split_once(' ').unwrap().1;
@@ -224,20 +304,37 @@ impl TaskReportTable {
       }
     }
 
+    self.settings.clear();
+    for line in data.split('\n') {
+      if let Some((key, value)) = line.split_once(char::is_whitespace)
+        && (key.ends_with(".indicator") || (key.starts_with("uda.") && key.ends_with(".type")))
+      {
+        self.settings.insert(key.to_string(), value.trim().to_string());
+      }
+    }
+
+    self.sort = vec![];
+    for line in data.split('\n') {
+      if let Some((_, spec)) = line
+        .strip_prefix(format!("report.{}.sort", report).as_str())
+        .and_then(|rest| rest.split_once(char::is_whitespace))
+      {
+        self.sort = parse_sort(spec.trim());
+      }
+    }
+
     if self.labels.is_empty() {
-      for label in &self.columns {
-        let label = label.split('.').collect::<Vec<&str>>()[0];
-        let label = if label == "id" { "ID" } else { label };
-        let mut c = label.chars();
-        let label = match c.next() {
-          None => String::new(),
-          Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-        };
+      for column in &self.columns {
+        let label = default_label(column);
         if !label.is_empty() {
           self.labels.push(label);
         }
       }
     }
+    if let Some((columns, labels)) = &self.custom_columns {
+      self.columns.clone_from(columns);
+      self.labels.clone_from(labels);
+    }
     let num_labels = self.labels.len();
     let num_columns = self.columns.len();
     assert!(
@@ -271,6 +368,7 @@ impl TaskReportTable {
   pub fn simplify_table(&mut self) -> (Vec<Vec<String>>, Vec<String>) {
     // find which columns are empty
     if self.tasks.is_empty() {
+      self.shown_columns.clear();
       return (vec![], vec![]);
     }
 
@@ -303,170 +401,193 @@ impl TaskReportTable {
       .filter(|&(i, _)| null_columns[i] != 0)
       .map(|(_, e)| e.clone())
       .collect();
+    self.shown_columns = (0..self.labels.len()).filter(|&i| null_columns[i] != 0).collect();
 
     (tasks, headers)
   }
 
+  /// The order of the report, as picked from inside it or from its `sort` setting
+  pub fn sort_keys(&self) -> &[SortKey] {
+    self.custom_sort.as_deref().unwrap_or(&self.sort)
+  }
+
+  /// The attribute shown in column `i`, which is what it is sorted by, e.g. `due` for `due.relative`
+  fn sort_column(&self, i: usize) -> Option<&str> {
+    self.columns.get(i).and_then(|c| c.split('.').next())
+  }
+
+  /// `▲` or `▼` if the report is sorted by column `i`
+  pub fn sort_indicator(&self, i: usize) -> Option<&'static str> {
+    let column = self.sort_column(i)?;
+    let key = self.sort_keys().iter().find(|key| key.column == column)?;
+    Some(if key.ascending { "▲" } else { "▼" })
+  }
+
+  /// Moves the focus to the next or previous column that is shown, starting at the first one
+  pub fn move_focus(&mut self, forward: bool) {
+    let last = self.shown_columns.len().saturating_sub(1);
+    let pos = self.focused_column.and_then(|c| self.shown_columns.iter().position(|s| *s == c));
+    let pos = match pos {
+      None => 0,
+      Some(p) if forward => (p + 1).min(last),
+      Some(p) => p.saturating_sub(1),
+    };
+    self.focused_column = self.shown_columns.get(pos).copied();
+  }
+
+  /// Cycles the focused column through ascending, descending and unsorted, see `sort::cycle_sort`
+  pub fn cycle_sort(&mut self, udas: &[String]) -> Result<(), String> {
+    if self.focused_column.is_none() {
+      self.move_focus(true);
+    }
+    let Some(column) = self.focused_column.and_then(|i| self.sort_column(i)) else {
+      return Ok(());
+    };
+    if !sort::is_supported(column, udas) {
+      return Err(format!("Cannot sort the report by {}", column));
+    }
+    self.custom_sort = Some(sort::cycle_sort(self.sort_keys(), column));
+    Ok(())
+  }
+
   pub fn get_string_attribute(&self, attribute: &str, task: &Task, tasks: &[Task]) -> String {
-    let description = utils::display_control_chars(task.description());
-
-    let value = match attribute {
-      "id" => task.id().unwrap_or_default().to_string(),
-      "scheduled.relative" => match task.scheduled() {
-        Some(v) => vague_format_date_time(
-          Local::now().naive_utc(),
-          NaiveDateTime::new(v.date(), v.time()),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
-      },
-      "scheduled.countdown" => match task.scheduled() {
-        Some(v) => vague_format_date_time(
-          Local::now().naive_utc(),
-          NaiveDateTime::new(v.date(), v.time()),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
-      },
-      "scheduled" => match task.scheduled() {
-        Some(v) => format_date(NaiveDateTime::new(v.date(), v.time()), None),
-        None => "".to_string(),
-      },
-      "due.relative" => match task.due() {
-        Some(v) => vague_format_date_time(
-          Local::now().naive_utc(),
-          NaiveDateTime::new(v.date(), v.time()),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
-      },
-      "due" => match task.due() {
-        Some(v) => format_date(NaiveDateTime::new(v.date(), v.time()), Some(self.date_format.clone())),
-        None => "".to_string(),
-      },
-      "until.remaining" => match task.until() {
-        Some(v) => vague_format_date_time(
-          Local::now().naive_utc(),
-          NaiveDateTime::new(v.date(), v.time()),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
-      },
-      "until" => match task.until() {
-        Some(v) => format_date(NaiveDateTime::new(v.date(), v.time()), None),
-        None => "".to_string(),
-      },
-      "entry.age" => vague_format_date_time(
-        NaiveDateTime::new(task.entry().date(), task.entry().time()),
-        Local::now().naive_utc(),
-        self.date_time_vague_precise,
-      ),
-      "entry" => format_date(NaiveDateTime::new(task.entry().date(), task.entry().time()), None),
-      "start.age" => match task.start() {
-        Some(v) => vague_format_date_time(
-          NaiveDateTime::new(v.date(), v.time()),
-          Local::now().naive_utc(),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
-      },
-      "start" => match task.start() {
-        Some(v) => format_date(NaiveDateTime::new(v.date(), v.time()), None),
-        None => "".to_string(),
-      },
-      "end.age" => match task.end() {
-        Some(v) => vague_format_date_time(
-          NaiveDateTime::new(v.date(), v.time()),
-          Local::now().naive_utc(),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
-      },
-      "end" => match task.end() {
-        Some(v) => format_date(NaiveDateTime::new(v.date(), v.time()), None),
-        None => "".to_string(),
-      },
-      "status.short" => task.status().to_string().chars().next().unwrap().to_string(),
-      "status" => task.status().to_string(),
-      "priority" => match task.priority() {
-        Some(p) => p.clone(),
-        None => "".to_string(),
-      },
-      "project" => match task.project() {
-        Some(p) => p.to_string(),
-        None => "".to_string(),
+    self.attribute(attribute, task, tasks, Local::now().naive_utc())
+  }
+
+  /// The `indicator` setting for `name`, e.g. `tag.indicator`, or taskwarrior's default
+  fn indicator(&self, name: &str, default: &str) -> String {
+    self
+      .settings
+      .get(&format!("{}.indicator", name))
+      .cloned()
+      .unwrap_or_else(|| default.to_string())
+  }
+
+  /// Renders a date column in `format`, one of the formats of `COLUMN_FORMATS`
+  fn date_attribute(&self, date: Option<NaiveDateTime>, format: &str, now: NaiveDateTime) -> String {
+    let Some(date) = date else {
+      return String::new();
+    };
+    let vague = |from, to| vague_format_date_time(from, to, self.date_time_vague_precise);
+    match format {
+      "julian" => {
+        let julian = date.and_utc().timestamp() as f64 / 86400.0 + 2_440_587.5;
+        let julian = format!("{:.5}", julian);
+        julian.trim_end_matches('0').trim_end_matches('.').to_string()
+      }
+      "epoch" => date.and_utc().timestamp().to_string(),
+      "iso" => date.format("%Y%m%dT%H%M%SZ").to_string(),
+      "age" => vague(date, now),
+      "relative" => vague(now, date),
+      "remaining" if date > now => vague(now, date),
+      "countdown" if date < now => vague(date, now),
+      "remaining" | "countdown" => String::new(),
+      _ => format_date(date, Some(self.date_format.clone())),
+    }
+  }
+
+  /// The annotations of `task`, each as its date followed by its text
+  fn annotation_lines(&self, task: &Task) -> Vec<String> {
+    task
+      .annotations()
+      .into_iter()
+      .flatten()
+      .map(|a| {
+        let text = utils::display_control_chars(a.description());
+        let text = if self.obfuscate { utils::obfuscate(&text) } else { text };
+        format!("{} {}", format_date(**a.entry(), Some(self.date_format.clone())), text)
+      })
+      .collect()
+  }
+
+  fn attribute(&self, attribute: &str, task: &Task, tasks: &[Task], now: NaiveDateTime) -> String {
+    let mut description = utils::display_control_chars(task.description());
+    if self.obfuscate {
+      description = utils::obfuscate(&description);
+    }
+    let (name, format) = attribute.split_once('.').unwrap_or((attribute, ""));
+    let date = |date: Option<&task_hookrs::date::Date>| self.date_attribute(date.map(|d| **d), format, now);
+    let short_uuid = |uuid: &uuid::Uuid| uuid.to_string()[..8].to_string();
+    let count = |n: usize| if n == 0 { String::new() } else { format!("[{}]", n) };
+
+    let value = match (name, format) {
+      ("id", _) => match task.id() {
+        Some(id) if id > 0 => id.to_string(),
+        _ => "-".to_string(),
       },
-      "depends.count" => match task.depends() {
-        Some(v) => {
-          if v.is_empty() {
-            "".to_string()
-          } else {
-            format!("{}", v.len())
-          }
+      ("uuid", "short") => short_uuid(task.uuid()),
+      ("uuid", _) => task.uuid().to_string(),
+      ("due", _) => date(task.due()),
+      ("scheduled", _) => date(task.scheduled()),
+      ("wait", _) => date(task.wait()),
+      ("until", _) => date(task.until()),
+      ("entry", _) => date(Some(task.entry())),
+      ("modified", _) => date(task.modified()),
+      ("start", "active") => {
+        if task.start().is_some() && task.end().is_none() {
+          self.indicator("active", "*")
+        } else {
+          String::new()
         }
-        None => "".to_string(),
-      },
-      "depends" => match task.depends() {
-        Some(v) => {
-          if v.is_empty() {
-            "".to_string()
-          } else {
-            let mut dt = vec![];
-            for u in v {
-              if let Some(t) = tasks.iter().find(|t| t.uuid() == u) {
-                dt.push(t.id().unwrap());
-              }
-            }
-            join(dt.iter().map(ToString::to_string), " ")
+      }
+      ("start", _) => date(task.start()),
+      ("end", _) => date(task.end()),
+      ("status", "short") => task.status().to_string().chars().next().unwrap().to_string(),
+      ("status", _) => task.status().to_string(),
+      ("project", _) => {
+        let project = task.project().map(String::as_str).unwrap_or_default();
+        let project = match format {
+          "parent" => project.split('.').next().unwrap_or_default().to_string(),
+          "indented" => match project.rsplit_once('.') {
+            Some((_, last)) => format!("{}{}", "  ".repeat(project.matches('.').count()), last),
+            None => project.to_string(),
+          },
+          _ => project.to_string(),
+        };
+        if self.obfuscate { utils::obfuscate(&project) } else { project }
+      }
+      ("depends", _) => {
+        let depends = task.depends().map(Vec::as_slice).unwrap_or_default();
+        match format {
+          "count" => count(depends.len()),
+          "indicator" if !depends.is_empty() => self.indicator("dependency", "D"),
+          "indicator" => String::new(),
+          _ => {
+            let ids = depends.iter().filter_map(|u| tasks.iter().find(|t| t.uuid() == u)).map(|t| match t.id() {
+              Some(id) if id > 0 => id.to_string(),
+              _ => short_uuid(t.uuid()),
+            });
+            join(ids, " ")
           }
         }
-        None => "".to_string(),
-      },
-      "tags.count" => match task.tags() {
-        Some(v) => {
-          let t = v.iter().filter(|t| !self.virtual_tags.contains(t)).count();
-          if t == 0 { "".to_string() } else { t.to_string() }
+      }
+      ("tags", _) => {
+        let tags: Vec<&String> = task.tags().into_iter().flatten().filter(|t| !self.virtual_tags.contains(t)).collect();
+        match format {
+          "count" => count(tags.len()),
+          "indicator" if !tags.is_empty() => self.indicator("tag", "+"),
+          "indicator" => String::new(),
+          _ => {
+            let tags = join(tags, " ");
+            if self.obfuscate { utils::obfuscate(&tags) } else { tags }
+          }
         }
-        None => "".to_string(),
-      },
-      "tags" => match task.tags() {
-        Some(v) => v.iter().filter(|t| !self.virtual_tags.contains(t)).cloned().collect::<Vec<_>>().join(","),
-        None => "".to_string(),
-      },
-      "recur" => match task.recur() {
-        Some(v) => v.clone(),
-        None => "".to_string(),
-      },
-      "wait" => match task.wait() {
-        Some(v) => vague_format_date_time(
-          NaiveDateTime::new(v.date(), v.time()),
-          Local::now().naive_utc(),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
-      },
-      "wait.remaining" => match task.wait() {
-        Some(v) => vague_format_date_time(
-          Local::now().naive_utc(),
-          NaiveDateTime::new(v.date(), v.time()),
-          self.date_time_vague_precise,
-        ),
-        None => "".to_string(),
+      }
+      ("recur", "indicator") => match task.recur() {
+        Some(_) => self.indicator("recurrence", "R"),
+        None => String::new(),
       },
-      "description.count" => {
-        let c = if let Some(a) = task.annotations() {
-          format!("[{}]", a.len())
-        } else {
-          Default::default()
-        };
-        format!("{} {}", description.clone(), c)
+      ("recur", _) => task.recur().cloned().unwrap_or_default(),
+      ("parent", "short") => task.parent().map(short_uuid).unwrap_or_default(),
+      ("parent", _) => task.parent().map(ToString::to_string).unwrap_or_default(),
+      ("imask", _) => task.imask().map(ToString::to_string).unwrap_or_default(),
+      ("mask", _) => task.mask().cloned().unwrap_or_default(),
+      ("description", "count") => {
+        let c = count(task.annotations().map_or(0, Vec::len));
+        if c.is_empty() { description } else { format!("{} {}", description, c) }
       }
-      "description.truncated_count" => {
-        let c = if let Some(a) = task.annotations() {
-          format!("[{}]", a.len())
-        } else {
-          Default::default()
-        };
+      ("description", "truncated_count") => {
+        let c = count(task.annotations().map_or(0, Vec::len));
         let d = description.to_string();
         let mut available_width = self.description_width;
         if self.description_width >= c.len() {
@@ -479,7 +600,7 @@ impl TaskReportTable {
         }
         format!("{}{}", d, c)
       }
-      "description.truncated" => {
+      ("description", "truncated") => {
         let d = description.to_string();
         let available_width = self.description_width;
         let (d, _) = d.unicode_truncate(available_width);
@@ -489,21 +610,41 @@ impl TaskReportTable {
         }
         d
       }
-      "description.desc" | "description" => description.clone(),
-      "urgency" => match &task.urgency() {
-        Some(f) => format!("{:.2}", *f),
-        None => "0.00".to_string(),
+      ("description", "oneline") => {
+        let mut words = vec![description];
+        words.extend(self.annotation_lines(task));
+        words.join(" ")
+      }
+      ("description", "desc") => description,
+      ("description", _) => {
+        // Each annotation goes on a line of its own, indented under the description
+        let mut lines = vec![description];
+        lines.extend(self.annotation_lines(task).into_iter().map(|a| format!("  {}", a)));
+        return lines.join("\n");
+      }
+      ("urgency", "integer") => (task.urgency().copied().unwrap_or_default() as i64).to_string(),
+      ("urgency", _) => significant(task.urgency().copied().unwrap_or_default(), 3),
+      ("priority", "indicator") => match task.priority() {
+        Some(_) => self.indicator("uda.priority", "U"),
+        None => String::new(),
       },
-      s => {
-        let u = &task.uda();
-        let v = u.get(s);
-        if v.is_none() {
-          return "".to_string();
+      ("priority", _) => task.priority().cloned().unwrap_or_default(),
+      (name, format) => {
+        let Some(value) = task.uda().get(name) else {
+          return String::new();
+        };
+        if format == "indicator" {
+          return self.indicator(&format!("uda.{}", name), "U");
         }
-        match v.unwrap() {
-          UDAValue::Str(s) => s.to_string(),
-          UDAValue::F64(f) => f.to_string(),
-          UDAValue::U64(u) => u.to_string(),
+        let kind = self.settings.get(&format!("uda.{}.type", name)).map(String::as_str);
+        match (value, kind) {
+          (UDAValue::Str(s), Some("date")) => match NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%SZ") {
+            Ok(d) => self.date_attribute(Some(d), format, now),
+            Err(_) => s.to_string(),
+          },
+          (UDAValue::Str(s), _) => s.to_string(),
+          (UDAValue::F64(f), _) => f.to_string(),
+          (UDAValue::U64(u), _) => u.to_string(),
         }
       }
     };
@@ -511,3 +652,125 @@ impl TaskReportTable {
     utils::display_control_chars(&value)
   }
 }
+
+/// Formats `value` with `digits` significant digits like a C++ stream does, e.g. `12.3` or `0.85`
+fn significant(value: f64, digits: i32) -> String {
+  if value == 0.0 {
+    return "0".to_string();
+  }
+  let magnitude = value.abs().log10().floor() as i32 + 1;
+  let decimals = (digits - magnitude).max(0) as usize;
+  let s = format!("{:.*}", decimals, value);
+  if s.contains('.') {
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+  } else {
+    s
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_column_formats() {
+    let data = r#"[
+      {"id":1,"uuid":"a1b2c3d4-0000-0000-0000-000000000000","description":"Fix bike","entry":"20260301T120000Z","modified":"20260309T120000Z",
+       "status":"pending","project":"home.garage.tools","tags":["next","repair"],"priority":"H","urgency":12.3456,
+       "due":"20260312T120000Z","scheduled":"20260308T120000Z","wait":"20260311T120000Z","until":"20260410T120000Z","start":"20260310T100000Z",
+       "recur":"weekly","parent":"b0000000-0000-0000-0000-000000000000","imask":3,"mask":"--+","depends":["20000000-0000-0000-0000-000000000000"],
+       "annotations":[{"entry":"20260305T120000Z","description":"order chain"}],"estimate":2.5,"review":"20260320T120000Z","size":"L"},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"Buy chain","entry":"20260301T120000Z","status":"pending"},
+      {"id":0,"uuid":"30000000-0000-0000-0000-000000000000","description":"Old","entry":"20260301T120000Z","status":"completed","end":"20260302T120000Z"}
+    ]"#;
+    let tasks = import(data.as_bytes()).unwrap();
+    let show = "report.next.columns id\nreport.next.labels ID\nreport.next.dateformat Y-M-D\ntag.indicator T\nuda.review.type date\n";
+    let mut table = TaskReportTable::new(show, "next", "task").unwrap();
+    table.description_width = 5;
+    let now = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    let cases = [
+      ("id", "1"),
+      ("uuid", "a1b2c3d4-0000-0000-0000-000000000000"),
+      ("uuid.long", "a1b2c3d4-0000-0000-0000-000000000000"),
+      ("uuid.short", "a1b2c3d4"),
+      ("due", "2026-03-12"),
+      ("due.formatted", "2026-03-12"),
+      ("due.julian", "2461112"),
+      ("due.epoch", "1773316800"),
+      ("due.iso", "20260312T120000Z"),
+      ("due.age", "-2d"),
+      ("due.relative", "2d"),
+      ("due.remaining", "2d"),
+      ("due.countdown", ""),
+      ("scheduled.countdown", "2d"),
+      ("scheduled.remaining", ""),
+      ("wait", "2026-03-11"),
+      ("until.remaining", "4w"),
+      ("entry.age", "9d"),
+      ("modified.relative", "-1d"),
+      ("start.age", "2h"),
+      ("start.active", "*"),
+      ("end", ""),
+      ("status", "Pending"),
+      ("status.long", "Pending"),
+      ("status.short", "P"),
+      ("project", "home.garage.tools"),
+      ("project.full", "home.garage.tools"),
+      ("project.parent", "home"),
+      ("project.indented", "    tools"),
+      ("tags", "next repair"),
+      ("tags.list", "next repair"),
+      ("tags.count", "[2]"),
+      ("tags.indicator", "T"),
+      ("depends", "2"),
+      ("depends.list", "2"),
+      ("depends.count", "[1]"),
+      ("depends.indicator", "D"),
+      ("recur", "weekly"),
+      ("recur.duration", "weekly"),
+      ("recur.indicator", "R"),
+      ("parent", "b0000000-0000-0000-0000-000000000000"),
+      ("parent.long", "b0000000-0000-0000-0000-000000000000"),
+      ("parent.short", "b0000000"),
+      ("imask", "3"),
+      ("mask", "--+"),
+      ("description", "Fix bike\n  2026-03-05 order chain"),
+      ("description.desc", "Fix bike"),
+      ("description.oneline", "Fix bike 2026-03-05 order chain"),
+      ("description.combined", "Fix bike\n  2026-03-05 order chain"),
+      ("description.count", "Fix bike [1]"),
+      ("description.truncated", "Fix b\u{2026}"),
+      ("description.truncated_count", "Fi\u{2026}[1]"),
+      ("urgency", "12.3"),
+      ("urgency.real", "12.3"),
+      ("urgency.integer", "12"),
+      ("priority", "H"),
+      ("priority.indicator", "U"),
+      ("estimate", "2.5"),
+      ("review", "2026-03-20"),
+      ("review.relative", "10d"),
+      ("size", "L"),
+      ("size.indicator", "U"),
+    ];
+    for (column, expected) in cases {
+      assert_eq!(table.attribute(column, &tasks[0], &tasks, now), expected, "{}", column);
+    }
+
+    assert_eq!(table.attribute("id", &tasks[2], &tasks, now), "-");
+    assert_eq!(table.attribute("end.age", &tasks[2], &tasks, now), "8d");
+    for column in [
+      "due.iso",
+      "tags.indicator",
+      "depends.count",
+      "recur.indicator",
+      "start.active",
+      "parent.short",
+      "size.indicator",
+    ] {
+      assert_eq!(table.attribute(column, &tasks[1], &tasks, now), "", "{}", column);
+    }
+  }
+}
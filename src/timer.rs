@@ -0,0 +1,133 @@
+use chrono::{Duration, NaiveDateTime};
+use task_hookrs::{task::Task, uda::UDAValue};
+
+/// How long `task` has been running since it was last started, or `None` if it is not active
+pub fn running(task: &Task, now: NaiveDateTime) -> Option<Duration> {
+  task.start().map(|start| (now - **start).max(Duration::zero()))
+}
+
+/// The time spent on `task` so far, including the current run if it is active.
+///
+/// Earlier runs are read from the duration UDA `uda` if it is set, otherwise from the pairs of
+/// `start_annotation` and `stop_annotation` annotations that taskwarrior adds with `journal.time=on`.
+pub fn tracked(task: &Task, now: NaiveDateTime, uda: &str, start_annotation: &str, stop_annotation: &str) -> Duration {
+  let recorded = if uda.is_empty() {
+    let mut annotations: Vec<_> = task.annotations().into_iter().flatten().collect();
+    annotations.sort_by_key(|a| **a.entry());
+    let mut total = Duration::zero();
+    let mut started = None;
+    for annotation in annotations {
+      if annotation.description() == start_annotation {
+        started = Some(**annotation.entry());
+      } else if annotation.description() == stop_annotation
+        && let Some(start) = started.take()
+      {
+        total += **annotation.entry() - start;
+      }
+    }
+    // An unmatched start annotation belongs to the current run, which is counted from `start` below
+    total
+  } else {
+    match task.uda().get(uda) {
+      Some(UDAValue::Str(s)) => parse_duration(s).unwrap_or_else(Duration::zero),
+      Some(UDAValue::U64(n)) => Duration::seconds(*n as i64),
+      Some(UDAValue::F64(n)) => Duration::seconds(*n as i64),
+      None => Duration::zero(),
+    }
+  };
+  recorded + running(task, now).unwrap_or_else(Duration::zero)
+}
+
+/// Parses a duration as taskwarrior exports it, e.g. `PT1H30M` or `P1DT2H`, or a number of seconds
+pub fn parse_duration(value: &str) -> Option<Duration> {
+  let value = value.trim();
+  if let Ok(seconds) = value.parse::<i64>() {
+    return Some(Duration::seconds(seconds));
+  }
+  let rest = value.strip_prefix('P')?;
+  let (date, time) = rest.split_once('T').unwrap_or((rest, ""));
+  let date_units: &[(char, i64)] = &[('Y', 365 * 86400), ('M', 30 * 86400), ('W', 7 * 86400), ('D', 86400)];
+  let time_units: &[(char, i64)] = &[('H', 3600), ('M', 60), ('S', 1)];
+  let mut seconds = 0;
+  for (part, units) in [(date, date_units), (time, time_units)] {
+    let mut number = String::new();
+    for c in part.chars() {
+      if c.is_ascii_digit() {
+        number.push(c);
+        continue;
+      }
+      let (_, unit) = units.iter().find(|(u, _)| *u == c)?;
+      seconds += number.parse::<i64>().ok()? * unit;
+      number.clear();
+    }
+    if !number.is_empty() {
+      return None;
+    }
+  }
+  Some(Duration::seconds(seconds))
+}
+
+/// Formats a duration as hours, minutes and seconds, e.g. `1:02:03`
+pub fn format_duration(duration: Duration) -> String {
+  let seconds = duration.num_seconds().max(0);
+  format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Formats a duration for `task modify`, e.g. `PT3723S`
+pub fn format_iso_duration(duration: Duration) -> String {
+  format!("PT{}S", duration.num_seconds().max(0))
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  fn now() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 3, 10).unwrap().and_hms_opt(12, 0, 0).unwrap()
+  }
+
+  #[test]
+  fn test_tracked_time_from_annotations_and_uda() {
+    let data = r#"[
+      {"id":1,"uuid":"10000000-0000-0000-0000-000000000000","description":"a","entry":"20260101T000000Z","status":"pending",
+       "start":"20260310T113000Z","spent":"PT1H15M",
+       "annotations":[
+         {"entry":"20260309T090000Z","description":"Started task"},
+         {"entry":"20260309T094500Z","description":"Stopped task"},
+         {"entry":"20260309T100000Z","description":"a note"},
+         {"entry":"20260310T080000Z","description":"Started task"},
+         {"entry":"20260310T081000Z","description":"Stopped task"},
+         {"entry":"20260310T113000Z","description":"Started task"}
+       ]},
+      {"id":2,"uuid":"20000000-0000-0000-0000-000000000000","description":"b","entry":"20260101T000000Z","status":"pending"}
+    ]"#;
+    let tasks = import(data.as_bytes()).unwrap();
+
+    assert_eq!(running(&tasks[0], now()), Some(Duration::minutes(30)));
+    assert_eq!(running(&tasks[1], now()), None);
+    assert_eq!(
+      tracked(&tasks[0], now(), "", "Started task", "Stopped task"),
+      Duration::minutes(45 + 10 + 30)
+    );
+    assert_eq!(
+      tracked(&tasks[0], now(), "spent", "Started task", "Stopped task"),
+      Duration::minutes(75 + 30)
+    );
+    assert_eq!(tracked(&tasks[1], now(), "", "Started task", "Stopped task"), Duration::zero());
+  }
+
+  #[test]
+  fn test_durations() {
+    assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+    assert_eq!(parse_duration("P1DT2H"), Some(Duration::hours(26)));
+    assert_eq!(parse_duration("P2W"), Some(Duration::days(14)));
+    assert_eq!(parse_duration("3600"), Some(Duration::hours(1)));
+    assert_eq!(parse_duration("PT1X"), None);
+    assert_eq!(parse_duration("1h"), None);
+    assert_eq!(format_duration(Duration::seconds(26 * 3600 + 62)), "26:01:02");
+    assert_eq!(format_iso_duration(Duration::minutes(2)), "PT120S");
+  }
+}
@@ -1,21 +1,172 @@
-use rustyline::line_buffer::{ChangeListener, DeleteListener, Direction};
+use std::collections::HashSet;
+
+use rustyline::line_buffer::{ChangeListener, DeleteListener, Direction, LineBuffer};
 use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Change {
+  Insert { idx: usize, text: String },
+  Delete { idx: usize, text: String },
+}
 
 /// Undo manager
+///
+/// Records the edits rustyline makes to a `LineBuffer` so that they can be stepped back and
+/// forth with `undo` and `redo`. Typing a word or deleting a run of characters is a single
+/// step, as is replacing the whole line with `LineBuffer::update`.
 #[derive(Default)]
-pub struct Changeset {}
+pub struct Changeset {
+  undos: Vec<Vec<Change>>,
+  redos: Vec<Vec<Change>>,
+  /// Set while typing so that consecutive characters are merged into one step
+  typing: bool,
+  /// Set while undoing or redoing so that the edits made are not recorded again
+  replaying: bool,
+}
+
+impl Changeset {
+  /// Forget the history, e.g. when a prompt is submitted or cancelled
+  pub fn reset(&mut self) {
+    *self = Self::default();
+  }
+
+  pub fn can_undo(&self) -> bool {
+    !self.undos.is_empty()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    !self.redos.is_empty()
+  }
+
+  /// Reverts the last step in `buffer`; returns false if there was nothing to undo
+  pub fn undo(&mut self, buffer: &mut LineBuffer) -> bool {
+    let Some(step) = self.undos.pop() else {
+      return false;
+    };
+    self.replaying = true;
+    for change in step.iter().rev() {
+      match change {
+        Change::Insert { idx, text } => {
+          buffer.delete_range(*idx..idx + text.len(), self);
+          buffer.set_pos(*idx);
+        }
+        Change::Delete { idx, text } => {
+          buffer.insert_str(*idx, text, self);
+          buffer.set_pos(idx + text.len());
+        }
+      }
+    }
+    self.replaying = false;
+    self.typing = false;
+    self.redos.push(step);
+    true
+  }
+
+  /// Re-applies the last undone step in `buffer`; returns false if there was nothing to redo
+  pub fn redo(&mut self, buffer: &mut LineBuffer) -> bool {
+    let Some(step) = self.redos.pop() else {
+      return false;
+    };
+    self.replaying = true;
+    for change in &step {
+      match change {
+        Change::Insert { idx, text } => {
+          buffer.insert_str(*idx, text, self);
+          buffer.set_pos(idx + text.len());
+        }
+        Change::Delete { idx, text } => {
+          buffer.delete_range(*idx..idx + text.len(), self);
+          buffer.set_pos(*idx);
+        }
+      }
+    }
+    self.replaying = false;
+    self.typing = false;
+    self.undos.push(step);
+    true
+  }
+
+  fn record(&mut self, change: Change, typing: bool) {
+    if self.replaying {
+      return;
+    }
+    self.redos.clear();
+    let merged = match (self.undos.last_mut().and_then(|step| step.last_mut()), &change) {
+      // Keep typing the current word
+      (Some(Change::Insert { idx, text }), Change::Insert { idx: i, text: t })
+        if typing && self.typing && *i == *idx + text.len() && !text.ends_with(char::is_whitespace) =>
+      {
+        text.push_str(t);
+        true
+      }
+      // Backspace over a run of characters
+      (Some(Change::Delete { idx, text }), Change::Delete { idx: i, text: t }) if !self.typing && t.chars().count() == 1 && *i + t.len() == *idx => {
+        *idx = *i;
+        text.insert_str(0, t);
+        true
+      }
+      // Delete forward over a run of characters
+      (Some(Change::Delete { idx, text }), Change::Delete { idx: i, text: t }) if !self.typing && t.chars().count() == 1 && *i == *idx => {
+        text.push_str(t);
+        true
+      }
+      _ => false,
+    };
+    if !merged {
+      // `LineBuffer::update` deletes the whole line and then inserts the new one
+      let replaces = matches!(
+        (self.undos.last().and_then(|step| step.last()), &change),
+        (Some(Change::Delete { idx, .. }), Change::Insert { idx: i, .. }) if !typing && !self.typing && idx == i
+      );
+      match self.undos.last_mut() {
+        Some(step) if replaces => step.push(change),
+        _ => self.undos.push(vec![change]),
+      }
+    }
+    self.typing = typing;
+  }
+}
 
 impl DeleteListener for Changeset {
-  fn delete(&mut self, idx: usize, string: &str, _: Direction) {}
+  fn delete(&mut self, idx: usize, string: &str, _: Direction) {
+    if !string.is_empty() {
+      self.record(
+        Change::Delete {
+          idx,
+          text: string.to_string(),
+        },
+        false,
+      );
+    }
+  }
 }
 
 impl ChangeListener for Changeset {
-  fn insert_char(&mut self, idx: usize, c: char) {}
+  fn insert_char(&mut self, idx: usize, c: char) {
+    self.record(Change::Insert { idx, text: c.to_string() }, true);
+  }
 
-  fn insert_str(&mut self, idx: usize, string: &str) {}
+  fn insert_str(&mut self, idx: usize, string: &str) {
+    if !string.is_empty() {
+      self.record(
+        Change::Insert {
+          idx,
+          text: string.to_string(),
+        },
+        false,
+      );
+    }
+  }
 
-  fn replace(&mut self, idx: usize, old: &str, new: &str) {}
+  fn replace(&mut self, idx: usize, old: &str, new: &str) {
+    self.delete(idx, old, Direction::Forward);
+    if !new.is_empty() {
+      // Part of the same step as the deletion above
+      self.typing = false;
+      self.record(Change::Insert { idx, text: new.to_string() }, false);
+    }
+  }
 }
 
 pub fn display_control_chars(text: &str) -> String {
@@ -39,6 +190,88 @@ pub fn display_width(text: &str) -> usize {
   display_control_chars(text).graphemes(true).map(|g| g.width()).sum()
 }
 
+/// Wraps each line of `text` at spaces so that it fits in `width` columns, breaking words that are longer.
+/// Wrapped lines keep the indentation of the line they belong to.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+  let mut lines = vec![];
+  for line in text.split('\n') {
+    let words = line.trim_start_matches(' ');
+    let indent = if line.len() - words.len() < width {
+      &line[..line.len() - words.len()]
+    } else {
+      ""
+    };
+    let mut current = indent.to_string();
+    let mut current_width = indent.len();
+    for (i, word) in words.split(' ').enumerate() {
+      if i > 0 && current_width + 1 + word.width() > width {
+        lines.push(std::mem::replace(&mut current, indent.to_string()));
+        current_width = indent.len();
+      } else if i > 0 {
+        current.push(' ');
+        current_width += 1;
+      }
+      for g in word.graphemes(true) {
+        if current_width + g.width() > width && current_width > indent.len() {
+          lines.push(std::mem::replace(&mut current, indent.to_string()));
+          current_width = indent.len();
+        }
+        current.push_str(g);
+        current_width += g.width();
+      }
+    }
+    lines.push(current);
+  }
+  lines
+}
+
+/// Replaces every letter with `x` or `X`, keeping the case and the width on screen, e.g. for screen sharing
+pub fn obfuscate(text: &str) -> String {
+  let mut obfuscated = String::with_capacity(text.len());
+  for c in text.chars() {
+    if c.is_alphabetic() {
+      let x = if c.is_uppercase() { "X" } else { "x" };
+      obfuscated.push_str(&x.repeat(c.width().unwrap_or(1).max(1)));
+    } else {
+      obfuscated.push(c);
+    }
+  }
+  obfuscated
+}
+
+/// Obfuscates the words of `text` that are in `words`, leaving the rest as it is
+pub fn obfuscate_words(text: &str, words: &HashSet<&str>) -> String {
+  text
+    .split_word_bounds()
+    .map(|word| if words.contains(word) { obfuscate(word) } else { word.to_string() })
+    .collect()
+}
+
+/// Masks the project names and tags of a filter, e.g. `(proj:ACME or +client)` becomes `(proj:XXXX or +xxxxxx)`
+pub fn obfuscate_filter(filter: &str) -> String {
+  filter
+    .split(' ')
+    .map(|term| {
+      let start = term.len() - term.trim_start_matches('(').len();
+      let (parens, rest) = term.split_at(start);
+      if let Some((name, value)) = rest.split_once(':') {
+        let attribute = name.split('.').next().unwrap_or_default();
+        if attribute.len() >= 3 && "project".starts_with(attribute) {
+          return format!("{}{}:{}", parens, name, obfuscate(value));
+        }
+      } else if let Some(tag) = rest.strip_prefix('+').or_else(|| rest.strip_prefix('-'))
+        && tag.starts_with(char::is_alphabetic)
+        // Virtual tags such as +OVERDUE say nothing about the user's data
+        && !tag.chars().all(|c| c.is_ascii_uppercase())
+      {
+        return format!("{}{}{}", parens, &rest[..1], obfuscate(tag));
+      }
+      term.to_string()
+    })
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -48,4 +281,64 @@ mod tests {
     assert_eq!("hello^Jworld^M", display_control_chars("hello\nworld\r"));
     assert_eq!(14, display_width("hello\nworld\r"));
   }
+
+  #[test]
+  fn test_obfuscate() {
+    assert_eq!(obfuscate("Call ACME re: v2.1, +client"), "Xxxx XXXX xx: x2.1, +xxxxxx");
+    assert_eq!(display_width(&obfuscate("Tâche 東京")), display_width("Tâche 東京"));
+
+    let words = HashSet::from(["ACME", "Call"]);
+    assert_eq!(
+      obfuscate_words("Description  Call ACME\nProject  ACMEs", &words),
+      "Description  Xxxx XXXX\nProject  ACMEs"
+    );
+    assert_eq!(
+      obfuscate_filter("(proj:ACME or project.not:Home) -next +OVERDUE status:pending due.before:eow"),
+      "(proj:XXXX or project.not:Xxxx) -xxxx +OVERDUE status:pending due.before:eow"
+    );
+  }
+
+  #[test]
+  fn test_wrap() {
+    assert_eq!(wrap("Fix the bike chain", 10), vec!["Fix the", "bike chain"]);
+    assert_eq!(wrap("  order a new chain", 10), vec!["  order a", "  new", "  chain"]);
+    assert_eq!(wrap("abcdefghijkl", 5), vec!["abcde", "fghij", "kl"]);
+    assert_eq!(wrap("short\n  note", 10), vec!["short", "  note"]);
+  }
+
+  #[test]
+  fn test_changeset_undo_redo() {
+    let mut changes = Changeset::default();
+    let mut buffer = LineBuffer::with_capacity(100);
+    for c in "project:home +work".chars() {
+      buffer.insert(c, 1, &mut changes);
+    }
+    buffer.backspace(1, &mut changes);
+    buffer.backspace(1, &mut changes);
+    assert_eq!(buffer.as_str(), "project:home +wo");
+
+    // The two backspaces are one step, and so is each typed word
+    assert!(changes.undo(&mut buffer));
+    assert_eq!(buffer.as_str(), "project:home +work");
+    assert!(changes.undo(&mut buffer));
+    assert_eq!(buffer.as_str(), "project:home ");
+    assert_eq!(buffer.pos(), "project:home ".len());
+
+    assert!(changes.redo(&mut buffer));
+    assert_eq!(buffer.as_str(), "project:home +work");
+    assert!(changes.redo(&mut buffer));
+    assert_eq!(buffer.as_str(), "project:home +wo");
+    assert!(!changes.redo(&mut buffer));
+
+    // Replacing the line is a single step, and a new edit drops what could be redone
+    buffer.update("status:pending", 14, &mut changes);
+    assert!(changes.undo(&mut buffer));
+    assert_eq!(buffer.as_str(), "project:home +wo");
+    buffer.insert('x', 1, &mut changes);
+    assert!(!changes.can_redo());
+
+    changes.reset();
+    assert!(!changes.can_undo());
+    assert!(!changes.undo(&mut buffer));
+  }
 }